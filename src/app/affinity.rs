@@ -1,48 +1,110 @@
 //! CPU affinity dialog management
 
-use crate::system::{get_process_affinity, get_system_core_count, set_process_affinity};
+use crate::constants::{AFFINITY_CORE_CELL_WIDTH, AFFINITY_MAX_CORES_PER_ROW, DIALOG_MARGIN};
+use crate::system::{get_process_affinity, set_process_affinity};
 
 use super::state::App;
 use super::ViewMode;
 
+/// Works out how many cores fit on one row of the affinity grid.
+///
+/// # Arguments
+/// * `total_cores` - Cores in the processor group
+/// * `screen_width` - Terminal width in columns
+///
+/// # Returns
+/// Cores per row: as many cells as fit inside the dialog's borders and
+/// padding, capped at `AFFINITY_MAX_CORES_PER_ROW` and at the core count.
+pub fn affinity_grid_columns(total_cores: usize, screen_width: usize) -> usize {
+    // Two border columns, plus a space either side of the grid
+    let grid_width = screen_width.saturating_sub(DIALOG_MARGIN + 4);
+    (grid_width / AFFINITY_CORE_CELL_WIDTH)
+        .clamp(1, AFFINITY_MAX_CORES_PER_ROW)
+        .min(total_cores.max(1))
+}
+
 impl App {
-    /// Opens the affinity dialog for the currently viewed process
-    pub fn open_affinity_dialog(&mut self) {
-        let pid = match self.detail_view_pid {
-            Some(pid) => pid,
-            None => return,
+    /// Opens the affinity dialog for the process in the detail view, or
+    /// for the selected process when invoked from the list. With a
+    /// multi-selection in the list, the dialog edits every marked process,
+    /// starting from the first one's current mask.
+    pub fn open_affinity_for_current(&mut self) {
+        if self.detail_view_pid.is_none() && !self.selected_pids.is_empty() {
+            let batch: Vec<(u32, String)> = self
+                .batch_targets()
+                .into_iter()
+                .map(|(pid, name, _)| (pid, name))
+                .collect();
+            if let Some((pid, name)) = batch.first().cloned() {
+                self.open_affinity_dialog(pid, name);
+                if self.view_mode == ViewMode::Affinity {
+                    self.affinity_batch = batch;
+                }
+            }
+            return;
+        }
+
+        let target = match self.detail_view_pid {
+            Some(pid) => Some((pid, self.detail_view_name.clone().unwrap_or_default())),
+            None => self
+                .selected_process()
+                .map(|p| (p.info.pid, p.info.name.clone())),
         };
 
-        let name = self.detail_view_name.clone().unwrap_or_default();
+        if let Some((pid, name)) = target {
+            self.open_affinity_dialog(pid, name);
+        }
+    }
 
+    /// Opens the affinity dialog for a process
+    ///
+    /// # Arguments
+    /// * `pid` - Process ID to edit
+    /// * `name` - Process name shown in the dialog title
+    pub fn open_affinity_dialog(&mut self, pid: u32, name: String) {
         // Get current affinity
-        let current_affinity = get_process_affinity(pid);
-        let total_cores = get_system_core_count();
-
-        let current_mask = match current_affinity {
-            Some(aff) => aff.process_mask,
+        let affinity = match get_process_affinity(pid) {
+            Some(aff) => aff,
             None => {
                 self.error_message = Some("Cannot read process affinity".to_string());
                 return;
             }
         };
 
+        if affinity.spans_groups() {
+            self.error_message = Some(format!(
+                "Cannot edit affinity: process spans {} processor groups",
+                affinity.groups.len()
+            ));
+            return;
+        }
+
         self.view_mode = ViewMode::Affinity;
         self.affinity_pid = Some(pid);
         self.affinity_name = Some(name);
-        self.affinity_mask = current_mask;
-        self.affinity_total_cores = total_cores;
+        self.affinity_mask = affinity.process_mask;
+        self.affinity_total_cores = affinity.total_cores;
+        self.affinity_group = affinity.groups.first().copied().unwrap_or(0);
+        self.affinity_group_count = affinity.system_group_count;
         self.affinity_selected_core = 0;
     }
 
-    /// Closes the affinity dialog without applying changes
+    /// Closes the affinity dialog without applying changes, returning to
+    /// the detail view or process list it was opened from
     pub fn close_affinity_dialog(&mut self) {
-        self.view_mode = ViewMode::DetailView;
+        self.view_mode = if self.detail_view_pid.is_some() {
+            ViewMode::DetailView
+        } else {
+            ViewMode::ProcessList
+        };
         self.affinity_pid = None;
         self.affinity_name = None;
         self.affinity_mask = 0;
         self.affinity_total_cores = 0;
+        self.affinity_group = 0;
+        self.affinity_group_count = 1;
         self.affinity_selected_core = 0;
+        self.affinity_batch.clear();
     }
 
     /// Toggles the selected core in the affinity mask
@@ -63,8 +125,12 @@ impl App {
 
     /// Selects all cores in affinity dialog
     pub fn select_all_cores(&mut self) {
-        let all_mask = (1usize << self.affinity_total_cores) - 1;
-        self.affinity_mask = all_mask;
+        // A full group has 64 cores, where shifting by the width would overflow
+        self.affinity_mask = if self.affinity_total_cores >= usize::BITS {
+            usize::MAX
+        } else {
+            (1usize << self.affinity_total_cores) - 1
+        };
     }
 
     /// Deselects all cores except the first one
@@ -86,8 +152,67 @@ impl App {
         }
     }
 
+    /// Moves selection up one row in affinity dialog, staying put on the
+    /// top row
+    ///
+    /// # Arguments
+    /// * `cores_per_row` - Grid columns, from `affinity_grid_columns`
+    pub fn affinity_move_up(&mut self, cores_per_row: usize) {
+        if self.affinity_selected_core >= cores_per_row {
+            self.affinity_selected_core -= cores_per_row;
+        }
+    }
+
+    /// Moves selection down one row in affinity dialog; from the row above
+    /// a short last row, lands on its last core
+    ///
+    /// # Arguments
+    /// * `cores_per_row` - Grid columns, from `affinity_grid_columns`
+    pub fn affinity_move_down(&mut self, cores_per_row: usize) {
+        let last = (self.affinity_total_cores as usize).saturating_sub(1);
+        let row = self.affinity_selected_core / cores_per_row.max(1);
+        if row < last / cores_per_row.max(1) {
+            self.affinity_selected_core = (self.affinity_selected_core + cores_per_row).min(last);
+        }
+    }
+
+    /// Applies the edited mask to every multi-selected process, reports
+    /// how many succeeded, then clears the selection.
+    fn apply_batch_affinity(&mut self) {
+        let targets = std::mem::take(&mut self.affinity_batch);
+        let mut changed = 0;
+        let mut first_error = None;
+
+        for (pid, name) in &targets {
+            match set_process_affinity(*pid, self.affinity_mask) {
+                Ok(()) => changed += 1,
+                Err(e) if first_error.is_none() => {
+                    first_error = Some(format!("{}: {}", name, e));
+                }
+                Err(_) => {}
+            }
+        }
+
+        let count = self.affinity_mask.count_ones();
+        let cores = format!("{} core{}", count, if count == 1 { "" } else { "s" });
+        self.error_message = Some(match first_error {
+            None => format!("Set affinity of {} of {} processes to {}", changed, targets.len(), cores),
+            Some(e) => format!(
+                "Set affinity of {} of {} processes to {}; failed {}",
+                changed, targets.len(), cores, e
+            ),
+        });
+        self.selected_pids.clear();
+        self.close_affinity_dialog();
+    }
+
     /// Applies the affinity changes
     pub fn apply_affinity(&mut self) {
+        if !self.affinity_batch.is_empty() {
+            self.apply_batch_affinity();
+            return;
+        }
+
         let pid = match self.affinity_pid {
             Some(pid) => pid,
             None => {
@@ -105,8 +230,8 @@ impl App {
                     if count == 1 { "" } else { "s" }
                 ));
                 self.close_affinity_dialog();
-                // Refresh detail view to show new affinity
-                self.refresh_detail_view();
+                // Reload detail view to show new affinity (no-op from the list)
+                self.reload_detail_view();
             }
             Err(e) => {
                 self.error_message = Some(e.to_string());
@@ -119,3 +244,34 @@ impl App {
         (self.affinity_mask >> core) & 1 == 1
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::source::MockProcessSource;
+
+    #[test]
+    fn test_affinity_grid_navigation() {
+        // 80 columns fit five 13-column cells; wide terminals stop at the cap
+        assert_eq!(affinity_grid_columns(64, 80), 5);
+        assert_eq!(affinity_grid_columns(64, 300), AFFINITY_MAX_CORES_PER_ROW);
+        assert_eq!(affinity_grid_columns(2, 300), 2);
+        assert_eq!(affinity_grid_columns(64, 10), 1);
+
+        let mut app = App::with_source(Box::new(MockProcessSource::default()));
+        app.affinity_total_cores = 10;
+        app.affinity_selected_core = 1;
+        app.affinity_move_up(4);
+        assert_eq!(app.affinity_selected_core, 1);
+        app.affinity_move_down(4);
+        assert_eq!(app.affinity_selected_core, 5);
+        // The last row (cores 8-9) is short: land on its last core
+        app.affinity_selected_core = 7;
+        app.affinity_move_down(4);
+        assert_eq!(app.affinity_selected_core, 9);
+        app.affinity_move_down(4);
+        assert_eq!(app.affinity_selected_core, 9);
+        app.affinity_move_up(4);
+        assert_eq!(app.affinity_selected_core, 5);
+    }
+}
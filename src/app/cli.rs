@@ -1,11 +1,29 @@
 //! Command-line argument parsing (manual implementation)
 
 use std::env;
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::PathBuf;
 use std::process;
 
-use crate::constants::{APP_NAME, APP_VERSION, DEFAULT_REFRESH_MS, MAX_REFRESH_MS, MIN_REFRESH_MS};
+use crate::constants::{
+    APP_NAME, APP_VERSION, DEFAULT_CPU_SMOOTHING_ALPHA, DEFAULT_KILL_EXIT_CODE, DEFAULT_NEW_PROCESS_SECS, DEFAULT_REFRESH_MS,
+    EXIT_USAGE, MAX_REFRESH_MS, MIN_REFRESH_MS,
+};
 
-use super::SortColumn;
+use crate::system::ByteUnits;
+
+use super::csv_column::parse_csv_columns;
+use super::export::CsvEncoding;
+use super::{CsvColumn, FilterScope, IdleMode, SortColumn};
+
+/// Output format for non-interactive dumps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text report
+    Text,
+    /// Single-line JSON document
+    Json,
+}
 
 /// Parsed command-line arguments
 #[derive(Debug)]
@@ -14,14 +32,70 @@ pub struct Args {
     pub refresh: u64,
     /// Initial filter string to match process names
     pub filter: Option<String>,
+    /// Fields the filter searches
+    pub filter_scope: FilterScope,
+    /// How processes doing no work are shown
+    pub idle_mode: IdleMode,
+    /// Binary (KiB) or decimal (KB) units for sizes and rates
+    pub units: ByteUnits,
     /// Initial sort column
     pub sort: SortColumn,
-    /// Sort in ascending order (default is descending)
+    /// Sort in ascending order (default depends on the column)
     pub ascending: bool,
     /// Start in tree view mode
     pub tree: bool,
+    /// Start with the compact column layout
+    pub compact: bool,
+    /// Start with alternating row backgrounds
+    pub stripes: bool,
+    /// Show the selected process's path on its own footer line
+    pub path_line: bool,
+    /// Maximum display width of process names (None = fill the row)
+    pub name_width: Option<usize>,
+    /// Maximum display width of the footer path (None = fill the line)
+    pub path_width: Option<usize>,
+    /// Show a line with timings and summed process metrics vs system totals
+    pub debug: bool,
     /// Export to CSV and exit (non-interactive mode)
     pub export: bool,
+    /// Also export connections and modules (implies `export`)
+    pub export_detail: bool,
+    /// Write the CSV export to stdout instead of a file (implies `export`)
+    pub stdout: bool,
+    /// CSV export columns in order (None = default set)
+    pub csv_columns: Option<Vec<CsvColumn>>,
+    /// Directory for export files (None = current directory)
+    pub export_dir: Option<PathBuf>,
+    /// Export filename template (None = timestamped default)
+    pub export_name: Option<String>,
+    /// Text encoding of CSV exports (None = default)
+    pub csv_encoding: Option<CsvEncoding>,
+    /// Kill processes without asking for confirmation
+    pub no_confirm: bool,
+    /// Ask for confirmation before quitting
+    pub confirm_quit: bool,
+    /// Exit code given to killed processes
+    pub kill_code: u32,
+    /// EMA weight for smoothed per-process CPU% (None = raw values)
+    pub smooth: Option<f64>,
+    /// Turn TCP statistics on for every connection so Net/s covers them
+    pub net_stats: bool,
+    /// Highlight processes younger than this many seconds (0 = off)
+    pub highlight_new: u64,
+    /// Print details for this PID and exit (non-interactive mode)
+    pub detail_pid: Option<u32>,
+    /// PID to select (and scroll to) on startup
+    pub select_pid: Option<u32>,
+    /// Whether `--sort` or `--ascending` was given (overrides the saved session)
+    pub sort_given: bool,
+    /// Don't restore or save the session (selection, sort, filter)
+    pub no_session: bool,
+    /// Output format for `--detail`
+    pub format: OutputFormat,
+    /// Serve the process table as JSON over HTTP on this port (no TUI)
+    pub serve_port: Option<u16>,
+    /// Address `--serve` listens on
+    pub bind: IpAddr,
 }
 
 impl Default for Args {
@@ -29,10 +103,38 @@ impl Default for Args {
         Self {
             refresh: DEFAULT_REFRESH_MS,
             filter: None,
+            filter_scope: FilterScope::Name,
+            idle_mode: IdleMode::Show,
+            units: ByteUnits::Binary,
             sort: SortColumn::Cpu,
             ascending: false,
             tree: false,
+            compact: false,
+            stripes: false,
+            path_line: false,
+            name_width: None,
+            path_width: None,
+            debug: false,
             export: false,
+            export_detail: false,
+            stdout: false,
+            csv_columns: None,
+            export_dir: None,
+            export_name: None,
+            csv_encoding: None,
+            no_confirm: false,
+            confirm_quit: false,
+            kill_code: DEFAULT_KILL_EXIT_CODE,
+            smooth: None,
+            net_stats: false,
+            highlight_new: DEFAULT_NEW_PROCESS_SECS,
+            detail_pid: None,
+            select_pid: None,
+            sort_given: false,
+            no_session: false,
+            format: OutputFormat::Text,
+            serve_port: None,
+            bind: IpAddr::V4(Ipv4Addr::LOCALHOST),
         }
     }
 }
@@ -50,12 +152,65 @@ OPTIONS:
     -r, --refresh <MS>     Refresh interval in milliseconds [default: 2000]
                            Range: 250-10000
     -f, --filter <NAME>    Initial filter string to match process names
+        --select <PID>     Start with this process selected (others stay listed)
+        --filter-in <FIELDS>
+                           Fields the filter searches [default: name]
+                           Values: name, path, cmdline
+        --idle <MODE>      How idle processes are shown [default: show]
+                           Values: show, dim, hide
+        --units <UNITS>    Units for sizes and rates [default: binary]
+                           Values: binary (KiB, 1024), decimal (KB, 1000)
     -s, --sort <COLUMN>    Initial sort column [default: cpu]
-                           Values: cpu, memory, name, pid, priority,
-                                   threads, handles, uptime, read, write
-    -a, --ascending        Sort in ascending order (default is descending)
+                           Values: cpu, time, memory, mem%, growth, name,
+                                   pid, session, priority, base, threads,
+                                   handles, children, uptime, read, write, net,
+                                   power
+    -a, --ascending        Sort ascending (default: names/IDs ascending,
+                           metrics descending)
     -t, --tree             Start in tree view mode
+        --compact          Start with the compact column layout
+        --stripes          Start with alternating row backgrounds
+        --path-line        Keep the selected process's path on its own footer
+                           line so messages don't hide it (needs 20+ rows)
+        --name-width <N>   Cut process names at N columns [default: row width]
+        --path-width <N>   Cut the footer path at N columns [default: line width]
+        --debug            Show refresh/render timings, summed process CPU%
+                           vs system CPU and total disk rates (F12 toggles)
     -x, --export           Export to CSV and exit (non-interactive)
+        --export-detail    Also export connections and modules per process
+        --stdout           Write the CSV export to stdout (UTF-8, no BOM)
+                           instead of a file, for piping
+        --csv-columns <LIST>
+                           Comma-separated CSV export columns, in order
+                           Values: pid, name, cpu, time, memory, mem%,
+                                   growth, threads, priority, handles,
+                                   children, session, uptime, read, write,
+                                   net, power, path
+        --csv-encoding <ENC>
+                           CSV text encoding [default: utf8-bom]
+                           Values: utf8, utf8-bom, utf16
+        --export-dir <PATH>
+                           Directory for exports and saved details
+                           [default: current directory]
+        --export-name <TEMPLATE>
+                           Export filename; {{date}}, {{time}} and {{count}}
+                           are filled in; a taken name gets -2, -3, ...
+                           [default: processes_{{date}}_{{time}}]
+        --no-confirm       Kill processes without Y/N confirmation
+        --confirm-quit     Ask Y/N before quitting (Ctrl+C twice forces exit)
+        --no-session       Don't restore or save the last selection, sort and filter
+        --kill-code <N>    Exit code given to killed processes [default: 1]
+        --smooth[=ALPHA]   Smooth per-process CPU% with a moving average
+                           ALPHA is the newest sample's weight, 0-1 [default: 0.3]
+        --net-stats        Turn on TCP statistics for all connections so Net/s
+                           counts them (needs admin; turned off on exit)
+        --detail <PID>     Print details of one process and exit
+        --format <FMT>     Output format for --detail: text, json [default: text]
+        --serve <PORT>     Serve GET /processes as JSON over HTTP (no TUI)
+        --bind <ADDR>      Address for --serve [default: 127.0.0.1]
+        --highlight-new <SECS>
+                           Highlight processes started within SECS [default: 10]
+                           Use 0 to disable
     -h, --help             Print help information
     -V, --version          Print version information
 
@@ -64,22 +219,63 @@ EXAMPLES:
     {} -r 500                   Start with 500ms refresh rate  
     {} -f chrome -s memory      Filter to chrome, sort by memory
     {} --tree                   Start in tree view mode
+    {} --select 1234            Start with PID 1234 selected
     {} --export                 Export all processes to CSV
     {} -f svchost --export      Export filtered processes to CSV
+    {} --export-detail          Export processes, connections and modules
+    {} -x --csv-columns pid,name,memory
+                                Export only PID, name and memory
+    {} -x --export-dir C:\\Temp --export-name snap_{{count}}
+                                Export to C:\\Temp\\snap_<N>.csv
+    {} --detail 1234 --format json
+                                Print one process's details as JSON
+    {} --serve 8080             Serve http://127.0.0.1:8080/processes
+
+EXIT CODES:
+    0         Success
+    1         I/O error (terminal setup, export write)
+    2         Invalid arguments
+    3         Process snapshot failed
+    4         --detail PID not found
 
 CONTROLS:
     q         Quit
     Enter     View process details
     k         Kill selected process
+    h         Kill and keep killing respawns for 5s
+    Space     Mark process for batch kill/suspend/priority/affinity
+    !         Toggle kill confirmation
     p         Suspend/Resume process
+    o         Open containing folder
+    c         Copy executable path
     t         Toggle tree view
+    w         Toggle compact columns
+    v         Toggle row stripes
+    z         Reset per-process CPU average/peak
     +/-       Raise/lower priority
+    a         Set CPU affinity
+    f         Toggle efficiency mode
+    b         Toggle priority boost
     s         Cycle sort column
+    S         Pick sort column from a list
     r         Reverse sort order
-    /         Filter by name
+    x         Freeze row order (metrics keep updating)
+    /         Filter by name (Tab: also path/command line)
+    Ctrl+F    Find: highlight matches without hiding rows
+    F3        Jump to next find match (Shift+F3: previous)
+    u         Show only my processes
+    n         Show only processes with network sockets
+    i         Show/hide Idle/System processes
+    l         Show, dim or hide idle processes
     [/]       Slow down/speed up refresh
-    ?         Show help overlay",
-        APP_NAME, APP_VERSION, APP_NAME, APP_NAME, APP_NAME, APP_NAME, APP_NAME, APP_NAME, APP_NAME
+    F5        Refresh now (also Ctrl+R)
+    ?         Show help overlay
+    d         Toggle disk columns between rate and lifetime total
+    g         Show process counts by CPU/memory band
+    F1        Show system information
+    F12       Toggle the debug line (timings, totals)",
+        APP_NAME, APP_VERSION, APP_NAME, APP_NAME, APP_NAME, APP_NAME, APP_NAME, APP_NAME, APP_NAME,
+        APP_NAME, APP_NAME, APP_NAME, APP_NAME, APP_NAME, APP_NAME
     );
     process::exit(0);
 }
@@ -94,24 +290,167 @@ fn print_version() {
 fn print_error(msg: &str) -> ! {
     eprintln!("error: {}", msg);
     eprintln!("For more information, try '--help'");
-    process::exit(1);
+    process::exit(EXIT_USAGE.into());
 }
 
 /// Parse sort column from string
 fn parse_sort(s: &str) -> SortColumn {
     match s.to_lowercase().as_str() {
         "cpu" => SortColumn::Cpu,
+        "time" | "cpu-time" => SortColumn::CpuTime,
         "memory" | "mem" => SortColumn::Memory,
+        "mem%" | "mem-percent" => SortColumn::MemPercent,
+        "growth" | "mem-growth" => SortColumn::MemGrowth,
         "name" => SortColumn::Name,
         "pid" => SortColumn::Pid,
+        "session" | "sess" => SortColumn::Session,
         "priority" | "prio" => SortColumn::Priority,
+        "base" | "base-priority" => SortColumn::BasePriority,
         "threads" => SortColumn::Threads,
         "handles" => SortColumn::Handles,
+        "children" | "kids" => SortColumn::Children,
         "uptime" => SortColumn::Uptime,
         "read" | "disk-read" => SortColumn::DiskReadRate,
         "write" | "disk-write" => SortColumn::DiskWriteRate,
+        "net" | "network" => SortColumn::Network,
+        "power" => SortColumn::Power,
         _ => print_error(&format!(
-            "invalid sort column '{}'. Valid values: cpu, memory, name, pid, priority, threads, handles, uptime, read, write",
+            "invalid sort column '{}'. Valid values: cpu, time, memory, mem%, growth, name, pid, session, priority, base, threads, handles, children, uptime, read, write, net, power",
+            s
+        )),
+    }
+}
+
+/// Parse a process ID from string
+fn parse_pid(s: &str) -> u32 {
+    match s.parse::<u32>() {
+        Ok(pid) => pid,
+        Err(_) => print_error(&format!("invalid PID '{}'. Must be a number", s)),
+    }
+}
+
+/// Parse output format from string
+fn parse_format(s: &str) -> OutputFormat {
+    match s.to_lowercase().as_str() {
+        "text" | "txt" => OutputFormat::Text,
+        "json" => OutputFormat::Json,
+        _ => print_error(&format!("invalid format '{}'. Valid values: text, json", s)),
+    }
+}
+
+/// Parse the CSV export column list from string
+fn parse_csv_column_list(s: &str) -> Vec<CsvColumn> {
+    match parse_csv_columns(s) {
+        Ok(columns) => columns,
+        Err(bad) => print_error(&format!(
+            "invalid CSV column '{}'. Valid values: pid, name, cpu, time, memory, mem%, growth, threads, priority, handles, children, session, uptime, read, write, net, power, path",
+            bad
+        )),
+    }
+}
+
+/// Parse the CSV text encoding from string
+fn parse_csv_encoding(s: &str) -> CsvEncoding {
+    match CsvEncoding::from_name(s) {
+        Some(encoding) => encoding,
+        None => print_error(&format!(
+            "invalid CSV encoding '{}'. Valid values: utf8, utf8-bom, utf16",
+            s
+        )),
+    }
+}
+
+/// Parse the export filename template, rejecting path separators
+/// (the directory belongs in `--export-dir`)
+fn parse_export_name(s: &str) -> String {
+    if s.is_empty() || s.contains(['/', '\\']) {
+        print_error(&format!(
+            "invalid export name '{}'. Use --export-dir for the directory",
+            s
+        ));
+    }
+    s.to_string()
+}
+
+/// Parse the filter scope from string
+fn parse_filter_scope(s: &str) -> FilterScope {
+    match s.to_lowercase().as_str() {
+        "name" => FilterScope::Name,
+        "path" => FilterScope::Path,
+        "cmdline" | "cmd" | "command-line" => FilterScope::CommandLine,
+        _ => print_error(&format!("invalid filter fields '{}'. Valid values: name, path, cmdline", s)),
+    }
+}
+
+/// Parse the idle process display mode from string
+fn parse_idle_mode(s: &str) -> IdleMode {
+    match IdleMode::from_name(s) {
+        Some(mode) => mode,
+        None => print_error(&format!("invalid idle mode '{}'. Valid values: show, dim, hide", s)),
+    }
+}
+
+/// Parse the `--serve` port from string
+fn parse_port(s: &str) -> u16 {
+    match s.parse::<u16>() {
+        Ok(port) if port > 0 => port,
+        _ => print_error(&format!("invalid port '{}'. Must be a number from 1 to 65535", s)),
+    }
+}
+
+/// Parse the `--bind` address from string
+fn parse_bind(s: &str) -> IpAddr {
+    match s.parse::<IpAddr>() {
+        Ok(addr) => addr,
+        Err(_) => print_error(&format!("invalid address '{}'. Use an IP such as 127.0.0.1 or 0.0.0.0", s)),
+    }
+}
+
+/// Parse the byte unit system from string
+fn parse_units(s: &str) -> ByteUnits {
+    match ByteUnits::from_name(s) {
+        Some(units) => units,
+        None => print_error(&format!("invalid units '{}'. Valid values: binary, decimal", s)),
+    }
+}
+
+/// Parse the new-process highlight threshold (seconds) from string
+fn parse_highlight_new(s: &str) -> u64 {
+    match s.parse::<u64>() {
+        Ok(secs) => secs,
+        Err(_) => print_error(&format!("invalid highlight duration '{}'. Must be a number of seconds", s)),
+    }
+}
+
+/// Parse the exit code given to killed processes
+fn parse_kill_code(s: &str) -> u32 {
+    match s.parse::<u32>() {
+        Ok(code) => code,
+        Err(_) => print_error(&format!(
+            "invalid kill exit code '{}'. Must be a number from 0 to {}",
+            s,
+            u32::MAX
+        )),
+    }
+}
+
+/// Parse a `--name-width`/`--path-width` limit from string
+fn parse_width_limit(s: &str, flag: &str) -> usize {
+    match s.parse::<usize>() {
+        Ok(columns) if columns > 0 => columns,
+        _ => print_error(&format!(
+            "invalid {} '{}'. Must be a positive number of columns",
+            flag, s
+        )),
+    }
+}
+
+/// Parse the CPU smoothing weight from string
+fn parse_smooth(s: &str) -> f64 {
+    match s.parse::<f64>() {
+        Ok(alpha) if alpha > 0.0 && alpha <= 1.0 => alpha,
+        _ => print_error(&format!(
+            "invalid smoothing weight '{}'. Must be greater than 0 and at most 1",
             s
         )),
     }
@@ -140,9 +479,29 @@ pub fn parse_args() -> Args {
         match arg.as_str() {
             "-h" | "--help" => print_help(),
             "-V" | "--version" => print_version(),
-            "-a" | "--ascending" => args.ascending = true,
+            "-a" | "--ascending" => {
+                args.ascending = true;
+                args.sort_given = true;
+            }
             "-t" | "--tree" => args.tree = true,
+            "--compact" => args.compact = true,
+            "--stripes" => args.stripes = true,
+            "--path-line" => args.path_line = true,
+            "--debug" => args.debug = true,
             "-x" | "--export" => args.export = true,
+            "--stdout" => {
+                args.export = true;
+                args.stdout = true;
+            }
+            "--export-detail" => {
+                args.export = true;
+                args.export_detail = true;
+            }
+            "--no-confirm" => args.no_confirm = true,
+            "--smooth" => args.smooth = Some(DEFAULT_CPU_SMOOTHING_ALPHA),
+            "--confirm-quit" => args.confirm_quit = true,
+            "--no-session" => args.no_session = true,
+            "--net-stats" => args.net_stats = true,
             
             "-r" | "--refresh" => {
                 if argv.is_empty() {
@@ -158,11 +517,124 @@ pub fn parse_args() -> Args {
                 args.filter = Some(argv.remove(0));
             }
             
+            "--csv-columns" => {
+                if argv.is_empty() {
+                    print_error("--csv-columns requires a value");
+                }
+                args.csv_columns = Some(parse_csv_column_list(&argv.remove(0)));
+            }
+            
+            "--csv-encoding" => {
+                if argv.is_empty() {
+                    print_error("--csv-encoding requires a value");
+                }
+                args.csv_encoding = Some(parse_csv_encoding(&argv.remove(0)));
+            }
+            
+            "--export-dir" => {
+                if argv.is_empty() {
+                    print_error("--export-dir requires a path");
+                }
+                args.export_dir = Some(PathBuf::from(argv.remove(0)));
+            }
+            
+            "--export-name" => {
+                if argv.is_empty() {
+                    print_error("--export-name requires a template");
+                }
+                args.export_name = Some(parse_export_name(&argv.remove(0)));
+            }
+            
+            "--filter-in" => {
+                if argv.is_empty() {
+                    print_error("--filter-in requires a value");
+                }
+                args.filter_scope = parse_filter_scope(&argv.remove(0));
+            }
+            
+            "--name-width" => {
+                if argv.is_empty() {
+                    print_error("--name-width requires a value");
+                }
+                args.name_width = Some(parse_width_limit(&argv.remove(0), "--name-width"));
+            }
+            
+            "--path-width" => {
+                if argv.is_empty() {
+                    print_error("--path-width requires a value");
+                }
+                args.path_width = Some(parse_width_limit(&argv.remove(0), "--path-width"));
+            }
+            
+            "--idle" => {
+                if argv.is_empty() {
+                    print_error("--idle requires a value");
+                }
+                args.idle_mode = parse_idle_mode(&argv.remove(0));
+            }
+            
+            "--units" => {
+                if argv.is_empty() {
+                    print_error("--units requires a value");
+                }
+                args.units = parse_units(&argv.remove(0));
+            }
+            
+            "--serve" => {
+                if argv.is_empty() {
+                    print_error("--serve requires a port");
+                }
+                args.serve_port = Some(parse_port(&argv.remove(0)));
+            }
+            
+            "--bind" => {
+                if argv.is_empty() {
+                    print_error("--bind requires an address");
+                }
+                args.bind = parse_bind(&argv.remove(0));
+            }
+            
+            "--detail" => {
+                if argv.is_empty() {
+                    print_error("--detail requires a PID");
+                }
+                args.detail_pid = Some(parse_pid(&argv.remove(0)));
+            }
+            
+            "--select" => {
+                if argv.is_empty() {
+                    print_error("--select requires a PID");
+                }
+                args.select_pid = Some(parse_pid(&argv.remove(0)));
+            }
+            
+            "--format" => {
+                if argv.is_empty() {
+                    print_error("--format requires a value");
+                }
+                args.format = parse_format(&argv.remove(0));
+            }
+            
+            "--kill-code" => {
+                if argv.is_empty() {
+                    print_error("--kill-code requires a value");
+                }
+                args.kill_code = parse_kill_code(&argv.remove(0));
+            }
+            
+            "--highlight-new" => {
+                if argv.is_empty() {
+                    print_error("--highlight-new requires a value");
+                }
+                args.highlight_new = parse_highlight_new(&argv.remove(0));
+            }
+            
             "-s" | "--sort" => {
                 if argv.is_empty() {
                     print_error("--sort requires a value");
                 }
                 args.sort = parse_sort(&argv.remove(0));
+                args.sort_given = true;
             }
             
             // Handle combined short flags like -at or -ta
@@ -182,7 +654,27 @@ pub fn parse_args() -> Args {
                 match key {
                     "--refresh" => args.refresh = parse_refresh(value),
                     "--filter" => args.filter = Some(value.to_string()),
-                    "--sort" => args.sort = parse_sort(value),
+                    "--filter-in" => args.filter_scope = parse_filter_scope(value),
+                    "--idle" => args.idle_mode = parse_idle_mode(value),
+                    "--units" => args.units = parse_units(value),
+                    "--serve" => args.serve_port = Some(parse_port(value)),
+                    "--bind" => args.bind = parse_bind(value),
+                    "--name-width" => args.name_width = Some(parse_width_limit(value, key)),
+                    "--path-width" => args.path_width = Some(parse_width_limit(value, key)),
+                    "--csv-columns" => args.csv_columns = Some(parse_csv_column_list(value)),
+                    "--csv-encoding" => args.csv_encoding = Some(parse_csv_encoding(value)),
+                    "--export-dir" => args.export_dir = Some(PathBuf::from(value)),
+                    "--export-name" => args.export_name = Some(parse_export_name(value)),
+                    "--sort" => {
+                        args.sort = parse_sort(value);
+                        args.sort_given = true;
+                    }
+                    "--highlight-new" => args.highlight_new = parse_highlight_new(value),
+                    "--kill-code" => args.kill_code = parse_kill_code(value),
+                    "--smooth" => args.smooth = Some(parse_smooth(value)),
+                    "--detail" => args.detail_pid = Some(parse_pid(value)),
+                    "--select" => args.select_pid = Some(parse_pid(value)),
+                    "--format" => args.format = parse_format(value),
                     _ => print_error(&format!("unknown option '{}'", key)),
                 }
             }
@@ -197,5 +689,15 @@ pub fn parse_args() -> Args {
         }
     }
     
+    if args.stdout && args.export_detail {
+        print_error("--stdout cannot be combined with --export-detail");
+    }
+    if args.stdout && args.csv_encoding.is_some() {
+        print_error("--stdout cannot be combined with --csv-encoding (stdout is always UTF-8)");
+    }
+    if args.serve_port.is_some() && (args.export || args.detail_pid.is_some()) {
+        print_error("--serve cannot be combined with --export or --detail");
+    }
+    
     args
 }
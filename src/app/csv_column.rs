@@ -0,0 +1,162 @@
+//! Columns available in the CSV process export
+
+use crate::constants::BYTES_PER_MB;
+
+use super::ProcessEntry;
+
+/// A column of the CSV process export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvColumn {
+    Pid,
+    Name,
+    Cpu,
+    CpuTime,
+    Memory,
+    MemPercent,
+    MemGrowth,
+    Threads,
+    Priority,
+    Handles,
+    Children,
+    Session,
+    Uptime,
+    DiskReadRate,
+    DiskWriteRate,
+    Network,
+    Power,
+    Path,
+}
+
+/// Columns exported when `--csv-columns` is not given
+pub const DEFAULT_CSV_COLUMNS: &[CsvColumn] = &[
+    CsvColumn::Pid,
+    CsvColumn::Name,
+    CsvColumn::Cpu,
+    CsvColumn::Memory,
+    CsvColumn::Threads,
+    CsvColumn::Priority,
+    CsvColumn::Handles,
+    CsvColumn::Uptime,
+    CsvColumn::DiskReadRate,
+    CsvColumn::DiskWriteRate,
+    CsvColumn::Path,
+];
+
+impl CsvColumn {
+    /// Parses a column name as accepted by `--csv-columns`.
+    ///
+    /// # Returns
+    /// `None` for an unknown name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name.trim().to_lowercase().as_str() {
+            "pid" => CsvColumn::Pid,
+            "name" => CsvColumn::Name,
+            "cpu" => CsvColumn::Cpu,
+            "time" | "cpu-time" => CsvColumn::CpuTime,
+            "memory" | "mem" => CsvColumn::Memory,
+            "mem%" | "mem-percent" => CsvColumn::MemPercent,
+            "growth" | "mem-growth" => CsvColumn::MemGrowth,
+            "threads" => CsvColumn::Threads,
+            "priority" | "prio" => CsvColumn::Priority,
+            "handles" => CsvColumn::Handles,
+            "children" | "kids" => CsvColumn::Children,
+            "session" | "sess" => CsvColumn::Session,
+            "uptime" => CsvColumn::Uptime,
+            "read" | "disk-read" => CsvColumn::DiskReadRate,
+            "write" | "disk-write" => CsvColumn::DiskWriteRate,
+            "net" | "network" => CsvColumn::Network,
+            "power" => CsvColumn::Power,
+            "path" => CsvColumn::Path,
+            _ => return None,
+        })
+    }
+
+    /// Gets the CSV header cell for this column.
+    pub fn header(self) -> &'static str {
+        match self {
+            CsvColumn::Pid => "PID",
+            CsvColumn::Name => "Name",
+            CsvColumn::Cpu => "CPU%",
+            CsvColumn::CpuTime => "CPUTime(s)",
+            CsvColumn::Memory => "Memory(MB)",
+            CsvColumn::MemPercent => "Memory%",
+            CsvColumn::MemGrowth => "MemGrowth(B/s)",
+            CsvColumn::Threads => "Threads",
+            CsvColumn::Priority => "Priority",
+            CsvColumn::Handles => "Handles",
+            CsvColumn::Children => "Children",
+            CsvColumn::Session => "Session",
+            CsvColumn::Uptime => "Uptime(s)",
+            CsvColumn::DiskReadRate => "DiskRead/s",
+            CsvColumn::DiskWriteRate => "DiskWrite/s",
+            CsvColumn::Network => "Net/s",
+            CsvColumn::Power => "Power",
+            CsvColumn::Path => "Path",
+        }
+    }
+
+    /// Formats this column's value for a process (not yet CSV-escaped).
+    pub fn value(self, proc: &ProcessEntry) -> String {
+        match self {
+            CsvColumn::Pid => proc.info.pid.to_string(),
+            CsvColumn::Name => proc.info.name.clone(),
+            CsvColumn::Cpu => format!("{:.2}", proc.cpu_percent),
+            CsvColumn::CpuTime => proc.cpu_time_seconds.to_string(),
+            CsvColumn::Memory => format!("{:.2}", proc.memory_bytes as f64 / BYTES_PER_MB),
+            CsvColumn::MemPercent => format!("{:.2}", proc.mem_percent),
+            CsvColumn::MemGrowth => format!("{:.0}", proc.memory_growth_rate),
+            CsvColumn::Threads => proc.thread_count.to_string(),
+            CsvColumn::Priority => proc.priority.name().to_string(),
+            CsvColumn::Handles => proc.handle_count.to_string(),
+            CsvColumn::Children => proc.child_count.to_string(),
+            CsvColumn::Session => proc.session_id.map(|id| id.to_string()).unwrap_or_default(),
+            CsvColumn::Uptime => proc.uptime_seconds.to_string(),
+            CsvColumn::DiskReadRate => format!("{:.0}", proc.disk_read_rate),
+            CsvColumn::DiskWriteRate => format!("{:.0}", proc.disk_write_rate),
+            CsvColumn::Network => format!("{:.0}", proc.net_recv_rate + proc.net_send_rate),
+            CsvColumn::Power => proc.power_usage().name().to_string(),
+            CsvColumn::Path => proc.path.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Parses a comma-separated column list such as "pid,name,memory".
+///
+/// # Returns
+/// * `Ok(Vec<CsvColumn>)` - The columns in the given order
+/// * `Err(String)` - The first unknown name, or an empty list
+pub fn parse_csv_columns(list: &str) -> Result<Vec<CsvColumn>, String> {
+    let columns = list
+        .split(',')
+        .filter(|name| !name.trim().is_empty())
+        .map(|name| CsvColumn::from_name(name).ok_or_else(|| name.trim().to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    if columns.is_empty() {
+        return Err(list.to_string());
+    }
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_header_unchanged() {
+        let header: Vec<&str> = DEFAULT_CSV_COLUMNS.iter().map(|c| c.header()).collect();
+        assert_eq!(
+            header.join(","),
+            "PID,Name,CPU%,Memory(MB),Threads,Priority,Handles,Uptime(s),DiskRead/s,DiskWrite/s,Path"
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_columns() {
+        assert_eq!(
+            parse_csv_columns("pid, Name,mem"),
+            Ok(vec![CsvColumn::Pid, CsvColumn::Name, CsvColumn::Memory])
+        );
+        assert_eq!(parse_csv_columns("pid,bogus"), Err("bogus".to_string()));
+        assert!(parse_csv_columns(",").is_err());
+    }
+}
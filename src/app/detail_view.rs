@@ -1,109 +1,250 @@
 //! Detail view management
 
+use crate::constants::DETAIL_SLOW_REFRESH_TICKS;
 use crate::system::{
-    get_process_affinity, get_process_command_line, get_process_modules,
-    get_process_tcp_connections, get_process_udp_endpoints, ProcessDetails,
+    get_app_user_model_id, get_package_family_name, get_priority_boost, get_process_affinity,
+    get_process_command_line, get_process_io_priority, get_process_modules,
+    get_process_protection_name,
+    get_process_services, get_process_tcp_connections, get_process_udp_endpoints,
+    get_signature_info, get_thread_cpu_times, query_process_io_priority, set_process_io_priority, IoPriority, ProcessDetails,
 };
 
 use super::state::App;
-use super::ViewMode;
+use super::{ProcessEntry, ViewMode};
+
+/// Gathers full details for a process, including the expensive
+/// per-PID queries (modules, connections, services, command line, affinity,
+/// thread CPU times).
+fn gather_details(process: &ProcessEntry) -> ProcessDetails {
+    let pid = process.info.pid;
+
+    let mut details = ProcessDetails {
+        pid,
+        name: process.info.name.clone(),
+        path: process.path.clone(),
+        command_line: get_process_command_line(pid),
+        services: get_process_services(pid),
+        is_32bit: process.is_32bit,
+        being_debugged: process.being_debugged,
+        efficiency_mode: process.efficiency_mode,
+        priority_boost: get_priority_boost(pid),
+        package_family: get_package_family_name(pid),
+        app_user_model_id: get_app_user_model_id(pid),
+        session_id: process.session_id,
+        modules: get_process_modules(pid),
+        tcp_connections: get_process_tcp_connections(pid),
+        udp_endpoints: get_process_udp_endpoints(pid),
+        cpu_percent: 0.0,
+        cpu_avg_percent: 0.0,
+        cpu_peak_percent: 0.0,
+        cpu_time_seconds: 0,
+        memory_bytes: 0,
+        mem_percent: 0.0,
+        memory_access_denied: false,
+        query_access_denied: false,
+        thread_count: 0,
+        handle_count: 0,
+        priority: String::new(),
+        base_priority: 0,
+        uptime_seconds: 0,
+        disk_read_rate: 0.0,
+        disk_write_rate: 0.0,
+        net_recv_rate: 0.0,
+        net_send_rate: 0.0,
+        cpu_affinity: get_process_affinity(pid).map(|a| a.format()),
+        io_priority: get_process_io_priority(pid),
+        protection: get_process_protection_name(pid),
+        thread_cpu: get_thread_cpu_times(pid),
+        signature: None,
+    };
+    update_metrics(&mut details, process);
+    details
+}
+
+/// Copies the cheap, per-refresh metrics from a process entry
+fn update_metrics(details: &mut ProcessDetails, process: &ProcessEntry) {
+    details.cpu_percent = process.cpu_percent;
+    details.cpu_avg_percent = process.cpu_avg_percent;
+    details.cpu_peak_percent = process.cpu_peak_percent;
+    details.cpu_time_seconds = process.cpu_time_seconds;
+    details.memory_bytes = process.memory_bytes;
+    details.mem_percent = process.mem_percent;
+    details.memory_access_denied = process.memory_access_denied;
+    details.query_access_denied = process.query_access_denied;
+    details.thread_count = process.thread_count;
+    details.handle_count = process.handle_count;
+    details.priority = process.priority.short_name().to_string();
+    details.base_priority = process.info.base_priority;
+    details.uptime_seconds = process.uptime_seconds;
+    details.disk_read_rate = process.disk_read_rate;
+    details.disk_write_rate = process.disk_write_rate;
+    details.net_recv_rate = process.net_recv_rate;
+    details.net_send_rate = process.net_send_rate;
+    details.efficiency_mode = process.efficiency_mode;
+}
 
 impl App {
+    /// Gathers full details for a process by PID from the current list.
+    ///
+    /// # Returns
+    /// `None` if no process with this PID was seen in the last refresh.
+    pub fn process_details(&self, pid: u32) -> Option<ProcessDetails> {
+        let mut details = self.processes.iter().find(|p| p.info.pid == pid).map(gather_details)?;
+        // One-off report: no point caching the signature
+        details.signature = details.path.as_deref().and_then(get_signature_info);
+        Some(details)
+    }
+
+    /// Fills in the signature of the viewed executable, verifying each
+    /// path only once per session since hashing large files is slow.
+    fn fill_signature(&mut self, details: &mut ProcessDetails) {
+        let path = match &details.path {
+            Some(path) => path,
+            None => return,
+        };
+        details.signature = self
+            .signatures
+            .entry(path.clone())
+            .or_insert_with(|| get_signature_info(path))
+            .clone();
+    }
+
     /// Opens detail view for the currently selected process
     pub fn open_detail_view(&mut self) {
-        if self.filtered_processes.is_empty() {
-            return;
-        }
-
-        let process = &self.filtered_processes[self.selected_index];
+        let process = match self.selected_process() {
+            Some(process) => process,
+            None => return,
+        };
         let pid = process.info.pid;
         let name = process.info.name.clone();
-
-        let modules = get_process_modules(pid);
-        let tcp_connections = get_process_tcp_connections(pid);
-        let udp_endpoints = get_process_udp_endpoints(pid);
-        let command_line = get_process_command_line(pid);
-        let cpu_affinity = get_process_affinity(pid).map(|a| a.format());
-
-        let details = ProcessDetails {
-            pid,
-            name: name.clone(),
-            path: process.path.clone(),
-            command_line,
-            modules,
-            tcp_connections,
-            udp_endpoints,
-            cpu_percent: process.cpu_percent,
-            memory_bytes: process.memory_bytes,
-            thread_count: process.thread_count,
-            handle_count: process.handle_count,
-            priority: process.priority.short_name().to_string(),
-            uptime_seconds: process.uptime_seconds,
-            disk_read_rate: process.disk_read_rate,
-            disk_write_rate: process.disk_write_rate,
-            cpu_affinity,
-        };
+        let mut details = gather_details(process);
+        self.fill_signature(&mut details);
 
         self.view_mode = ViewMode::DetailView;
         self.detail_view_pid = Some(pid);
         self.detail_view_name = Some(name);
         self.detail_view_data = Some(details);
-        self.detail_scroll_offset = 0;
+        // Return to where this process was last scrolled to
+        self.detail_scroll_offset = self.saved_detail_scroll(pid);
+        self.detail_refresh_ticks = 0;
+        self.detail_view_exited = false;
+        // The module search is per process; the sort order carries over
+        self.module_view.filter.clear();
+        self.module_view.filter_input = false;
     }
 
     /// Closes the detail view and returns to process list
     pub fn close_detail_view(&mut self) {
+        if let Some(pid) = self.detail_view_pid {
+            self.save_detail_scroll(pid, self.detail_scroll_offset);
+        }
         self.view_mode = ViewMode::ProcessList;
         self.detail_view_pid = None;
         self.detail_view_name = None;
         self.detail_view_data = None;
         self.detail_scroll_offset = 0;
+        self.detail_view_exited = false;
     }
 
-    /// Refreshes the detail view data for the currently viewed process
+    /// Refreshes the detail view data for the currently viewed process.
+    ///
+    /// Live metrics (CPU, memory, I/O rates) update on every call, while
+    /// modules, connections, and affinity are only re-fetched every
+    /// `DETAIL_SLOW_REFRESH_TICKS` calls since they are expensive to query.
+    /// The scroll position is preserved.
     pub fn refresh_detail_view(&mut self) {
+        self.detail_refresh_ticks += 1;
+        let full_refresh = self.detail_refresh_ticks >= DETAIL_SLOW_REFRESH_TICKS;
+        if full_refresh {
+            self.detail_refresh_ticks = 0;
+        }
+        self.update_detail_view(full_refresh);
+    }
+
+    /// Re-fetches all detail view data immediately (e.g. after changing
+    /// a setting that only the expensive queries would pick up).
+    pub fn reload_detail_view(&mut self) {
+        self.update_detail_view(true);
+    }
+
+    /// Updates the detail view from the latest process list.
+    ///
+    /// Once the process has exited the last data is kept frozen until the
+    /// view is closed, even if Windows hands the PID to a new process.
+    fn update_detail_view(&mut self, full_refresh: bool) {
         let pid = match self.detail_view_pid {
-            Some(pid) => pid,
-            None => return,
+            Some(pid) if !self.detail_view_exited => pid,
+            _ => return,
         };
 
         // Find the process in the updated list to get current metrics
         let process = self.processes.iter().find(|p| p.info.pid == pid);
         
         if let Some(process) = process {
-            let modules = get_process_modules(pid);
-            let tcp_connections = get_process_tcp_connections(pid);
-            let udp_endpoints = get_process_udp_endpoints(pid);
-            let command_line = get_process_command_line(pid);
-            let cpu_affinity = get_process_affinity(pid).map(|a| a.format());
-
-            let details = ProcessDetails {
-                pid,
-                name: process.info.name.clone(),
-                path: process.path.clone(),
-                command_line,
-                modules,
-                tcp_connections,
-                udp_endpoints,
-                cpu_percent: process.cpu_percent,
-                memory_bytes: process.memory_bytes,
-                thread_count: process.thread_count,
-                handle_count: process.handle_count,
-                priority: process.priority.short_name().to_string(),
-                uptime_seconds: process.uptime_seconds,
-                disk_read_rate: process.disk_read_rate,
-                disk_write_rate: process.disk_write_rate,
-                cpu_affinity,
-            };
-
-            self.detail_view_data = Some(details);
+            match self.detail_view_data {
+                Some(ref mut details) if !full_refresh => update_metrics(details, process),
+                _ => {
+                    let mut details = gather_details(process);
+                    self.fill_signature(&mut details);
+                    self.detail_view_data = Some(details);
+                }
+            }
         } else {
-            // Process no longer exists - close detail view
-            self.error_message = Some("Process no longer exists".to_string());
-            self.close_detail_view();
+            // Keep the last-known details readable until Esc
+            self.detail_view_exited = true;
+            self.error_message = Some("Process exited; showing last-known details".to_string());
         }
     }
 
+    /// Cycles the I/O priority of the viewed process
+    /// (Very Low → Low → Normal) and re-reads it for display.
+    pub fn cycle_io_priority(&mut self) {
+        let pid = match self.detail_view_pid {
+            Some(pid) => pid,
+            None => return,
+        };
+        let name = self.detail_view_name.clone().unwrap_or_default();
+
+        let current = query_process_io_priority(pid).unwrap_or(IoPriority::Normal);
+        let new_priority = current.cycle();
+
+        match set_process_io_priority(pid, new_priority) {
+            Ok(()) => {
+                self.error_message = Some(format!(
+                    "{}: I/O {} → {}",
+                    name, current.name(), new_priority.name()
+                ));
+                if let Some(ref mut details) = self.detail_view_data {
+                    details.io_priority = get_process_io_priority(pid);
+                }
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to set I/O priority: {}", e));
+            }
+        }
+    }
+
+    /// Returns true (and says so) if the viewed process has exited, so
+    /// actions are not sent to a process that may now own its PID.
+    pub(super) fn detail_process_exited(&mut self) -> bool {
+        if self.detail_view_exited {
+            self.error_message = Some("Process has exited".to_string());
+        }
+        self.detail_view_exited
+    }
+
+    /// Cycles the module list order (load order → name → address).
+    pub fn cycle_module_sort(&mut self) {
+        self.module_view.sort = self.module_view.sort.next();
+    }
+
+    /// Starts typing a module search. The modules are the last section,
+    /// so the view scrolls to the end to show them.
+    pub fn start_module_filter(&mut self) {
+        self.module_view.filter_input = true;
+        self.detail_scroll_offset = usize::MAX; // Will be clamped during render
+    }
+
     /// Scrolls the detail view down
     pub fn detail_scroll_down(&mut self) {
         if let Some(ref details) = self.detail_view_data {
@@ -137,7 +278,10 @@ impl App {
 
     /// Counts the total number of lines in detail view
     fn count_detail_lines(&self, details: &ProcessDetails) -> usize {
-        let mut count = 7; // Header + basic info lines
+        let mut count = 9; // Header + basic info lines
+        if !details.services.is_empty() {
+            count += 1;
+        }
 
         // Modules section
         count += 2; // Header + separator
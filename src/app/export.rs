@@ -1,24 +1,169 @@
-//! CSV export functionality
+//! CSV and text report export functionality
 
-use std::fs::File;
-use std::io::{self, BufWriter, Write};
-use std::path::PathBuf;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
 use windows::Win32::System::SystemInformation::GetLocalTime;
 
-use crate::constants::BYTES_PER_MB;
+use crate::system::{
+    format_bytes, format_cpu_time, format_on_off, format_rate, format_session, format_uptime,
+    get_process_modules, get_process_tcp_connections, get_process_udp_endpoints, ByteUnits,
+    ProcessDetails,
+};
 
+use super::csv_column::CsvColumn;
 use super::ProcessEntry;
 
-/// Generates a timestamped filename for the export
-fn generate_filename() -> String {
-    let st = unsafe { GetLocalTime() };
-    
-    format!(
-        "processes_{:04}-{:02}-{:02}_{:02}{:02}{:02}.csv",
-        st.wYear, st.wMonth, st.wDay,
-        st.wHour, st.wMinute, st.wSecond
-    )
+/// Filename template used when `--export-name` is not given
+pub const DEFAULT_EXPORT_NAME: &str = "processes_{date}_{time}";
+
+/// Text encoding of CSV exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CsvEncoding {
+    /// Plain UTF-8
+    Utf8,
+    /// UTF-8 with a byte order mark, so Excel detects the encoding
+    #[default]
+    Utf8Bom,
+    /// UTF-16LE with a byte order mark
+    Utf16,
+}
+
+impl CsvEncoding {
+    /// Parses an encoding name as accepted by `--csv-encoding`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "utf8" | "utf-8" => Some(CsvEncoding::Utf8),
+            "utf8-bom" | "utf-8-bom" => Some(CsvEncoding::Utf8Bom),
+            "utf16" | "utf-16" | "utf16le" | "utf-16le" => Some(CsvEncoding::Utf16),
+            _ => None,
+        }
+    }
+
+    /// Encodes UTF-8 text as file contents in this encoding.
+    fn encode(self, text: &str) -> Vec<u8> {
+        match self {
+            CsvEncoding::Utf8 => text.as_bytes().to_vec(),
+            CsvEncoding::Utf8Bom => {
+                let mut bytes = vec![0xEF, 0xBB, 0xBF];
+                bytes.extend_from_slice(text.as_bytes());
+                bytes
+            }
+            CsvEncoding::Utf16 => {
+                let mut bytes = vec![0xFF, 0xFE];
+                bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+                bytes
+            }
+        }
+    }
+}
+
+/// Where export files are written, how they are named and encoded
+#[derive(Debug, Clone, Default)]
+pub struct ExportTarget {
+    /// Directory for export files (None = current directory)
+    pub dir: Option<PathBuf>,
+    /// Filename template with `{date}`, `{time}` and `{count}` placeholders
+    /// (None = `DEFAULT_EXPORT_NAME`)
+    pub name_template: Option<String>,
+    /// Encoding of CSV files (text reports are always UTF-8)
+    pub csv_encoding: CsvEncoding,
+}
+
+impl ExportTarget {
+    /// Builds the path of a new export file, creating the export directory
+    /// if it does not exist yet.
+    ///
+    /// # Arguments
+    /// * `suffix` - Appended before the extension (e.g. "_detail")
+    /// * `extension` - File extension without the dot
+    /// * `count` - Number of processes exported, for `{count}`
+    fn path(&self, suffix: &str, extension: &str, count: usize) -> io::Result<PathBuf> {
+        let st = unsafe { GetLocalTime() };
+        let date = format!("{:04}-{:02}-{:02}", st.wYear, st.wMonth, st.wDay);
+        let time = format!("{:02}{:02}{:02}", st.wHour, st.wMinute, st.wSecond);
+        let template = self.name_template.as_deref().unwrap_or(DEFAULT_EXPORT_NAME);
+        let filename = expand_name_template(template, &date, &time, count, suffix, extension);
+
+        match self.dir {
+            Some(ref dir) => {
+                fs::create_dir_all(dir).map_err(|e| with_path(e, dir))?;
+                Ok(dir.join(filename))
+            }
+            None => Ok(PathBuf::from(filename)),
+        }
+    }
+}
+
+/// Fills in a filename template and adds the suffix and extension.
+///
+/// An extension already present in the template (e.g. "snap.csv") is kept
+/// at the end rather than doubled.
+fn expand_name_template(
+    template: &str,
+    date: &str,
+    time: &str,
+    count: usize,
+    suffix: &str,
+    extension: &str,
+) -> String {
+    let name = template
+        .replace("{date}", date)
+        .replace("{time}", time)
+        .replace("{count}", &count.to_string());
+    let dotted = format!(".{}", extension);
+    let stem = name.strip_suffix(dotted.as_str()).unwrap_or(&name);
+    format!("{}{}{}", stem, suffix, dotted)
+}
+
+/// Adds the path to an I/O error, so "Access is denied" says where.
+fn with_path(e: io::Error, path: &Path) -> io::Error {
+    io::Error::new(e.kind(), format!("{}: {}", path.display(), e))
+}
+
+/// Adds a counter to a filename before its extension ("a.csv" -> "a-2.csv").
+fn numbered_path(path: &Path, n: u32) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{}-{}.{}", stem, n, ext.to_string_lossy()),
+        None => format!("{}-{}", stem, n),
+    };
+    path.with_file_name(name)
+}
+
+/// Creates a new export file, naming it in the error on failure.
+///
+/// An existing file is never overwritten: if the name is taken (a name
+/// template without `{time}`, or two exports in the same second), "-2",
+/// "-3", ... is added before the extension until a free name is found.
+///
+/// # Returns
+/// The open file and the path actually used.
+fn create_export_file(path: &Path) -> io::Result<(File, PathBuf)> {
+    let mut candidate = path.to_path_buf();
+    let mut n = 2;
+    loop {
+        match OpenOptions::new().write(true).create_new(true).open(&candidate) {
+            Ok(file) => return Ok((file, candidate)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                candidate = numbered_path(path, n);
+                n += 1;
+            }
+            Err(e) => return Err(with_path(e, &candidate)),
+        }
+    }
+}
+
+/// Writes CSV text (built as UTF-8) to a new file in the target's encoding.
+///
+/// # Returns
+/// The path actually written, which may have a counter added.
+fn write_csv_file(path: &Path, csv: &[u8], encoding: CsvEncoding) -> io::Result<PathBuf> {
+    let text = String::from_utf8_lossy(csv);
+    let (mut file, path) = create_export_file(path)?;
+    file.write_all(&encoding.encode(&text)).map_err(|e| with_path(e, &path))?;
+    Ok(path)
 }
 
 /// Escapes a string for CSV format
@@ -31,45 +176,226 @@ fn escape_csv(s: &str) -> String {
     }
 }
 
-/// Exports the process list to a CSV file
+/// Exports the process list to a CSV file with the given columns, in order
+/// Returns the path to the exported file on success
+pub fn export_to_csv(
+    processes: &[ProcessEntry],
+    columns: &[CsvColumn],
+    target: &ExportTarget,
+) -> io::Result<PathBuf> {
+    let path = target.path("", "csv", processes.len())?;
+    
+    // Built in memory, then encoded as a whole
+    let mut writer: Vec<u8> = Vec::new();
+    write_process_csv(&mut writer, processes, columns)?;
+    write_csv_file(&path, &writer, target.csv_encoding)
+}
+
+/// Writes the process list as UTF-8 CSV with the given columns, in order.
+///
+/// # Arguments
+/// * `writer` - Destination, e.g. a buffer or `io::stdout()`
+/// * `processes` - Rows to write
+/// * `columns` - Columns to write, in order
+pub fn write_process_csv(
+    writer: &mut dyn Write,
+    processes: &[ProcessEntry],
+    columns: &[CsvColumn],
+) -> io::Result<()> {
+    // Write CSV header
+    let header: Vec<&str> = columns.iter().map(|c| c.header()).collect();
+    writeln!(writer, "{}", header.join(","))?;
+    
+    // Write each process
+    for proc in processes {
+        let row: Vec<String> = columns.iter().map(|c| escape_csv(&c.value(proc))).collect();
+        writeln!(writer, "{}", row.join(","))?;
+    }
+    
+    Ok(())
+}
+
+/// Exports per-process network connections and loaded modules to CSV.
+///
+/// Writes one row per TCP connection, UDP endpoint, or module, tagged by
+/// `Kind`. These are gathered here rather than on refresh because they
+/// require a separate query per process.
 /// Returns the path to the exported file on success
-pub fn export_to_csv(processes: &[ProcessEntry]) -> io::Result<PathBuf> {
-    let filename = generate_filename();
-    let path = PathBuf::from(&filename);
+pub fn export_details_to_csv(
+    processes: &[ProcessEntry],
+    target: &ExportTarget,
+) -> io::Result<PathBuf> {
+    let path = target.path("_detail", "csv", processes.len())?;
     
-    let file = File::create(&path)?;
-    let mut writer = BufWriter::new(file);
+    // Built in memory, then encoded as a whole
+    let mut writer: Vec<u8> = Vec::new();
     
     // Write CSV header
     writeln!(
         writer,
-        "PID,Name,CPU%,Memory(MB),Threads,Priority,Handles,Uptime(s),DiskRead/s,DiskWrite/s,Path"
+        "PID,Name,Kind,LocalAddress,RemoteAddress,State,Module,ModulePath,BaseAddress"
     )?;
     
-    // Write each process
     for proc in processes {
+        let pid = proc.info.pid;
         let name = escape_csv(&proc.info.name);
-        let path_str = proc.path.as_deref().unwrap_or("");
-        let path_escaped = escape_csv(path_str);
         
-        writeln!(
-            writer,
-            "{},{},{:.2},{:.2},{},{},{},{},{:.0},{:.0},{}",
-            proc.info.pid,
-            name,
-            proc.cpu_percent,
-            proc.memory_bytes as f64 / BYTES_PER_MB,
-            proc.thread_count,
-            proc.priority.name(),
-            proc.handle_count,
-            proc.uptime_seconds,
-            proc.disk_read_rate,
-            proc.disk_write_rate,
-            path_escaped,
-        )?;
+        for conn in get_process_tcp_connections(pid) {
+            writeln!(
+                writer,
+                "{},{},TCP,{}:{},{}:{},{},,,",
+                pid,
+                name,
+                conn.local_addr,
+                conn.local_port,
+                conn.remote_addr,
+                conn.remote_port,
+                conn.state,
+            )?;
+        }
+        
+        for ep in get_process_udp_endpoints(pid) {
+            writeln!(
+                writer,
+                "{},{},UDP,{}:{},,,,,",
+                pid,
+                name,
+                ep.local_addr,
+                ep.local_port,
+            )?;
+        }
+        
+        for module in get_process_modules(pid) {
+            writeln!(
+                writer,
+                "{},{},Module,,,,{},{},0x{:016X}",
+                pid,
+                name,
+                escape_csv(&module.name),
+                escape_csv(&module.path),
+                module.base_address,
+            )?;
+        }
     }
     
-    writer.flush()?;
+    write_csv_file(&path, &writer, target.csv_encoding)
+}
+
+/// Formats process details as a plain-text report, section by section
+/// in the same order as the detail view, with sizes in the given units.
+pub fn format_details_report(details: &ProcessDetails, units: ByteUnits) -> String {
+    let mut lines: Vec<String> = Vec::new();
+
+    lines.push(format!("Process Details: {} (PID: {})", details.name, details.pid));
+    lines.push(String::new());
+
+    lines.push("== Basic Information ==".to_string());
+    lines.push(format!("Name:          {}", details.name));
+    lines.push(format!("PID:           {}", details.pid));
+    lines.push(format!("Path:          {}", details.path.as_deref().unwrap_or("<access denied>")));
+    lines.push(format!("Command:       {}", details.command_line.as_deref().unwrap_or("<access denied>")));
+    if !details.services.is_empty() {
+        lines.push(format!("Services:      {}", details.services.join(", ")));
+    }
+    lines.push(format!("Arch:          {}", match details.is_32bit {
+        Some(true) => "32-bit (WOW64)",
+        Some(false) => "Native",
+        None => "Unknown",
+    }));
+    lines.push(format!("Session:       {}", format_session(details.session_id)));
+    lines.push(format!("Package:       {}", details.package_family.as_deref().unwrap_or("None (classic app)")));
+    if let Some(ref aumid) = details.app_user_model_id {
+        lines.push(format!("App ID:        {}", aumid));
+    }
+    lines.push(format!("Debugger:      {}", match details.being_debugged {
+        Some(true) => "Attached",
+        Some(false) => "None",
+        None => "Unknown",
+    }));
+    lines.push(format!("Efficiency:    {}", format_on_off(details.efficiency_mode)));
+    lines.push(format!("Prio Boost:    {}", format_on_off(details.priority_boost)));
+    lines.push(format!("Protection:    {}", details.protection.as_deref().unwrap_or("None")));
+    lines.push(format!(
+        "Signature:     {}",
+        details.signature.as_ref().map_or_else(|| "Unknown".to_string(), |s| s.describe())
+    ));
+    lines.push(format!("Priority:      {}", details.priority));
+    lines.push(format!("Base Priority: {}", details.base_priority));
+    lines.push(format!("Uptime:        {}", format_uptime(details.uptime_seconds)));
+    lines.push(String::new());
+
+    lines.push("== Resource Usage ==".to_string());
+    let denied = "<access denied>".to_string();
+    let (cpu, cpu_time, handles) = if details.query_access_denied {
+        (denied.clone(), denied.clone(), denied.clone())
+    } else {
+        (
+            format!(
+                "{:.1}% (avg {:.1}%, peak {:.1}%)",
+                details.cpu_percent, details.cpu_avg_percent, details.cpu_peak_percent
+            ),
+            format_cpu_time(details.cpu_time_seconds),
+            details.handle_count.to_string(),
+        )
+    };
+    let memory = if details.memory_access_denied {
+        denied
+    } else {
+        format!("{} ({:.1}% of RAM)", format_bytes(details.memory_bytes, units), details.mem_percent)
+    };
+    lines.push(format!("CPU:           {}", cpu));
+    lines.push(format!("CPU Time:      {}", cpu_time));
+    lines.push(format!("Memory:        {}", memory));
+    lines.push(format!("Threads:       {}", details.thread_count));
+    if let Some(times) = &details.thread_cpu {
+        lines.push(format!("Thread CPU:    {}", times.summary()));
+    }
+    lines.push(format!("Handles:       {}", handles));
+    lines.push(format!("CPU Affinity:  {}", details.cpu_affinity.as_deref().unwrap_or("Unknown")));
+    lines.push(format!("I/O Priority:  {}", details.io_priority.as_deref().unwrap_or("Unknown")));
+    lines.push(format!("Disk Read:     {}", format_rate(details.disk_read_rate, units)));
+    lines.push(format!("Disk Write:    {}", format_rate(details.disk_write_rate, units)));
+    lines.push(format!("Net Recv:      {}", format_rate(details.net_recv_rate, units)));
+    lines.push(format!("Net Send:      {}", format_rate(details.net_send_rate, units)));
+    lines.push(String::new());
+
+    lines.push(format!(
+        "== Network Connections ({} TCP, {} UDP) ==",
+        details.tcp_connections.len(),
+        details.udp_endpoints.len()
+    ));
+    for conn in &details.tcp_connections {
+        lines.push(format!(
+            "TCP  {}:{} -> {}:{}  [{}]",
+            conn.local_addr, conn.local_port, conn.remote_addr, conn.remote_port, conn.state
+        ));
+    }
+    for ep in &details.udp_endpoints {
+        lines.push(format!("UDP  {}:{}", ep.local_addr, ep.local_port));
+    }
+    lines.push(String::new());
+
+    lines.push(format!("== Loaded Modules ({}) ==", details.modules.len()));
+    for module in &details.modules {
+        lines.push(format!("0x{:016X}  {}", module.base_address, module.path));
+    }
+
+    let mut report = lines.join("\r\n");
+    report.push_str("\r\n");
+    report
+}
+
+/// Writes a process's details to a timestamped text file
+/// Returns the path to the written file on success
+pub fn export_details_to_text(
+    details: &ProcessDetails,
+    target: &ExportTarget,
+    units: ByteUnits,
+) -> io::Result<PathBuf> {
+    let path = target.path(&format!("_pid{}", details.pid), "txt", 1)?;
+    
+    let (mut file, path) = create_export_file(&path)?;
+    file.write_all(format_details_report(details, units).as_bytes())?;
     
     Ok(path)
 }
@@ -77,13 +403,13 @@ pub fn export_to_csv(processes: &[ProcessEntry]) -> io::Result<PathBuf> {
 impl super::App {
     /// Exports the current (filtered) process list to CSV
     pub fn export_processes(&mut self) {
-        let processes = if self.filtered_processes.is_empty() && self.filter.is_empty() {
+        let processes = if self.filtered_processes.is_empty() && !self.is_filtered() {
             &self.processes
         } else {
             &self.filtered_processes
         };
         
-        match export_to_csv(processes) {
+        match export_to_csv(processes, &self.csv_columns, &self.export_target) {
             Ok(path) => {
                 self.error_message = Some(format!(
                     "Exported {} processes to {}",
@@ -96,4 +422,112 @@ impl super::App {
             }
         }
     }
+
+    /// Saves the detail view's process details to a text file
+    pub fn export_detail_view(&mut self) {
+        let details = match self.detail_view_data {
+            Some(ref d) => d,
+            None => return,
+        };
+        
+        match export_details_to_text(details, &self.export_target, self.units) {
+            Ok(path) => {
+                self.error_message = Some(format!(
+                    "Saved details of {} to {}",
+                    details.name,
+                    path.display()
+                ));
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Save failed: {}", e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::source::MockProcessSource;
+    use crate::app::App;
+
+    #[test]
+    fn test_export_never_overwrites() {
+        let dir = std::env::temp_dir().join(format!("tm_export_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("snap.csv");
+
+        let (_, first) = create_export_file(&path).unwrap();
+        let (_, second) = create_export_file(&path).unwrap();
+        let (_, third) = create_export_file(&path).unwrap();
+        assert_eq!(first, path);
+        assert_eq!(second, dir.join("snap-2.csv"));
+        assert_eq!(third, dir.join("snap-3.csv"));
+        assert_eq!(numbered_path(Path::new("report"), 2), Path::new("report-2"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_details_report_rates() {
+        let pid = std::process::id();
+        let source = MockProcessSource::default().with_process(pid, 1, "self.exe", 1024);
+        let mut app = App::with_source(Box::new(source));
+        app.refresh();
+        let mut details = app.process_details(pid).unwrap();
+        details.disk_read_rate = 1_532_871.0;
+        details.net_send_rate = 1_500.0;
+
+        // Rates are scaled like the detail view, in the chosen units
+        let report = format_details_report(&details, ByteUnits::Binary);
+        assert!(report.contains("Disk Read:     1.5 MiB/s\r\n"));
+        assert!(report.contains("Net Send:      1.5 KiB/s\r\n"));
+        let report = format_details_report(&details, ByteUnits::Decimal);
+        assert!(report.contains("Disk Read:     1.5 MB/s\r\n"));
+        assert!(report.contains("Net Recv:      0 B/s\r\n"));
+    }
+
+    #[test]
+    fn test_write_process_csv() {
+        let source = MockProcessSource::default()
+            .with_process(101, 1, "plain.exe", 1024)
+            .with_process(103, 1, "odd, name.exe", 1024);
+        let mut app = App::with_source(Box::new(source));
+        app.refresh();
+
+        let mut out: Vec<u8> = Vec::new();
+        write_process_csv(&mut out, &app.processes, &[CsvColumn::Pid, CsvColumn::Name]).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let mut lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.remove(0), "PID,Name");
+        lines.sort();
+        assert_eq!(lines, ["101,plain.exe", "103,\"odd, name.exe\""]);
+    }
+
+    #[test]
+    fn test_expand_name_template() {
+        // The default template reproduces the original names
+        assert_eq!(
+            expand_name_template(DEFAULT_EXPORT_NAME, "2024-05-01", "093000", 12, "", "csv"),
+            "processes_2024-05-01_093000.csv"
+        );
+        assert_eq!(
+            expand_name_template("snap_{count}", "d", "t", 12, "_detail", "csv"),
+            "snap_12_detail.csv"
+        );
+        // An extension in the template is not doubled
+        assert_eq!(
+            expand_name_template("{date}.csv", "2024-05-01", "t", 0, "", "csv"),
+            "2024-05-01.csv"
+        );
+    }
+
+    #[test]
+    fn test_csv_encoding() {
+        assert_eq!(CsvEncoding::Utf8.encode("Ж"), [0xD0, 0x96]);
+        assert_eq!(CsvEncoding::Utf8Bom.encode("Ж"), [0xEF, 0xBB, 0xBF, 0xD0, 0x96]);
+        assert_eq!(CsvEncoding::Utf16.encode("Ж,"), [0xFF, 0xFE, 0x16, 0x04, 0x2C, 0x00]);
+        assert_eq!(CsvEncoding::from_name("UTF16"), Some(CsvEncoding::Utf16));
+        assert_eq!(CsvEncoding::from_name("latin1"), None);
+    }
 }
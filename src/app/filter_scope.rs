@@ -0,0 +1,227 @@
+//! Which process fields the name filter searches
+
+use crate::system::Priority;
+
+/// Fields matched by the filter string
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FilterScope {
+    /// Process name only
+    #[default]
+    Name,
+    /// Process name and executable path
+    Path,
+    /// Process name, executable path and command line
+    CommandLine,
+}
+
+impl FilterScope {
+    /// Cycles to the next scope (Name → Path → CommandLine → Name).
+    pub fn next(self) -> Self {
+        match self {
+            FilterScope::Name => FilterScope::Path,
+            FilterScope::Path => FilterScope::CommandLine,
+            FilterScope::CommandLine => FilterScope::Name,
+        }
+    }
+
+    /// Gets the short label shown in the filter bar.
+    pub fn label(self) -> &'static str {
+        match self {
+            FilterScope::Name => "name",
+            FilterScope::Path => "name+path",
+            FilterScope::CommandLine => "name+path+cmd",
+        }
+    }
+
+    /// Returns true if the executable path is searched
+    pub fn includes_path(self) -> bool {
+        matches!(self, FilterScope::Path | FilterScope::CommandLine)
+    }
+
+    /// Returns true if the command line is searched
+    pub fn includes_command_line(self) -> bool {
+        matches!(self, FilterScope::CommandLine)
+    }
+}
+
+/// Parses a `pid:` filter term into inclusive PID ranges.
+///
+/// Accepts single PIDs and ranges separated by commas, e.g.
+/// `pid:1234,5678` or `pid:1000-2000,4321`.
+///
+/// # Returns
+/// * `Option<Vec<(u32, u32)>>` - None if the term is not a valid `pid:` term
+pub fn parse_pid_term(term: &str) -> Option<Vec<(u32, u32)>> {
+    let list = term.strip_prefix("pid:")?;
+    list.split(',')
+        .map(|part| match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end): (u32, u32) = (start.parse().ok()?, end.parse().ok()?);
+                Some((start.min(end), start.max(end)))
+            }
+            None => part.parse().ok().map(|pid| (pid, pid)),
+        })
+        .collect()
+}
+
+/// Checks a `priority:` filter term against a priority class.
+///
+/// Accepts full or short names (spaces dropped, any case) separated by
+/// commas, e.g. `priority:high,realtime` or `priority:rt,abovenormal`.
+///
+/// # Returns
+/// * `Option<bool>` - Whether the priority is listed, or None if the term
+///   is not a valid `priority:` term
+pub fn priority_term_matches(term: &str, priority: Priority) -> Option<bool> {
+    let list = term.strip_prefix("priority:")?;
+    let full = priority.name().replace(' ', "").to_lowercase();
+    let short = priority.short_name().to_lowercase();
+    let mut matched = false;
+    for name in list.split(',') {
+        let known = ALL_PRIORITIES.iter().any(|p| {
+            p.name().replace(' ', "").eq_ignore_ascii_case(name) || p.short_name().eq_ignore_ascii_case(name)
+        });
+        if !known {
+            return None;
+        }
+        matched |= name == full || name == short;
+    }
+    Some(matched)
+}
+
+/// Priority classes a `priority:` term may name
+const ALL_PRIORITIES: [Priority; 6] = [
+    Priority::Idle,
+    Priority::BelowNormal,
+    Priority::Normal,
+    Priority::AboveNormal,
+    Priority::High,
+    Priority::Realtime,
+];
+
+/// Checks a lowercased filter against a process.
+///
+/// The filter is split on whitespace and every term must match, so
+/// "python script.py" matches a process named `python.exe` whose command
+/// line mentions `script.py`. A `pid:` term (see `parse_pid_term`) matches
+/// the PID, a `priority:` term (see `priority_term_matches`) the priority
+/// class; any other term must appear in at least one field. An all-digit
+/// term also matches PIDs containing it, so "123" finds PID 1234 without
+/// needing `pid:`.
+///
+/// A leading `!` negates a term, so `!svchost` keeps everything but
+/// svchost and `!pid:4` drops PID 4. A lone `!` is searched for literally,
+/// and `!!` stands for a literal `!` at the start of a term.
+///
+/// # Arguments
+/// * `filter_lower` - Lowercased filter text
+/// * `pid` - The process ID
+/// * `priority` - The process's priority class
+/// * `fields` - Searched field values (any case)
+pub fn matches_filter(filter_lower: &str, pid: u32, priority: Priority, fields: &[&str]) -> bool {
+    let fields: Vec<String> = fields.iter().map(|f| f.to_lowercase()).collect();
+    filter_lower.split_whitespace().all(|term| {
+        let (negated, term) = split_negation(term);
+        let matched = if let Some(ranges) = parse_pid_term(term) {
+            ranges.iter().any(|&(start, end)| (start..=end).contains(&pid))
+        } else if let Some(matched) = priority_term_matches(term, priority) {
+            matched
+        } else {
+            let pid_matches = term.bytes().all(|b| b.is_ascii_digit()) && pid.to_string().contains(term);
+            pid_matches || fields.iter().any(|f| f.contains(term))
+        };
+        matched != negated
+    })
+}
+
+/// Splits a filter term into whether it is negated and the term to match.
+fn split_negation(term: &str) -> (bool, &str) {
+    if term.starts_with("!!") {
+        // Escaped: drop one "!" and match the rest literally
+        return (false, &term[1..]);
+    }
+    match term.strip_prefix('!') {
+        Some(rest) if !rest.is_empty() => (true, rest),
+        _ => (false, term),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const N: Priority = Priority::Normal;
+
+    #[test]
+    fn test_matches_filter() {
+        let name = "python.exe";
+        let cmd = r#""C:\Python312\python.exe" C:\work\script.py --verbose"#;
+
+        assert!(matches_filter("pyth", 1, N, &[name]));
+        assert!(matches_filter("", 1, N, &[name]));
+        assert!(!matches_filter("python script.py", 1, N, &[name]));
+        assert!(matches_filter("python script.py", 1, N, &[name, cmd]));
+        assert!(!matches_filter("python other.py", 1, N, &[name, cmd]));
+
+        // Digits match the PID as well as the name
+        assert!(matches_filter("123", 41234, N, &[name]));
+        assert!(matches_filter("123", 1, N, &["app123.exe"]));
+        assert!(!matches_filter("123", 1324, N, &[name]));
+        assert!(!matches_filter("!123", 1234, N, &[name]));
+    }
+
+    #[test]
+    fn test_negated_filter() {
+        let name = "svchost.exe";
+        assert!(!matches_filter("!svchost", 1, N, &[name]));
+        assert!(matches_filter("!svchost", 1, N, &["chrome.exe"]));
+        assert!(!matches_filter("!pid:4", 4, N, &[name]));
+        assert!(matches_filter("host !pid:4", 8, N, &[name]));
+
+        // A lone "!" and "!!" prefixes are matched literally
+        assert!(matches_filter("!", 1, N, &["wow!.exe"]));
+        assert!(!matches_filter("!", 1, N, &[name]));
+        assert!(matches_filter("!!x", 1, N, &["!x.exe"]));
+        assert!(!matches_filter("!!x", 1, N, &["x.exe"]));
+    }
+
+    #[test]
+    fn test_pid_filter() {
+        assert_eq!(parse_pid_term("pid:1234,5678"), Some(vec![(1234, 1234), (5678, 5678)]));
+        assert_eq!(parse_pid_term("pid:2000-1000"), Some(vec![(1000, 2000)]));
+        assert_eq!(parse_pid_term("pid:12x"), None);
+        assert_eq!(parse_pid_term("python"), None);
+
+        let name = "python.exe";
+        assert!(matches_filter("pid:1000-2000", 1500, N, &[name]));
+        assert!(!matches_filter("pid:1000-2000", 2001, N, &[name]));
+        assert!(matches_filter("pid:42,1500 pyth", 1500, N, &[name]));
+        assert!(!matches_filter("pid:1500 node", 1500, N, &[name]));
+    }
+
+    #[test]
+    fn test_priority_filter() {
+        let name = "game.exe";
+        assert_eq!(priority_term_matches("priority:high", Priority::High), Some(true));
+        assert_eq!(priority_term_matches("priority:high,realtime", Priority::Realtime), Some(true));
+        assert_eq!(priority_term_matches("priority:rt", Priority::Realtime), Some(true));
+        assert_eq!(priority_term_matches("priority:abovenormal", Priority::AboveNormal), Some(true));
+        // Exact names: "normal" does not match Below Normal
+        assert_eq!(priority_term_matches("priority:normal", Priority::BelowNormal), Some(false));
+        assert_eq!(priority_term_matches("priority:hihg", Priority::High), None);
+        assert_eq!(priority_term_matches("high", Priority::High), None);
+
+        assert!(matches_filter("priority:high game", 1, Priority::High, &[name]));
+        assert!(!matches_filter("priority:high", 1, Priority::Normal, &[name]));
+        assert!(matches_filter("!priority:normal", 1, Priority::High, &[name]));
+    }
+
+    #[test]
+    fn test_scope_cycle() {
+        assert_eq!(FilterScope::Name.next(), FilterScope::Path);
+        assert_eq!(FilterScope::Path.next(), FilterScope::CommandLine);
+        assert_eq!(FilterScope::CommandLine.next(), FilterScope::Name);
+        assert!(!FilterScope::Name.includes_path());
+        assert!(FilterScope::CommandLine.includes_command_line());
+    }
+}
@@ -0,0 +1,105 @@
+//! Find: highlight matching rows and jump between them
+//!
+//! Unlike the filter, find keeps every row visible. Rows whose name
+//! contains the find text (case-insensitive), or whose PID equals it, are
+//! highlighted, and F3 / Shift+F3 move the selection to the next or
+//! previous match, wrapping around the list. The find text is kept
+//! separately from the filter, so the two can be combined.
+
+use super::state::App;
+use super::{ProcessEntry, ViewMode};
+
+impl App {
+    /// Starts typing find text, keeping the previous text for editing.
+    pub fn start_find(&mut self) {
+        self.view_mode = ViewMode::FindInput;
+    }
+
+    /// Checks whether a row matches the find text.
+    ///
+    /// # Returns
+    /// `false` while no find text is set.
+    pub fn is_find_match(&self, entry: &ProcessEntry) -> bool {
+        let term = self.find.trim();
+        if term.is_empty() {
+            return false;
+        }
+        entry.info.name.to_lowercase().contains(&term.to_lowercase())
+            || entry.info.pid.to_string() == term
+    }
+
+    /// Selects the next (or previous) matching row, wrapping around the
+    /// list. The search starts after the selected row, so repeated presses
+    /// step through every match.
+    ///
+    /// # Arguments
+    /// * `forward` - Search down the list (true) or up (false)
+    pub fn find_next(&mut self, forward: bool) {
+        if self.find.trim().is_empty() {
+            self.error_message = Some("Nothing to find; press Ctrl+F to search".to_string());
+            return;
+        }
+        let len = self.filtered_processes.len();
+        let found = (1..=len)
+            .map(|step| {
+                if forward {
+                    (self.selected_index + step) % len
+                } else {
+                    (self.selected_index + len - step % len) % len
+                }
+            })
+            .find(|&i| self.is_find_match(&self.filtered_processes[i]));
+        match found {
+            Some(index) => self.selected_index = index,
+            None => self.error_message = Some(format!("No match for \"{}\"", self.find.trim())),
+        }
+    }
+
+    /// Stops finding and removes the highlight.
+    pub fn clear_find(&mut self) {
+        self.find.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::source::MockProcessSource;
+    use crate::app::SortColumn;
+
+    #[test]
+    fn test_find_next() {
+        let source = MockProcessSource::default()
+            .with_process(101, 1, "chrome.exe", 1024)
+            .with_process(103, 1, "notepad.exe", 1024)
+            .with_process(105, 1, "Chrome.exe", 1024)
+            .with_process(107, 1, "explorer.exe", 1024);
+        let mut app = App::with_source(Box::new(source));
+        app.sort_column = SortColumn::Pid;
+        app.sort_ascending = true;
+        app.refresh();
+        let selected_pid = |app: &App| app.filtered_processes[app.selected_index].info.pid;
+
+        app.find = "chrome".to_string();
+        app.selected_index = 0;
+        // Every row stays visible; matches are case-insensitive
+        assert_eq!(app.filtered_processes.len(), 4);
+        app.find_next(true);
+        assert_eq!(selected_pid(&app), 105);
+        // Wraps around in both directions
+        app.find_next(true);
+        assert_eq!(selected_pid(&app), 101);
+        app.find_next(false);
+        assert_eq!(selected_pid(&app), 105);
+
+        // A PID matches exactly
+        app.find = "107".to_string();
+        app.find_next(false);
+        assert_eq!(selected_pid(&app), 107);
+
+        app.find = "missing".to_string();
+        app.find_next(true);
+        assert_eq!(selected_pid(&app), 107);
+        assert_eq!(app.error_message.as_deref(), Some("No match for \"missing\""));
+    }
+}
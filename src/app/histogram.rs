@@ -0,0 +1,96 @@
+//! Counting processes into CPU and memory bands
+//!
+//! The histogram panel gives a quick sense of how busy the system is:
+//! how many processes sit at 0% CPU, how many are working hard, and how
+//! memory is spread. It reuses the CPU% and working set already gathered
+//! by refresh. The Idle/System pseudo-processes are left out, since Idle
+//! alone would otherwise always fill the 50%+ band.
+
+use crate::constants::{BYTES_PER_MB, HISTOGRAM_CPU_BOUNDS, HISTOGRAM_MEMORY_BOUNDS_MB};
+
+use super::ProcessEntry;
+
+/// Labels of the CPU bands, lowest first
+const CPU_BAND_LABELS: [&str; 5] = ["0%", "0-5%", "5-20%", "20-50%", "50%+"];
+
+/// Labels of the memory bands, lowest first
+const MEMORY_BAND_LABELS: [&str; 4] = ["<10 MB", "10-100 MB", "100 MB-1 GB", "1 GB+"];
+
+/// Counts values into bands with the given upper bounds.
+///
+/// # Arguments
+/// * `values` - Values to count
+/// * `bounds` - Exclusive upper bounds, ascending; one band more than bounds
+///
+/// # Returns
+/// One count per band, lowest first.
+fn count_bands(values: impl Iterator<Item = f64>, bounds: &[f64]) -> Vec<usize> {
+    let mut counts = vec![0; bounds.len() + 1];
+    for value in values {
+        let band = bounds.iter().position(|&bound| value < bound).unwrap_or(bounds.len());
+        counts[band] += 1;
+    }
+    counts
+}
+
+/// Counts processes into CPU% bands.
+///
+/// # Returns
+/// Band labels with their process counts, lowest CPU first.
+pub fn cpu_bands(processes: &[ProcessEntry]) -> Vec<(&'static str, usize)> {
+    let counts = count_bands(
+        processes
+            .iter()
+            .filter(|p| !p.info.is_system_pseudo_process())
+            .map(|p| p.cpu_percent),
+        &HISTOGRAM_CPU_BOUNDS,
+    );
+    CPU_BAND_LABELS.into_iter().zip(counts).collect()
+}
+
+/// Counts processes into working set bands.
+///
+/// # Returns
+/// Band labels with their process counts, smallest first.
+pub fn memory_bands(processes: &[ProcessEntry]) -> Vec<(&'static str, usize)> {
+    let counts = count_bands(
+        processes
+            .iter()
+            .filter(|p| !p.info.is_system_pseudo_process())
+            .map(|p| p.memory_bytes as f64 / BYTES_PER_MB),
+        &HISTOGRAM_MEMORY_BOUNDS_MB,
+    );
+    MEMORY_BAND_LABELS.into_iter().zip(counts).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::source::MockProcessSource;
+    use crate::app::App;
+
+    #[test]
+    fn test_bands() {
+        let source = MockProcessSource::default()
+            .with_process(101, 1, "a.exe", 5 * 1024 * 1024)
+            .with_process(103, 1, "b.exe", 50 * 1024 * 1024)
+            .with_process(105, 1, "c.exe", 500 * 1024 * 1024)
+            .with_process(107, 1, "d.exe", 2048 * 1024 * 1024)
+            .with_process(0, 0, "System Idle Process", 0);
+        let mut app = App::with_source(Box::new(source));
+        app.refresh();
+        let cpu = [0.0, 3.0, 20.0, 75.0];
+        for p in app.processes.iter_mut() {
+            p.cpu_percent = match p.info.pid {
+                0 => 95.0,
+                pid => cpu[(pid as usize - 101) / 2],
+            };
+        }
+
+        let counts = |bands: Vec<(&str, usize)>| bands.iter().map(|(_, n)| *n).collect::<Vec<_>>();
+        // Idle is left out; 20% starts the 20-50% band
+        assert_eq!(counts(cpu_bands(&app.processes)), [1, 1, 0, 1, 1]);
+        assert_eq!(counts(memory_bands(&app.processes)), [1, 1, 1, 1]);
+        assert_eq!(cpu_bands(&[])[4], ("50%+", 0));
+    }
+}
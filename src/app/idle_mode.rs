@@ -0,0 +1,78 @@
+//! Dimming or hiding processes that are doing no work
+
+use crate::constants::{IDLE_CPU_PERCENT, IDLE_MEMORY_GROWTH_BYTES};
+
+use super::ProcessEntry;
+
+/// How idle processes are shown in the list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdleMode {
+    /// Shown like any other process
+    #[default]
+    Show,
+    /// Shown in a dim color
+    Dim,
+    /// Left out of the list (pinned processes are kept)
+    Hide,
+}
+
+impl IdleMode {
+    /// Cycles to the next mode (Show → Dim → Hide → Show).
+    pub fn next(self) -> Self {
+        match self {
+            IdleMode::Show => IdleMode::Dim,
+            IdleMode::Dim => IdleMode::Hide,
+            IdleMode::Hide => IdleMode::Show,
+        }
+    }
+
+    /// Gets the label used in status messages.
+    pub fn label(self) -> &'static str {
+        match self {
+            IdleMode::Show => "shown",
+            IdleMode::Dim => "dimmed",
+            IdleMode::Hide => "hidden",
+        }
+    }
+
+    /// Parses a mode name as given to `--idle`.
+    ///
+    /// # Returns
+    /// * `Option<IdleMode>` - None if the name is not recognized
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "show" => Some(IdleMode::Show),
+            "dim" => Some(IdleMode::Dim),
+            "hide" => Some(IdleMode::Hide),
+            _ => None,
+        }
+    }
+}
+
+impl ProcessEntry {
+    /// Checks whether the process did no noticeable work over the last
+    /// refresh: (near) 0% CPU, no disk or network traffic and a stable
+    /// working set.
+    pub fn is_idle(&self) -> bool {
+        self.cpu_percent < IDLE_CPU_PERCENT
+            && self.disk_read_rate == 0.0
+            && self.disk_write_rate == 0.0
+            && self.net_recv_rate == 0.0
+            && self.net_send_rate == 0.0
+            && self.memory_growth_rate.abs() < IDLE_MEMORY_GROWTH_BYTES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_cycle_and_names() {
+        assert_eq!(IdleMode::Show.next(), IdleMode::Dim);
+        assert_eq!(IdleMode::Dim.next(), IdleMode::Hide);
+        assert_eq!(IdleMode::Hide.next(), IdleMode::Show);
+        assert_eq!(IdleMode::from_name("DIM"), Some(IdleMode::Dim));
+        assert_eq!(IdleMode::from_name("off"), None);
+    }
+}
@@ -5,6 +5,7 @@
 //! - Filter mode (text input for filtering)
 //! - Confirm kill mode (Y/N confirmation)
 //! - Detail view mode (scrolling process details)
+//! - System info panel
 
 use std::io;
 
@@ -12,8 +13,9 @@ use crossterm::event::{KeyCode, KeyModifiers};
 use crossterm::terminal;
 
 use crate::constants::{HELP_PAGE_SCROLL_LINES, VISIBLE_ROWS_OVERHEAD};
+use crate::system::get_system_info;
 
-use super::{App, ViewMode};
+use super::{affinity_grid_columns, App, SortColumn, ViewMode};
 
 /// Result of handling a key event
 pub enum KeyAction {
@@ -21,23 +23,46 @@ pub enum KeyAction {
     Continue,
     /// Exit the application
     Exit,
+    /// Refresh immediately and restart the refresh interval
+    Refresh,
+    /// Copy the detail view's text to the clipboard
+    CopyDetails,
 }
 
 impl App {
     /// Handles key events when help overlay is shown
     pub fn handle_help_key(&mut self, code: KeyCode) -> KeyAction {
         match code {
+            // Esc clears the search first, then closes
+            KeyCode::Esc if !self.help_filter.is_empty() => {
+                self.help_filter.clear();
+                self.help_scroll_offset = 0;
+            }
             // Close help
-            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('?') | KeyCode::Char('q') => {
+            KeyCode::Esc | KeyCode::Enter => {
+                self.view_mode = ViewMode::ProcessList;
+                self.help_scroll_offset = 0;
+                self.help_filter.clear();
+            }
+            KeyCode::Char('?') if self.help_filter.is_empty() => {
                 self.view_mode = ViewMode::ProcessList;
                 self.help_scroll_offset = 0;
             }
+            // Type to search the shortcuts
+            KeyCode::Char(c) => {
+                self.help_filter.push(c);
+                self.help_scroll_offset = 0;
+            }
+            KeyCode::Backspace => {
+                self.help_filter.pop();
+                self.help_scroll_offset = 0;
+            }
             // Scroll up
-            KeyCode::Up | KeyCode::Char('k') => {
+            KeyCode::Up => {
                 self.help_scroll_offset = self.help_scroll_offset.saturating_sub(1);
             }
             // Scroll down
-            KeyCode::Down | KeyCode::Char('j') => {
+            KeyCode::Down => {
                 self.help_scroll_offset = self.help_scroll_offset.saturating_add(1);
             }
             // Page up
@@ -57,6 +82,66 @@ impl App {
         KeyAction::Continue
     }
 
+    /// Handles key events when the system info panel is shown
+    pub fn handle_sys_info_key(&mut self, code: KeyCode) -> KeyAction {
+        if matches!(code, KeyCode::Esc | KeyCode::Enter | KeyCode::F(1) | KeyCode::Char('q')) {
+            self.view_mode = ViewMode::ProcessList;
+        }
+        KeyAction::Continue
+    }
+
+    /// Handles key events when the CPU/memory histogram is shown
+    pub fn handle_histogram_key(&mut self, code: KeyCode) -> KeyAction {
+        if matches!(code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('g') | KeyCode::Char('q')) {
+            self.view_mode = ViewMode::ProcessList;
+        }
+        KeyAction::Continue
+    }
+
+    /// Opens the system info panel with freshly gathered machine information
+    pub fn show_sys_info(&mut self) {
+        self.sys_info = Some(get_system_info());
+        self.view_mode = ViewMode::SysInfo;
+    }
+
+    /// Opens the sort column picker with the current column highlighted
+    pub fn open_sort_picker(&mut self) {
+        self.sort_picker_index = SortColumn::ALL
+            .iter()
+            .position(|&c| c == self.sort_column)
+            .unwrap_or(0);
+        self.view_mode = ViewMode::SortPicker;
+    }
+
+    /// Handles key events in the sort column picker.
+    /// Enter sorts by the highlighted column; Esc closes without changing it.
+    pub fn handle_sort_picker_key(&mut self, code: KeyCode) -> KeyAction {
+        let last = SortColumn::ALL.len() - 1;
+        match code {
+            KeyCode::Up => {
+                self.sort_picker_index = self.sort_picker_index.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                self.sort_picker_index = (self.sort_picker_index + 1).min(last);
+            }
+            KeyCode::Home => {
+                self.sort_picker_index = 0;
+            }
+            KeyCode::End => {
+                self.sort_picker_index = last;
+            }
+            KeyCode::Enter => {
+                self.set_sort_column(SortColumn::ALL[self.sort_picker_index.min(last)]);
+                self.view_mode = ViewMode::ProcessList;
+            }
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('S') => {
+                self.view_mode = ViewMode::ProcessList;
+            }
+            _ => {}
+        }
+        KeyAction::Continue
+    }
+
     /// Handles key events in confirm kill mode
     pub fn handle_confirm_kill_key(&mut self, code: KeyCode) -> KeyAction {
         match code {
@@ -72,6 +157,47 @@ impl App {
         KeyAction::Continue
     }
 
+    /// Handles key events while confirming a raise to Realtime priority
+    pub fn handle_confirm_realtime_key(&mut self, code: KeyCode) -> KeyAction {
+        match code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                self.confirm_realtime();
+                self.refresh();
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                self.cancel_realtime();
+            }
+            _ => {}
+        }
+        KeyAction::Continue
+    }
+
+    /// Handles key events in confirm quit mode.
+    /// Ctrl+C exits as well, so a second press always gets the user out.
+    pub fn handle_confirm_quit_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> KeyAction {
+        match code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => return KeyAction::Exit,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                return KeyAction::Exit
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                self.view_mode = ViewMode::ProcessList;
+            }
+            _ => {}
+        }
+        KeyAction::Continue
+    }
+
+    /// Exits immediately, or asks first when `confirm_quit` is enabled.
+    fn request_quit(&mut self) -> KeyAction {
+        if self.confirm_quit {
+            self.view_mode = ViewMode::ConfirmQuit;
+            KeyAction::Continue
+        } else {
+            KeyAction::Exit
+        }
+    }
+
     /// Handles key events in filter mode
     pub fn handle_filter_key(&mut self, code: KeyCode) -> KeyAction {
         match code {
@@ -80,13 +206,25 @@ impl App {
             }
             KeyCode::Enter => {
                 self.view_mode = ViewMode::ProcessList;
+                self.push_filter_history();
                 self.apply_filter();
             }
+            KeyCode::Up => {
+                self.filter_history_prev();
+            }
+            KeyCode::Down => {
+                self.filter_history_next();
+            }
+            KeyCode::Tab => {
+                self.cycle_filter_scope();
+            }
             KeyCode::Backspace => {
+                self.reset_filter_history_position();
                 self.filter.pop();
                 self.apply_filter();
             }
             KeyCode::Char(c) => {
+                self.reset_filter_history_position();
                 self.filter.push(c);
                 self.apply_filter();
             }
@@ -95,17 +233,61 @@ impl App {
         KeyAction::Continue
     }
 
+    /// Handles key events in find mode: the highlight follows the text as
+    /// it is typed, Enter jumps to the first match and Esc clears it
+    pub fn handle_find_key(&mut self, code: KeyCode) -> KeyAction {
+        match code {
+            KeyCode::Esc => {
+                self.clear_find();
+                self.view_mode = ViewMode::ProcessList;
+            }
+            KeyCode::Enter => {
+                self.view_mode = ViewMode::ProcessList;
+                if !self.find.trim().is_empty()
+                    && !self
+                        .selected_process()
+                        .is_some_and(|p| self.is_find_match(p))
+                {
+                    self.find_next(true);
+                }
+            }
+            KeyCode::Backspace => {
+                self.find.pop();
+            }
+            KeyCode::Char(c) => {
+                self.find.push(c);
+            }
+            _ => {}
+        }
+        KeyAction::Continue
+    }
+
     /// Handles key events in detail view mode
     pub fn handle_detail_view_key(&mut self, code: KeyCode) -> io::Result<KeyAction> {
+        if self.module_view.filter_input {
+            self.handle_module_filter_key(code);
+            return Ok(KeyAction::Continue);
+        }
+
         match code {
             KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
                 self.close_detail_view();
             }
+            KeyCode::Char('k') | KeyCode::Char('K') | KeyCode::Char('a') | KeyCode::Char('A')
+            | KeyCode::Char('i') | KeyCode::Char('I')
+                if self.detail_process_exited() => {}
             KeyCode::Char('k') | KeyCode::Char('K') => {
                 // Allow killing from detail view
                 self.close_detail_view();
-                self.request_kill();
+                self.request_kill_current();
+            }
+            KeyCode::Char('S') => {
+                self.export_detail_view();
             }
+            KeyCode::Char('c') | KeyCode::Char('C') => return Ok(KeyAction::CopyDetails),
+            KeyCode::Char('m') | KeyCode::Char('M') => self.cycle_module_sort(),
+            KeyCode::Char('/') => self.start_module_filter(),
+            KeyCode::F(5) => return Ok(KeyAction::Refresh),
             KeyCode::Up => self.detail_scroll_up(),
             KeyCode::Down => self.detail_scroll_down(),
             KeyCode::PageUp => {
@@ -124,15 +306,42 @@ impl App {
             }
             KeyCode::Char('a') | KeyCode::Char('A') => {
                 // Open affinity dialog
-                self.open_affinity_dialog();
+                self.open_affinity_for_current();
+            }
+            KeyCode::Char('i') | KeyCode::Char('I') => {
+                self.cycle_io_priority();
+            }
+            KeyCode::Char('z') | KeyCode::Char('Z') => {
+                self.reset_cpu_stats();
             }
             _ => {}
         }
         Ok(KeyAction::Continue)
     }
 
+    /// Handles key events while typing a module search in the detail view
+    fn handle_module_filter_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => {
+                self.module_view.filter.clear();
+                self.module_view.filter_input = false;
+            }
+            KeyCode::Enter => {
+                self.module_view.filter_input = false;
+            }
+            KeyCode::Backspace => {
+                self.module_view.filter.pop();
+            }
+            KeyCode::Char(c) => {
+                self.module_view.filter.push(c);
+            }
+            _ => {}
+        }
+        self.detail_scroll_offset = usize::MAX; // Keep the modules in view
+    }
+
     /// Handles key events in affinity edit mode
-    pub fn handle_affinity_key(&mut self, code: KeyCode) -> KeyAction {
+    pub fn handle_affinity_key(&mut self, code: KeyCode) -> io::Result<KeyAction> {
         match code {
             KeyCode::Esc => {
                 self.close_affinity_dialog();
@@ -146,6 +355,14 @@ impl App {
             KeyCode::Right => {
                 self.affinity_move_right();
             }
+            KeyCode::Up => {
+                let (w, _) = terminal::size()?;
+                self.affinity_move_up(affinity_grid_columns(self.affinity_total_cores as usize, w as usize));
+            }
+            KeyCode::Down => {
+                let (w, _) = terminal::size()?;
+                self.affinity_move_down(affinity_grid_columns(self.affinity_total_cores as usize, w as usize));
+            }
             KeyCode::Char(' ') => {
                 self.toggle_affinity_core();
             }
@@ -159,20 +376,31 @@ impl App {
             }
             _ => {}
         }
-        KeyAction::Continue
+        Ok(KeyAction::Continue)
     }
 
     /// Handles key events in normal mode (process list).
-    /// Returns `KeyAction::Exit` if the application should quit.
+    /// Returns `KeyAction::Exit` if the application should quit, or
+    /// `KeyAction::Refresh` for F5/Ctrl+R.
     pub fn handle_normal_key(
         &mut self,
         code: KeyCode,
         modifiers: KeyModifiers,
     ) -> io::Result<KeyAction> {
         match code {
-            KeyCode::Char('q') | KeyCode::Char('Q') => return Ok(KeyAction::Exit),
+            KeyCode::Char('q') | KeyCode::Char('Q') => return Ok(self.request_quit()),
             KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
-                return Ok(KeyAction::Exit)
+                return Ok(self.request_quit())
+            }
+            KeyCode::F(5) => return Ok(KeyAction::Refresh),
+            KeyCode::Char('r') if modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(KeyAction::Refresh)
+            }
+            KeyCode::Char('f') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.start_find();
+            }
+            KeyCode::F(3) => {
+                self.find_next(!modifiers.contains(KeyModifiers::SHIFT));
             }
             KeyCode::Char('k') | KeyCode::Char('K') => {
                 self.request_kill();
@@ -180,6 +408,18 @@ impl App {
             KeyCode::Char('p') | KeyCode::Char('P') => {
                 self.toggle_suspend();
             }
+            KeyCode::Char('a') | KeyCode::Char('A') => {
+                self.open_affinity_for_current();
+            }
+            KeyCode::Char('!') => {
+                self.toggle_kill_confirmation();
+            }
+            KeyCode::Char('*') => {
+                self.toggle_pin();
+            }
+            KeyCode::Char(' ') => {
+                self.toggle_selected();
+            }
             KeyCode::Char('+') | KeyCode::Char('=') => {
                 self.raise_priority();
                 self.refresh();
@@ -188,18 +428,61 @@ impl App {
                 self.lower_priority();
                 self.refresh();
             }
-            KeyCode::Char('s') | KeyCode::Char('S') => {
+            KeyCode::Char('s') => {
                 self.cycle_sort();
             }
+            KeyCode::Char('S') => {
+                self.open_sort_picker();
+            }
             KeyCode::Char('r') | KeyCode::Char('R') => {
                 self.toggle_sort_order();
             }
             KeyCode::Char('t') | KeyCode::Char('T') => {
                 self.toggle_tree_view();
             }
+            KeyCode::Char('w') | KeyCode::Char('W') => {
+                self.toggle_compact_rows();
+            }
+            KeyCode::Char('v') | KeyCode::Char('V') => {
+                self.toggle_row_stripes();
+            }
+            KeyCode::Char('x') | KeyCode::Char('X') => {
+                self.toggle_freeze_order();
+            }
+            KeyCode::Char('z') | KeyCode::Char('Z') => {
+                self.reset_cpu_stats();
+            }
             KeyCode::Char('e') | KeyCode::Char('E') => {
                 self.export_processes();
             }
+            KeyCode::Char('u') | KeyCode::Char('U') => {
+                self.toggle_current_user_only();
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                self.toggle_network_only();
+            }
+            KeyCode::Char('i') | KeyCode::Char('I') => {
+                self.toggle_show_system();
+            }
+            KeyCode::Char('h') | KeyCode::Char('H') => {
+                self.request_kill_hold();
+            }
+            KeyCode::Char('l') | KeyCode::Char('L') => {
+                self.cycle_idle_mode();
+            }
+            KeyCode::Char('o') | KeyCode::Char('O') => {
+                self.open_containing_folder();
+            }
+            KeyCode::Char('f') | KeyCode::Char('F') => {
+                self.toggle_efficiency_mode();
+                self.refresh();
+            }
+            KeyCode::Char('b') | KeyCode::Char('B') => {
+                self.toggle_priority_boost();
+            }
+            KeyCode::Char('c') | KeyCode::Char('C') => {
+                self.copy_process_path();
+            }
             KeyCode::Char('[') => {
                 self.increase_refresh_interval();
             }
@@ -207,14 +490,34 @@ impl App {
                 self.decrease_refresh_interval();
             }
             KeyCode::Char('/') => {
+                self.reset_filter_history_position();
                 self.view_mode = ViewMode::FilterInput;
             }
             KeyCode::Char('?') => {
                 self.view_mode = ViewMode::Help;
             }
+            KeyCode::F(1) => {
+                self.show_sys_info();
+            }
+            KeyCode::Char('d') | KeyCode::Char('D') => {
+                self.toggle_disk_totals();
+            }
+            KeyCode::Char('g') | KeyCode::Char('G') => {
+                self.view_mode = ViewMode::Histogram;
+            }
+            KeyCode::F(12) => {
+                self.toggle_debug_line();
+            }
             KeyCode::Esc => {
-                self.filter.clear();
-                self.apply_filter();
+                // Clear the multi-selection first, then the find text, then the filter
+                if !self.selected_pids.is_empty() {
+                    self.selected_pids.clear();
+                } else if !self.find.is_empty() {
+                    self.clear_find();
+                } else {
+                    self.filter.clear();
+                    self.apply_filter();
+                }
             }
             KeyCode::Enter => {
                 self.open_detail_view();
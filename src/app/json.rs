@@ -0,0 +1,262 @@
+//! Minimal JSON serialization (no external dependencies)
+//!
+//! Output is compact (single-line) and only covers the value types the
+//! app needs: strings, numbers, booleans, null, arrays, and objects.
+
+use std::fmt::Display;
+
+use crate::system::{ProcessDetails, SignatureStatus};
+
+use super::ProcessEntry;
+
+/// Encodes a string as a quoted JSON string literal
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Joins already-encoded JSON values into an array
+pub fn json_array<I>(items: I) -> String
+where
+    I: IntoIterator<Item = String>,
+{
+    format!("[{}]", items.into_iter().collect::<Vec<_>>().join(","))
+}
+
+/// Builds a JSON object one field at a time, preserving field order
+#[derive(Debug, Default)]
+pub struct JsonObject {
+    fields: Vec<String>,
+}
+
+impl JsonObject {
+    /// Creates an empty object
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an already-encoded JSON value
+    pub fn raw(mut self, key: &str, json: String) -> Self {
+        self.fields.push(format!("{}:{}", json_string(key), json));
+        self
+    }
+
+    /// Adds a string field
+    pub fn string(self, key: &str, value: &str) -> Self {
+        self.raw(key, json_string(value))
+    }
+
+    /// Adds a string field, or null when absent
+    pub fn opt_string(self, key: &str, value: Option<&str>) -> Self {
+        self.raw(key, value.map_or_else(|| "null".to_string(), json_string))
+    }
+
+    /// Adds an integer field
+    pub fn integer<T: Display>(self, key: &str, value: T) -> Self {
+        self.raw(key, value.to_string())
+    }
+
+    /// Adds an integer field, or null when unknown
+    pub fn opt_integer<T: Display>(self, key: &str, value: Option<T>) -> Self {
+        self.raw(key, value.map_or_else(|| "null".to_string(), |v| v.to_string()))
+    }
+
+    /// Adds a floating-point field (null if not finite, which JSON cannot represent)
+    pub fn float(self, key: &str, value: f64) -> Self {
+        let json = if value.is_finite() { value.to_string() } else { "null".to_string() };
+        self.raw(key, json)
+    }
+
+    /// Adds a boolean field
+    pub fn boolean(self, key: &str, value: bool) -> Self {
+        self.raw(key, value.to_string())
+    }
+
+    /// Adds a boolean field, or null when unknown
+    pub fn opt_boolean(self, key: &str, value: Option<bool>) -> Self {
+        self.raw(key, value.map_or_else(|| "null".to_string(), |b| b.to_string()))
+    }
+
+    /// Finishes the object
+    pub fn build(self) -> String {
+        format!("{{{}}}", self.fields.join(","))
+    }
+}
+
+/// Serializes full process details (metrics, modules, connections) to JSON
+pub fn details_to_json(details: &ProcessDetails) -> String {
+    let modules = json_array(details.modules.iter().map(|m| {
+        JsonObject::new()
+            .string("name", &m.name)
+            .string("path", &m.path)
+            .integer("base_address", m.base_address)
+            .build()
+    }));
+    let tcp = json_array(details.tcp_connections.iter().map(|c| {
+        JsonObject::new()
+            .string("local_addr", &c.local_addr)
+            .integer("local_port", c.local_port)
+            .string("remote_addr", &c.remote_addr)
+            .integer("remote_port", c.remote_port)
+            .string("state", &c.state)
+            .build()
+    }));
+    let udp = json_array(details.udp_endpoints.iter().map(|e| {
+        JsonObject::new()
+            .string("local_addr", &e.local_addr)
+            .integer("local_port", e.local_port)
+            .build()
+    }));
+    let services = json_array(details.services.iter().map(|s| json_string(s)));
+    let signature = details.signature.as_ref().map_or_else(
+        || "null".to_string(),
+        |s| {
+            let (status, reason) = match &s.status {
+                SignatureStatus::Valid => ("valid", None),
+                SignatureStatus::Invalid(reason) => ("invalid", Some(reason.as_str())),
+                SignatureStatus::Unsigned => ("unsigned", None),
+            };
+            JsonObject::new()
+                .string("status", status)
+                .opt_string("reason", reason)
+                .opt_string("signer", s.signer.as_deref())
+                .boolean("catalog", s.catalog)
+                .build()
+        },
+    );
+    let thread_cpu = details.thread_cpu.map_or_else(
+        || "null".to_string(),
+        |t| {
+            JsonObject::new()
+                .integer("thread_count", t.thread_count)
+                .integer("unreadable", t.unreadable)
+                .float("threads_seconds", t.threads_seconds)
+                .float("process_seconds", t.process_seconds)
+                .opt_string("divergence", t.divergence_note())
+                .build()
+        },
+    );
+
+    JsonObject::new()
+        .integer("pid", details.pid)
+        .string("name", &details.name)
+        .opt_string("path", details.path.as_deref())
+        .opt_string("command_line", details.command_line.as_deref())
+        .raw("services", services)
+        .opt_boolean("is_32bit", details.is_32bit)
+        .opt_boolean("being_debugged", details.being_debugged)
+        .opt_boolean("efficiency_mode", details.efficiency_mode)
+        .opt_boolean("priority_boost", details.priority_boost)
+        .opt_string("package_family", details.package_family.as_deref())
+        .opt_string("app_user_model_id", details.app_user_model_id.as_deref())
+        .opt_integer("session_id", details.session_id)
+        .opt_string("protection", details.protection.as_deref())
+        .raw("signature", signature)
+        .string("priority", &details.priority)
+        .integer("base_priority", details.base_priority)
+        .integer("uptime_seconds", details.uptime_seconds)
+        .float("cpu_percent", details.cpu_percent)
+        .float("cpu_avg_percent", details.cpu_avg_percent)
+        .float("cpu_peak_percent", details.cpu_peak_percent)
+        .integer("cpu_time_seconds", details.cpu_time_seconds)
+        .integer("memory_bytes", details.memory_bytes)
+        .float("mem_percent", details.mem_percent)
+        .boolean("memory_access_denied", details.memory_access_denied)
+        .boolean("query_access_denied", details.query_access_denied)
+        .integer("thread_count", details.thread_count)
+        .raw("thread_cpu", thread_cpu)
+        .integer("handle_count", details.handle_count)
+        .float("disk_read_rate", details.disk_read_rate)
+        .float("disk_write_rate", details.disk_write_rate)
+        .float("net_recv_rate", details.net_recv_rate)
+        .float("net_send_rate", details.net_send_rate)
+        .opt_string("cpu_affinity", details.cpu_affinity.as_deref())
+        .opt_string("io_priority", details.io_priority.as_deref())
+        .raw("modules", modules)
+        .raw("tcp_connections", tcp)
+        .raw("udp_endpoints", udp)
+        .build()
+}
+
+/// Serializes the process table (the metrics shown in the list) to JSON
+pub fn processes_to_json(processes: &[ProcessEntry]) -> String {
+    json_array(processes.iter().map(|p| {
+        JsonObject::new()
+            .integer("pid", p.info.pid)
+            .integer("parent_pid", p.info.parent_pid)
+            .string("name", &p.info.name)
+            .opt_string("path", p.path.as_deref())
+            .opt_integer("session_id", p.session_id)
+            .string("priority", p.priority.name())
+            .integer("base_priority", p.info.base_priority)
+            .integer("thread_count", p.thread_count)
+            .integer("handle_count", p.handle_count)
+            .integer("uptime_seconds", p.uptime_seconds)
+            .float("cpu_percent", p.cpu_percent)
+            .integer("cpu_time_seconds", p.cpu_time_seconds)
+            .integer("memory_bytes", p.memory_bytes)
+            .float("mem_percent", p.mem_percent)
+            .float("memory_growth_rate", p.memory_growth_rate)
+            .integer("disk_read", p.disk_read)
+            .integer("disk_write", p.disk_write)
+            .float("disk_read_rate", p.disk_read_rate)
+            .float("disk_write_rate", p.disk_write_rate)
+            .float("net_recv_rate", p.net_recv_rate)
+            .float("net_send_rate", p.net_send_rate)
+            .boolean("memory_access_denied", p.memory_access_denied)
+            .boolean("query_access_denied", p.query_access_denied)
+            .build()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_string_escaping() {
+        assert_eq!(json_string("plain"), "\"plain\"");
+        assert_eq!(json_string(r"C:\Windows"), r#""C:\\Windows""#);
+        assert_eq!(json_string("say \"hi\"\n"), r#""say \"hi\"\n""#);
+        assert_eq!(json_string("\u{1}"), r#""\u0001""#);
+    }
+
+    #[test]
+    fn test_json_object() {
+        let json = JsonObject::new()
+            .integer("pid", 42)
+            .opt_string("path", None)
+            .float("cpu", f64::NAN)
+            .raw("list", json_array(vec!["1".to_string(), "2".to_string()]))
+            .build();
+        assert_eq!(json, r#"{"pid":42,"path":null,"cpu":null,"list":[1,2]}"#);
+    }
+
+    #[test]
+    fn test_processes_to_json() {
+        use crate::app::source::MockProcessSource;
+        use crate::app::App;
+
+        let source = MockProcessSource::default().with_process(101, 1, "say \"hi\".exe", 2048);
+        let mut app = App::with_source(Box::new(source));
+        app.refresh();
+        let json = processes_to_json(&app.processes);
+        assert!(json.starts_with(r#"[{"pid":101,"parent_pid":1,"name":"say \"hi\".exe","path":null,"#));
+        assert!(json.contains(r#""memory_bytes":2048,"#));
+        assert!(json.ends_with("}]"));
+        assert_eq!(processes_to_json(&[]), "[]");
+    }
+}
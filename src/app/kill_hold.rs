@@ -0,0 +1,283 @@
+//! Kill and hold: keep killing respawns of a process for a few seconds
+//!
+//! Some background apps restart themselves (or are restarted by a helper)
+//! the moment they are killed. After killing the selected process, a hold
+//! watches for new processes started from the same executable path and
+//! kills them too, until `KILL_HOLD_SECS` have passed. Instances that were
+//! already running when the hold started (other browser tabs, other
+//! consoles) are left alone.
+//!
+//! Blocking the executable for good (an Image File Execution Options
+//! debugger redirect, or renaming the file) is deliberately not done: it
+//! is a persistent system change that needs Administrator rights and is
+//! easy to forget about. The hold is contained to this session and ends
+//! on its own.
+//!
+//! It is refused for processes where repeated kills could destabilize
+//! Windows: pseudo-processes, session 0 (services and system processes),
+//! anything under the Windows directory, protected processes and this
+//! application itself.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use crate::constants::KILL_HOLD_SECS;
+use crate::system::terminate_process;
+
+use super::state::App;
+use super::{ProcessEntry, ViewMode};
+
+/// A running hold on one executable
+#[derive(Debug, Clone)]
+pub struct KillHold {
+    /// Executable path being held, lowercased for comparison
+    pub image_path: String,
+    /// Process name, for messages and a cheap first match
+    pub name: String,
+    /// When the hold ends
+    pub until: Instant,
+    /// PIDs already running from the executable when the hold started,
+    /// which are not respawns and are never killed by it
+    pub existing: HashSet<u32>,
+    /// Respawns killed so far
+    pub kills: u32,
+}
+
+/// Checks whether a process may be killed and held.
+///
+/// # Arguments
+/// * `entry` - The process to check
+/// * `windows_dir` - The Windows directory (e.g. `C:\Windows`), if known
+///
+/// # Returns
+/// * `Option<&'static str>` - Why the hold is refused, or None if allowed
+pub fn hold_refusal(entry: &ProcessEntry, windows_dir: Option<&str>) -> Option<&'static str> {
+    if entry.info.pid == std::process::id() {
+        return Some("it is this task manager");
+    }
+    if entry.info.is_system_pseudo_process() {
+        return Some("it is a system pseudo-process");
+    }
+    if entry.session_id == Some(0) {
+        return Some("it runs in session 0 (services and system processes)");
+    }
+    let path = match entry.path {
+        Some(ref path) => path.to_lowercase(),
+        None => return Some("its executable path is unknown"),
+    };
+    if let Some(dir) = windows_dir {
+        let dir = format!("{}\\", dir.trim_end_matches('\\').to_lowercase());
+        if path.starts_with(&dir) {
+            return Some("it is part of Windows");
+        }
+    }
+    None
+}
+
+impl App {
+    /// Asks to kill the selected process and hold it down for a few
+    /// seconds, refusing processes where that could destabilize Windows.
+    pub fn request_kill_hold(&mut self) {
+        if self.kill_hold.is_some() {
+            self.stop_kill_hold();
+            return;
+        }
+        let entry = match self.selected_process() {
+            Some(entry) => entry.clone(),
+            None => return,
+        };
+        let (pid, name) = (entry.info.pid, entry.info.name.clone());
+
+        let windows_dir = std::env::var("SystemRoot").ok();
+        if let Some(reason) = hold_refusal(&entry, windows_dir.as_deref()) {
+            self.error_message = Some(format!(
+                "Cannot kill and hold {} (PID {}): {}",
+                name, pid, reason
+            ));
+            return;
+        }
+        if self.warn_if_protected(pid, &name, "kill and hold") {
+            return;
+        }
+
+        self.pending_kill_pid = Some(pid);
+        self.pending_kill_name = Some(name.clone());
+        self.pending_kill_hold = entry.path;
+
+        if self.no_confirm_kill {
+            self.confirm_kill();
+            self.refresh();
+            return;
+        }
+
+        self.error_message = Some(format!(
+            "Kill {} (PID {}) and kill respawns for {}s? Press Y to confirm, N to cancel",
+            name, pid, KILL_HOLD_SECS
+        ));
+        self.view_mode = ViewMode::ConfirmKill;
+    }
+
+    /// Starts holding an executable after its process was killed.
+    ///
+    /// # Arguments
+    /// * `image_path` - Executable path of the killed process
+    /// * `name` - Its process name
+    pub(super) fn start_kill_hold(&mut self, image_path: &str, name: &str) {
+        let image_path = image_path.to_lowercase();
+        let existing = self
+            .processes_from_image(&image_path, name)
+            .into_iter()
+            .map(|(pid, _)| pid)
+            .collect();
+        self.kill_hold = Some(KillHold {
+            image_path,
+            name: name.to_string(),
+            until: Instant::now() + Duration::from_secs(KILL_HOLD_SECS),
+            existing,
+            kills: 0,
+        });
+        self.error_message = Some(format!(
+            "Terminated {}; killing respawns for {}s (h to stop)",
+            name, KILL_HOLD_SECS
+        ));
+    }
+
+    /// Ends the hold early (or when it runs out) and reports the result.
+    pub fn stop_kill_hold(&mut self) {
+        if let Some(hold) = self.kill_hold.take() {
+            self.error_message = Some(format!(
+                "Stopped holding {}: killed {} respawn(s)",
+                hold.name, hold.kills
+            ));
+        }
+    }
+
+    /// Lists respawns of the held executable: processes running from it
+    /// that were not already running when the hold started.
+    ///
+    /// # Returns
+    /// PIDs and names of the processes to kill.
+    pub(super) fn kill_hold_targets(&self) -> Vec<(u32, String)> {
+        let hold = match self.kill_hold {
+            Some(ref hold) => hold,
+            None => return Vec::new(),
+        };
+        self.processes_from_image(&hold.image_path, &hold.name)
+            .into_iter()
+            .filter(|(pid, _)| !hold.existing.contains(pid))
+            .collect()
+    }
+
+    /// Lists running processes started from an executable.
+    ///
+    /// # Arguments
+    /// * `image_path` - Executable path, lowercased
+    /// * `name` - Process name, compared first so only likely matches are opened
+    fn processes_from_image(&self, image_path: &str, name: &str) -> Vec<(u32, String)> {
+        let processes = match self.source.enumerate() {
+            Ok(processes) => processes,
+            Err(_) => return Vec::new(),
+        };
+        processes
+            .into_iter()
+            // Names are compared first so only likely matches are opened
+            .filter(|p| p.name.eq_ignore_ascii_case(name))
+            .filter(|p| {
+                self.source
+                    .path(p.pid)
+                    .is_some_and(|path| path.to_lowercase() == image_path)
+            })
+            .map(|p| (p.pid, p.name))
+            .collect()
+    }
+
+    /// Kills any respawn of the held executable; called from the main loop
+    /// every `KILL_HOLD_POLL_MS` while a hold is running.
+    ///
+    /// # Returns
+    /// `true` if a process was killed (so the list should be refreshed).
+    pub fn enforce_kill_hold(&mut self) -> bool {
+        let expired = self.kill_hold.as_ref().is_some_and(|h| Instant::now() >= h.until);
+        if expired {
+            self.stop_kill_hold();
+            return false;
+        }
+
+        let mut killed = false;
+        for (pid, name) in self.kill_hold_targets() {
+            if terminate_process(pid, self.kill_exit_code).is_ok() {
+                if let Some(ref mut hold) = self.kill_hold {
+                    hold.kills += 1;
+                }
+                self.error_message = Some(format!("Killed respawned {} (PID {})", name, pid));
+                killed = true;
+            }
+        }
+        killed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::source::MockProcessSource;
+
+    #[test]
+    fn test_hold_refusal() {
+        let source = MockProcessSource::default()
+            .with_process(101, 1, "updater.exe", 1024)
+            .with_process(103, 1, "svchost.exe", 1024)
+            .with_process(4, 0, "System", 1024);
+        let mut app = App::with_source(Box::new(source));
+        app.refresh();
+        let entry = |pid: u32| app.processes.iter().find(|p| p.info.pid == pid).unwrap().clone();
+
+        // The mock has no paths, so nothing is holdable yet
+        assert_eq!(hold_refusal(&entry(101), None), Some("its executable path is unknown"));
+
+        let mut updater = entry(101);
+        updater.path = Some(r"C:\Program Files\App\updater.exe".to_string());
+        assert_eq!(hold_refusal(&updater, Some(r"C:\Windows")), None);
+
+        let mut svchost = entry(103);
+        svchost.path = Some(r"C:\WINDOWS\System32\svchost.exe".to_string());
+        assert_eq!(hold_refusal(&svchost, Some(r"C:\Windows\")), Some("it is part of Windows"));
+        svchost.session_id = Some(0);
+        assert!(hold_refusal(&svchost, None).unwrap().contains("session 0"));
+
+        assert_eq!(hold_refusal(&entry(4), None), Some("it is a system pseudo-process"));
+    }
+
+    #[test]
+    fn test_kill_hold_targets() {
+        let path = r"C:\Program Files\App\updater.exe";
+        let mut source = MockProcessSource::default()
+            .with_process(101, 1, "updater.exe", 1024)
+            .with_process(103, 1, "Updater.exe", 1024)
+            .with_process(105, 1, "updater.exe", 1024);
+        source.processes[0].1.path = Some(path.to_string());
+        source.processes[1].1.path = Some(path.to_uppercase());
+        // Same name, different executable
+        source.processes[2].1.path = Some(r"C:\Other\updater.exe".to_string());
+
+        let mut app = App::with_source(Box::new(source.clone()));
+        assert!(app.kill_hold_targets().is_empty());
+
+        // 101 was the killed process; 103 was already running beside it
+        source.processes.remove(0);
+        app.source = Box::new(source.clone());
+        app.start_kill_hold(path, "updater.exe");
+        assert!(app.kill_hold_targets().is_empty());
+
+        // Respawns are killed; the sibling that was already running survives
+        let mut source = source.with_process(109, 1, "updater.exe", 1024);
+        source.processes[2].1.path = Some(path.to_string());
+        app.source = Box::new(source);
+        let pids: Vec<u32> = app.kill_hold_targets().iter().map(|(pid, _)| *pid).collect();
+        assert_eq!(pids, [109]);
+
+        app.stop_kill_hold();
+        assert!(app.kill_hold.is_none());
+        assert!(app.error_message.as_deref().unwrap().contains("killed 0"));
+    }
+}
@@ -11,17 +11,40 @@
 //! - `input` - Keyboard event handling
 //! - `cli` - Command-line argument parsing
 //! - `export` - CSV export functionality
+//! - `csv_column` - Selectable CSV export columns
+//! - `json` - Minimal JSON serialization
 //! - `affinity` - CPU affinity dialog
+//! - `filter_scope` - Fields searched by the filter
+//! - `idle_mode` - Dimming or hiding idle processes
+//! - `histogram` - Process counts by CPU and memory band
+//! - `kill_hold` - Killing respawns of a killed process for a few seconds
+//! - `module_view` - Sort and search of the detail view's module list
+//! - `power` - Heuristic power usage rating
+//! - `serve` - Read-only HTTP/JSON endpoint (`--serve`)
+//! - `session` - Selection, sort and filter saved between launches
+//! - `source` - Process data source used by refresh (mockable in tests)
 
 mod affinity;
 mod cli;
+mod csv_column;
 mod detail_view;
 mod export;
+mod filter_scope;
+mod find;
+mod histogram;
+mod idle_mode;
 mod input;
+mod json;
+mod kill_hold;
+mod module_view;
 mod navigation;
+mod power;
 mod process_entry;
 mod process_ops;
+mod serve;
+mod session;
 mod sort;
+mod source;
 mod state;
 mod tree_builder;
 mod view_mode;
@@ -31,15 +54,36 @@ mod view_mode;
 // ============================================================================
 
 // CLI argument parsing
-pub use cli::parse_args;
+pub use cli::{parse_args, OutputFormat};
 
-// CSV export
-pub use export::export_to_csv;
+// CSV export and text reports
+pub use csv_column::CsvColumn;
+pub use export::{
+    export_details_to_csv, export_to_csv, format_details_report, write_process_csv,
+};
+
+// HTTP endpoint
+pub use serve::serve;
+
+// Session persistence
+pub use session::{load_session, save_session, Session};
+
+// JSON serialization
+pub use json::{details_to_json, processes_to_json};
 
 // Input handling
 pub use input::KeyAction;
 
+// Histogram bands
+pub use histogram::{cpu_bands, memory_bands};
+
+// Affinity grid layout
+pub use affinity::affinity_grid_columns;
+
 // Core types
+pub use filter_scope::FilterScope;
+pub use idle_mode::IdleMode;
+pub use module_view::ModuleView;
 pub use process_entry::ProcessEntry;
 pub use sort::SortColumn;
 pub use state::App;
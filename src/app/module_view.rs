@@ -0,0 +1,115 @@
+//! Sort order and search of the detail view's module list
+
+use crate::system::ModuleInfo;
+
+/// Order of the detail view's module list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModuleSort {
+    /// As enumerated (the executable first, then in load order)
+    #[default]
+    LoadOrder,
+    /// By module name, case-insensitive
+    Name,
+    /// By base address, lowest first
+    BaseAddress,
+}
+
+impl ModuleSort {
+    /// Cycles to the next order (Load order → Name → Base address → Load order).
+    pub fn next(self) -> Self {
+        match self {
+            ModuleSort::LoadOrder => ModuleSort::Name,
+            ModuleSort::Name => ModuleSort::BaseAddress,
+            ModuleSort::BaseAddress => ModuleSort::LoadOrder,
+        }
+    }
+
+    /// Gets the label shown in the module section header.
+    pub fn label(self) -> &'static str {
+        match self {
+            ModuleSort::LoadOrder => "load order",
+            ModuleSort::Name => "name",
+            ModuleSort::BaseAddress => "address",
+        }
+    }
+}
+
+/// Sort and search state of the detail view's module list
+#[derive(Debug, Clone, Default)]
+pub struct ModuleView {
+    /// Current order
+    pub sort: ModuleSort,
+    /// Text matched against module names and paths (case-insensitive)
+    pub filter: String,
+    /// Whether keys are being typed into the filter
+    pub filter_input: bool,
+}
+
+impl ModuleView {
+    /// Applies the current search and order to a module list.
+    ///
+    /// # Arguments
+    /// * `modules` - Modules as enumerated
+    ///
+    /// # Returns
+    /// The matching modules in display order.
+    pub fn apply<'a>(&self, modules: &'a [ModuleInfo]) -> Vec<&'a ModuleInfo> {
+        let filter_lower = self.filter.to_lowercase();
+        let mut shown: Vec<&ModuleInfo> = modules
+            .iter()
+            .filter(|m| {
+                filter_lower.is_empty()
+                    || m.name.to_lowercase().contains(&filter_lower)
+                    || m.path.to_lowercase().contains(&filter_lower)
+            })
+            .collect();
+
+        match self.sort {
+            ModuleSort::LoadOrder => {}
+            ModuleSort::Name => shown.sort_by_key(|m| m.name.to_lowercase()),
+            ModuleSort::BaseAddress => shown.sort_by_key(|m| m.base_address),
+        }
+        shown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module(name: &str, path: &str, base_address: usize) -> ModuleInfo {
+        ModuleInfo { name: name.to_string(), path: path.to_string(), base_address }
+    }
+
+    fn names(modules: &[&ModuleInfo]) -> Vec<String> {
+        modules.iter().map(|m| m.name.clone()).collect()
+    }
+
+    #[test]
+    fn test_sort_and_filter() {
+        let modules = vec![
+            module("app.exe", r"C:\App\app.exe", 0x4000),
+            module("ntdll.dll", r"C:\Windows\System32\ntdll.dll", 0x9000),
+            module("Inject.dll", r"C:\Temp\inject.dll", 0x1000),
+        ];
+        let mut view = ModuleView::default();
+        assert_eq!(names(&view.apply(&modules)), ["app.exe", "ntdll.dll", "Inject.dll"]);
+
+        view.sort = ModuleSort::Name;
+        assert_eq!(names(&view.apply(&modules)), ["app.exe", "Inject.dll", "ntdll.dll"]);
+
+        view.sort = ModuleSort::BaseAddress;
+        assert_eq!(names(&view.apply(&modules)), ["Inject.dll", "app.exe", "ntdll.dll"]);
+
+        // Paths are searched too
+        view.filter = "TEMP".to_string();
+        assert_eq!(names(&view.apply(&modules)), ["Inject.dll"]);
+    }
+
+    #[test]
+    fn test_sort_cycle() {
+        assert_eq!(ModuleSort::LoadOrder.next(), ModuleSort::Name);
+        assert_eq!(ModuleSort::Name.next(), ModuleSort::BaseAddress);
+        assert_eq!(ModuleSort::BaseAddress.next(), ModuleSort::LoadOrder);
+    }
+}
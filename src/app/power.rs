@@ -0,0 +1,89 @@
+//! Heuristic per-process power usage rating
+//!
+//! Windows derives Task Manager's "Power usage" column from energy
+//! counters that are not exposed to applications. This estimate weights
+//! metrics already collected (CPU% and disk rate) into the same
+//! qualitative levels.
+
+use crate::constants::{BYTES_PER_MB, POWER_DISK_MB_WEIGHT, POWER_SCORE_THRESHOLDS};
+
+use super::ProcessEntry;
+
+/// Qualitative power usage level, lowest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PowerUsage {
+    VeryLow,
+    Low,
+    Moderate,
+    High,
+    VeryHigh,
+}
+
+impl PowerUsage {
+    /// Maps an activity score (see `ProcessEntry::power_score`) to a level.
+    pub fn from_score(score: f64) -> Self {
+        let [low, moderate, high, very_high] = POWER_SCORE_THRESHOLDS;
+        if score >= very_high {
+            PowerUsage::VeryHigh
+        } else if score >= high {
+            PowerUsage::High
+        } else if score >= moderate {
+            PowerUsage::Moderate
+        } else if score >= low {
+            PowerUsage::Low
+        } else {
+            PowerUsage::VeryLow
+        }
+    }
+
+    /// Gets the display name, e.g. "Very High".
+    pub fn name(self) -> &'static str {
+        match self {
+            PowerUsage::VeryLow => "Very Low",
+            PowerUsage::Low => "Low",
+            PowerUsage::Moderate => "Moderate",
+            PowerUsage::High => "High",
+            PowerUsage::VeryHigh => "Very High",
+        }
+    }
+
+    /// Gets the short name for the process list column.
+    pub fn short_name(self) -> &'static str {
+        match self {
+            PowerUsage::VeryLow => "V.Low",
+            PowerUsage::Low => "Low",
+            PowerUsage::Moderate => "Mod",
+            PowerUsage::High => "High",
+            PowerUsage::VeryHigh => "V.High",
+        }
+    }
+}
+
+impl ProcessEntry {
+    /// Activity score behind the power estimate: CPU% plus disk
+    /// throughput weighted per MB/s.
+    pub fn power_score(&self) -> f64 {
+        let disk_mb = (self.disk_read_rate + self.disk_write_rate) / BYTES_PER_MB;
+        self.cpu_percent + disk_mb * POWER_DISK_MB_WEIGHT
+    }
+
+    /// Estimated power usage level.
+    pub fn power_usage(&self) -> PowerUsage {
+        PowerUsage::from_score(self.power_score())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_score() {
+        assert_eq!(PowerUsage::from_score(0.0), PowerUsage::VeryLow);
+        assert_eq!(PowerUsage::from_score(POWER_SCORE_THRESHOLDS[0]), PowerUsage::Low);
+        assert_eq!(PowerUsage::from_score(POWER_SCORE_THRESHOLDS[1]), PowerUsage::Moderate);
+        assert_eq!(PowerUsage::from_score(POWER_SCORE_THRESHOLDS[2]), PowerUsage::High);
+        assert_eq!(PowerUsage::from_score(100.0), PowerUsage::VeryHigh);
+        assert!(PowerUsage::Low < PowerUsage::High);
+    }
+}
@@ -10,8 +10,19 @@ pub struct ProcessEntry {
     pub info: ProcessInfo,
     /// CPU usage percentage
     pub cpu_percent: f64,
+    /// Average CPU usage percentage since first seen (or last reset)
+    pub cpu_avg_percent: f64,
+    /// Peak CPU usage percentage since first seen (or last reset)
+    pub cpu_peak_percent: f64,
+    /// Total CPU time (kernel + user) consumed since start, in seconds
+    pub cpu_time_seconds: u64,
     /// Memory usage in bytes (working set)
     pub memory_bytes: u64,
+    /// Working set as a percentage of physical memory
+    pub mem_percent: f64,
+    /// Working set change since the last refresh, in bytes per second
+    /// (negative when shrinking)
+    pub memory_growth_rate: f64,
     /// Disk read bytes (total since process start)
     pub disk_read: u64,
     /// Disk write bytes (total since process start)
@@ -20,6 +31,10 @@ pub struct ProcessEntry {
     pub disk_read_rate: f64,
     /// Disk write rate in bytes per second
     pub disk_write_rate: f64,
+    /// Network (TCP) receive rate in bytes per second
+    pub net_recv_rate: f64,
+    /// Network (TCP) send rate in bytes per second
+    pub net_send_rate: f64,
     /// Process priority class
     pub priority: Priority,
     /// Number of threads in the process
@@ -32,6 +47,23 @@ pub struct ProcessEntry {
     pub path: Option<String>,
     /// Number of open handles
     pub handle_count: u32,
+    /// Number of running processes whose parent is this one
+    pub child_count: u32,
+    /// Memory counters could not be read (shown as unavailable, not zero)
+    pub memory_access_denied: bool,
+    /// Limited-information queries were denied, so CPU, CPU time and
+    /// handle count are unavailable rather than zero
+    pub query_access_denied: bool,
+    /// Whether the process is 32-bit under WOW64 (None if inaccessible)
+    pub is_32bit: Option<bool>,
+    /// Whether a debugger is attached (None if inaccessible)
+    pub being_debugged: Option<bool>,
+    /// Whether efficiency mode (EcoQoS) is on (None if inaccessible)
+    pub efficiency_mode: Option<bool>,
+    /// Terminal Services session ID (0 = services, None if exited)
+    pub session_id: Option<u32>,
+    /// Whether the process is owned by the user running this application
+    pub is_current_user: bool,
     /// Tree depth for tree view (0 = root)
     pub tree_depth: usize,
 }
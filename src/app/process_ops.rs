@@ -1,53 +1,162 @@
-//! Process management operations (kill, suspend, priority)
-
-use windows::Win32::Foundation::CloseHandle;
-use windows::Win32::System::Threading::{OpenProcess, TerminateProcess, PROCESS_TERMINATE};
+//! Process management operations (kill, suspend, priority, shell actions)
 
 use super::state::App;
-use super::ViewMode;
-use crate::system::{is_process_suspended, set_process_priority, toggle_suspend};
+use super::{ProcessEntry, ViewMode};
+use crate::system::{
+    copy_to_clipboard, format_bytes, get_priority_boost, get_process_protection,
+    get_process_protection_name, resume_process, reveal_in_explorer, set_efficiency_mode,
+    set_priority_boost, set_process_priority, suspend_process, terminate_process, ByteUnits,
+    Priority, ProcessResult,
+};
+
+impl ProcessEntry {
+    /// Summarizes what killing this process affects, for the kill
+    /// confirmation, e.g. "23.0% CPU, 1.2 GiB, 14 children".
+    ///
+    /// Metrics that could not be read are left out, so the summary never
+    /// shows a misleading zero.
+    ///
+    /// # Arguments
+    /// * `units` - Binary (KiB) or decimal (KB) units for memory
+    pub fn kill_impact(&self, units: ByteUnits) -> String {
+        let mut parts = Vec::new();
+        if !self.query_access_denied {
+            parts.push(format!("{:.1}% CPU", self.cpu_percent));
+        }
+        if !self.memory_access_denied {
+            parts.push(format_bytes(self.memory_bytes, units));
+        }
+        parts.push(match self.child_count {
+            0 => "no children".to_string(),
+            1 => "1 child".to_string(),
+            n => format!("{} children", n),
+        });
+        parts.join(", ")
+    }
+}
 
 impl App {
-    /// Requests to kill the currently selected process (shows confirmation)
+    /// Returns the multi-selected processes (PID, name, priority) in list order
+    pub(super) fn batch_targets(&self) -> Vec<(u32, String, Priority)> {
+        self.processes
+            .iter()
+            .filter(|p| self.selected_pids.contains(&p.info.pid))
+            .map(|p| (p.info.pid, p.info.name.clone(), p.priority))
+            .collect()
+    }
+
+    /// Warns instead of acting when a process is protected (PP/PPL), since
+    /// killing, suspending and reprioritizing it would all be denied.
+    ///
+    /// # Returns
+    /// `true` if the process is protected and the action should be skipped.
+    pub(super) fn warn_if_protected(&mut self, pid: u32, name: &str, action: &str) -> bool {
+        if !get_process_protection(pid).protected {
+            return false;
+        }
+        let signer = get_process_protection_name(pid).unwrap_or_else(|| "protected".to_string());
+        self.error_message = Some(format!(
+            "Cannot {} {} (PID {}): protected process ({})",
+            action, name, pid, signer
+        ));
+        true
+    }
+
+    /// Requests to kill the currently selected process, or every
+    /// multi-selected process when a selection exists.
+    ///
+    /// Shows a Y/N confirmation, or kills immediately when
+    /// `no_confirm_kill` is enabled.
     pub fn request_kill(&mut self) {
-        if self.filtered_processes.is_empty() {
+        if !self.selected_pids.is_empty() {
+            self.pending_kill_batch = self
+                .batch_targets()
+                .into_iter()
+                .map(|(pid, name, _)| (pid, name))
+                .collect();
+
+            if self.no_confirm_kill {
+                self.confirm_kill();
+                self.refresh();
+                return;
+            }
+
+            self.view_mode = ViewMode::ConfirmKill;
+            self.error_message = Some(format!(
+                "Kill {} selected processes? Press Y to confirm, N to cancel",
+                self.pending_kill_batch.len()
+            ));
+            return;
+        }
+
+        self.request_kill_current();
+    }
+
+    /// Requests to kill only the process under the cursor, ignoring any
+    /// multi-selection (e.g. when killing from the detail view).
+    pub fn request_kill_current(&mut self) {
+        let process = match self.selected_process() {
+            Some(process) => process,
+            None => return,
+        };
+        let (pid, name) = (process.info.pid, process.info.name.clone());
+        if self.warn_if_protected(pid, &name, "kill") {
             return;
         }
 
-        let process = &self.filtered_processes[self.selected_index];
-        self.pending_kill_pid = Some(process.info.pid);
-        self.pending_kill_name = Some(process.info.name.clone());
-        self.view_mode = ViewMode::ConfirmKill;
         self.error_message = Some(format!(
             "Kill {} (PID {})? Press Y to confirm, N to cancel",
-            process.info.name, process.info.pid
+            name, pid
         ));
+        self.pending_kill_pid = Some(pid);
+        self.pending_kill_name = Some(name);
+
+        if self.no_confirm_kill {
+            self.confirm_kill();
+            self.refresh();
+            return;
+        }
+
+        self.view_mode = ViewMode::ConfirmKill;
     }
 
     /// Confirms and executes the pending kill
     pub fn confirm_kill(&mut self) {
+        if !self.pending_kill_batch.is_empty() {
+            let batch = std::mem::take(&mut self.pending_kill_batch);
+            let mut failures = Vec::new();
+            for (pid, name) in &batch {
+                if let Err(e) = terminate_process(*pid, self.kill_exit_code) {
+                    failures.push(format!("{} (PID {}): {}", name, pid, e));
+                }
+            }
+
+            self.error_message = Some(match failures.first() {
+                None => format!("Terminated {} processes", batch.len()),
+                Some(first) => format!(
+                    "Terminated {} of {} processes; failed {}",
+                    batch.len() - failures.len(),
+                    batch.len(),
+                    first
+                ),
+            });
+            self.selected_pids.clear();
+            self.cancel_kill();
+            return;
+        }
+
         let pid = match self.pending_kill_pid {
             Some(p) => p,
             None => return,
         };
         let name = self.pending_kill_name.clone().unwrap_or_default();
 
-        // SAFETY: OpenProcess and TerminateProcess are safe to call with valid params.
-        let result = unsafe {
-            let handle = OpenProcess(PROCESS_TERMINATE, false, pid);
-            match handle {
-                Ok(h) => {
-                    let term_result = TerminateProcess(h, 1);
-                    let _ = CloseHandle(h);
-                    term_result
-                }
-                Err(e) => Err(e),
-            }
-        };
-
-        match result {
+        match terminate_process(pid, self.kill_exit_code) {
             Ok(_) => {
                 self.error_message = Some(format!("Terminated process: {} (PID {})", name, pid));
+                if let Some(path) = self.pending_kill_hold.take() {
+                    self.start_kill_hold(&path, &name);
+                }
             }
             Err(e) => {
                 self.error_message = Some(format!(
@@ -65,24 +174,133 @@ impl App {
         self.view_mode = ViewMode::ProcessList;
         self.pending_kill_pid = None;
         self.pending_kill_name = None;
+        self.pending_kill_batch.clear();
+        self.pending_kill_hold = None;
     }
 
-    /// Raises the priority of the selected process
-    pub fn raise_priority(&mut self) {
-        if self.filtered_processes.is_empty() {
+    /// Toggles whether kills require Y/N confirmation
+    pub fn toggle_kill_confirmation(&mut self) {
+        self.no_confirm_kill = !self.no_confirm_kill;
+        self.error_message = Some(if self.no_confirm_kill {
+            "Kill confirmation disabled".to_string()
+        } else {
+            "Kill confirmation enabled".to_string()
+        });
+    }
+
+    /// Raises or lowers the priority of every multi-selected process
+    /// by one level, then clears the selection.
+    ///
+    /// Raising asks for confirmation first if it would put any of them
+    /// into Realtime.
+    fn change_batch_priority(&mut self, raise: bool) {
+        let targets = self.batch_targets();
+        if raise && targets.iter().any(|(_, _, p)| p.raise() == Priority::Realtime) {
+            self.request_realtime(targets);
             return;
         }
+        self.step_priorities(&targets, raise);
+        self.selected_pids.clear();
+    }
+
+    /// Moves each process one priority level up or down and reports
+    /// how many changed.
+    fn step_priorities(&mut self, targets: &[(u32, String, Priority)], raise: bool) {
+        let mut changed = 0;
+        let mut first_error = None;
+
+        for (pid, name, current) in targets {
+            let new_priority = if raise { current.raise() } else { current.lower() };
+            if new_priority == *current {
+                continue;
+            }
+            match set_process_priority(*pid, new_priority) {
+                Ok(()) => changed += 1,
+                Err(e) if first_error.is_none() => {
+                    first_error = Some(format!("{}: {}", name, e));
+                }
+                Err(_) => {}
+            }
+        }
+
+        let verb = if raise { "Raised" } else { "Lowered" };
+        self.error_message = Some(match first_error {
+            None => format!("{} priority of {} of {} processes", verb, changed, targets.len()),
+            Some(e) => format!(
+                "{} priority of {} of {} processes; failed {}",
+                verb, changed, targets.len(), e
+            ),
+        });
+    }
+
+    /// Asks for confirmation before raising processes to Realtime, which
+    /// can starve input, disk and system threads and hang the machine.
+    ///
+    /// # Arguments
+    /// * `targets` - Processes (PID, name, current priority) to raise
+    fn request_realtime(&mut self, targets: Vec<(u32, String, Priority)>) {
+        self.error_message = Some(
+            "Realtime priority can freeze the system. Press Y to confirm, N to cancel".to_string(),
+        );
+        self.pending_realtime = targets;
+        self.view_mode = ViewMode::ConfirmRealtime;
+    }
+
+    /// Applies the raise that was waiting for Realtime confirmation
+    pub fn confirm_realtime(&mut self) {
+        let targets = std::mem::take(&mut self.pending_realtime);
+        self.view_mode = ViewMode::ProcessList;
+
+        match targets.as_slice() {
+            [] => {}
+            // A single process keeps the usual "old → new" message
+            [(pid, name, current)] if self.selected_pids.is_empty() => {
+                let new_priority = current.raise();
+                self.error_message = Some(match set_process_priority(*pid, new_priority) {
+                    Ok(()) => format!("{}: {} → {}", name, current.name(), new_priority.name()),
+                    Err(e) => format!("Failed to raise priority: {}", e),
+                });
+            }
+            _ => {
+                self.step_priorities(&targets, true);
+                self.selected_pids.clear();
+            }
+        }
+    }
+
+    /// Cancels the pending raise to Realtime
+    pub fn cancel_realtime(&mut self) {
+        self.view_mode = ViewMode::ProcessList;
+        self.pending_realtime.clear();
+        self.error_message = Some("Priority unchanged".to_string());
+    }
 
-        let process = &self.filtered_processes[self.selected_index];
+    /// Raises the priority of the selected process (or multi-selection)
+    pub fn raise_priority(&mut self) {
+        if !self.selected_pids.is_empty() {
+            self.change_batch_priority(true);
+            return;
+        }
+        let process = match self.selected_process() {
+            Some(process) => process,
+            None => return,
+        };
         let pid = process.info.pid;
         let name = process.info.name.clone();
         let current = process.priority;
         let new_priority = current.raise();
+        if self.warn_if_protected(pid, &name, "change priority of") {
+            return;
+        }
 
         if current == new_priority {
             self.error_message = Some(format!("{} is already at maximum priority", name));
             return;
         }
+        if new_priority == Priority::Realtime {
+            self.request_realtime(vec![(pid, name, current)]);
+            return;
+        }
 
         match set_process_priority(pid, new_priority) {
             Ok(_) => {
@@ -97,17 +315,23 @@ impl App {
         }
     }
 
-    /// Lowers the priority of the selected process
+    /// Lowers the priority of the selected process (or multi-selection)
     pub fn lower_priority(&mut self) {
-        if self.filtered_processes.is_empty() {
+        if !self.selected_pids.is_empty() {
+            self.change_batch_priority(false);
             return;
         }
-
-        let process = &self.filtered_processes[self.selected_index];
+        let process = match self.selected_process() {
+            Some(process) => process,
+            None => return,
+        };
         let pid = process.info.pid;
         let name = process.info.name.clone();
         let current = process.priority;
         let new_priority = current.lower();
+        if self.warn_if_protected(pid, &name, "change priority of") {
+            return;
+        }
 
         if current == new_priority {
             self.error_message = Some(format!("{} is already at minimum priority", name));
@@ -127,17 +351,47 @@ impl App {
         }
     }
 
-    /// Toggle suspend/resume for the selected process
+    /// Toggle suspend/resume for the selected process (or multi-selection)
     pub fn toggle_suspend(&mut self) {
-        if self.filtered_processes.is_empty() {
+        if !self.selected_pids.is_empty() {
+            let targets = self.batch_targets();
+            let (mut suspended, mut resumed) = (0, 0);
+            let mut first_error = None;
+
+            for (pid, name, _) in &targets {
+                match self.toggle_suspend_pid(*pid) {
+                    Ok(true) => suspended += 1,
+                    Ok(false) => resumed += 1,
+                    Err(e) if first_error.is_none() => {
+                        first_error = Some(format!("{}: {}", name, e));
+                    }
+                    Err(_) => {}
+                }
+            }
+
+            let summary = format!(
+                "Suspended {}, resumed {} of {} processes",
+                suspended, resumed, targets.len()
+            );
+            self.error_message = Some(match first_error {
+                None => summary,
+                Some(e) => format!("{}; failed {}", summary, e),
+            });
+            self.selected_pids.clear();
             return;
         }
 
-        let process = &self.filtered_processes[self.selected_index];
+        let process = match self.selected_process() {
+            Some(process) => process,
+            None => return,
+        };
         let pid = process.info.pid;
         let name = process.info.name.clone();
+        if self.warn_if_protected(pid, &name, "suspend") {
+            return;
+        }
 
-        match toggle_suspend(pid) {
+        match self.toggle_suspend_pid(pid) {
             Ok(is_suspended) => {
                 if is_suspended {
                     self.error_message = Some(format!("Suspended: {} (PID {})", name, pid));
@@ -151,8 +405,139 @@ impl App {
         }
     }
 
+    /// Suspends a process, or resumes it if this app suspended it.
+    ///
+    /// # Returns
+    /// `true` if the process is now suspended, `false` if it was resumed.
+    fn toggle_suspend_pid(&mut self, pid: u32) -> ProcessResult<bool> {
+        if self.suspended_pids.contains(&pid) {
+            resume_process(pid)?;
+            self.suspended_pids.remove(&pid);
+            Ok(false)
+        } else {
+            suspend_process(pid)?;
+            self.suspended_pids.insert(pid);
+            Ok(true)
+        }
+    }
+
+    /// Opens Explorer with the selected process's executable selected
+    pub fn open_containing_folder(&mut self) {
+        let process = match self.selected_process() {
+            Some(process) => process,
+            None => return,
+        };
+        let path = match process.path {
+            Some(ref p) => p.clone(),
+            None => {
+                self.error_message = Some(format!(
+                    "Path unavailable for {} (access denied)",
+                    process.info.name
+                ));
+                return;
+            }
+        };
+
+        self.error_message = Some(match reveal_in_explorer(&path) {
+            Ok(()) => format!("Opened in Explorer: {}", path),
+            Err(e) => format!("Failed to open Explorer: {}", e),
+        });
+    }
+
+    /// Turns efficiency mode (EcoQoS) on or off for the selected process
+    pub fn toggle_efficiency_mode(&mut self) {
+        let process = match self.selected_process() {
+            Some(process) => process,
+            None => return,
+        };
+        let (pid, name) = (process.info.pid, process.info.name.clone());
+        let enable = process.efficiency_mode != Some(true);
+        if self.warn_if_protected(pid, &name, "change efficiency mode of") {
+            return;
+        }
+
+        self.error_message = Some(match set_efficiency_mode(pid, enable) {
+            Ok(()) => format!(
+                "Efficiency mode {} for {} (PID {})",
+                if enable { "on" } else { "off" },
+                name,
+                pid
+            ),
+            Err(e) => format!("Failed to change efficiency mode of {}: {}", name, e),
+        });
+    }
+
+    /// Enables or disables dynamic priority boost for the selected process
+    pub fn toggle_priority_boost(&mut self) {
+        let process = match self.selected_process() {
+            Some(process) => process,
+            None => return,
+        };
+        let (pid, name) = (process.info.pid, process.info.name.clone());
+        let enable = match get_priority_boost(pid) {
+            Some(enabled) => !enabled,
+            None => {
+                self.error_message =
+                    Some(format!("Cannot read priority boost of {} (access denied)", name));
+                return;
+            }
+        };
+        if self.warn_if_protected(pid, &name, "change priority boost of") {
+            return;
+        }
+
+        self.error_message = Some(match set_priority_boost(pid, enable) {
+            Ok(()) => format!(
+                "Priority boost {} for {} (PID {})",
+                if enable { "enabled" } else { "disabled" },
+                name,
+                pid
+            ),
+            Err(e) => format!("Failed to change priority boost of {}: {}", name, e),
+        });
+    }
+
+    /// Copies the selected process's executable path to the clipboard
+    pub fn copy_process_path(&mut self) {
+        let process = match self.selected_process() {
+            Some(process) => process,
+            None => return,
+        };
+        let path = match process.path {
+            Some(ref p) => p.clone(),
+            None => {
+                self.error_message = Some(format!(
+                    "Path unavailable for {} (access denied)",
+                    process.info.name
+                ));
+                return;
+            }
+        };
+
+        self.error_message = Some(match copy_to_clipboard(&path) {
+            Ok(()) => format!("Copied path: {}", path),
+            Err(e) => format!("Failed to copy path: {}", e),
+        });
+    }
+
+    /// Copies the detail view's text to the clipboard
+    ///
+    /// # Arguments
+    /// * `text` - The detail view rendered as plain text
+    pub fn copy_detail_text(&mut self, text: &str) {
+        let name = match self.detail_view_data {
+            Some(ref d) => d.name.clone(),
+            None => return,
+        };
+
+        self.error_message = Some(match copy_to_clipboard(text) {
+            Ok(()) => format!("Copied details of {} ({} lines)", name, text.lines().count()),
+            Err(e) => format!("Failed to copy details: {}", e),
+        });
+    }
+
     /// Check if a process is suspended
     pub fn is_process_suspended(&self, pid: u32) -> bool {
-        is_process_suspended(pid)
+        self.suspended_pids.contains(&pid)
     }
 }
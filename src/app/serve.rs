@@ -0,0 +1,203 @@
+//! Read-only HTTP/JSON endpoint for remote monitoring (`--serve`)
+//!
+//! A minimal HTTP/1.1 responder on a std `TcpListener`: one request per
+//! connection, handled on the refresh thread between refreshes, so no
+//! locking is needed. Only `GET /processes` is served, returning the
+//! same process table the list shows (after `--filter`, sorted by
+//! `--sort`) as a JSON array. It binds to localhost unless `--bind`
+//! says otherwise; there is no authentication, so exposing it on other
+//! interfaces is left to the user's judgement.
+
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::constants::{SERVE_MAX_REQUEST_BYTES, SERVE_POLL_MS, SERVE_READ_TIMEOUT_MS};
+
+use super::json::processes_to_json;
+use super::state::App;
+
+/// A response to send back: status line text, content type and body
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    /// Status code and reason, e.g. "200 OK"
+    pub status: &'static str,
+    /// Value of the Content-Type header
+    pub content_type: &'static str,
+    /// Response body
+    pub body: String,
+}
+
+impl Response {
+    /// Creates a plain-text error response.
+    fn text(status: &'static str, body: &str) -> Self {
+        Self { status, content_type: "text/plain; charset=utf-8", body: format!("{}\n", body) }
+    }
+
+    /// Formats the full HTTP response, closing the connection afterwards.
+    fn to_http(&self) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.content_type,
+            self.body.len(),
+            self.body
+        )
+    }
+}
+
+/// Picks the response for a request line such as `GET /processes HTTP/1.1`.
+///
+/// # Arguments
+/// * `request_line` - The first line of the request
+/// * `app` - Application state holding the latest refresh
+///
+/// # Returns
+/// The response to send; unknown paths get 404 and methods other than GET 405.
+pub fn route(request_line: &str, app: &App) -> Response {
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => (method, target),
+        _ => return Response::text("400 Bad Request", "Bad request"),
+    };
+    if method != "GET" {
+        return Response::text("405 Method Not Allowed", "Only GET is supported");
+    }
+    // Query strings are accepted but not used
+    let path = target.split('?').next().unwrap_or(target);
+    match path {
+        "/processes" => Response {
+            status: "200 OK",
+            content_type: "application/json",
+            body: processes_to_json(&app.filtered_processes),
+        },
+        _ => Response::text("404 Not Found", "Not found; try GET /processes"),
+    }
+}
+
+/// Reads one request and writes its response.
+///
+/// The whole request must arrive within `SERVE_READ_TIMEOUT_MS`, not just
+/// each read, so a client trickling bytes cannot hold up refreshes.
+fn handle_connection(mut stream: TcpStream, app: &App) -> io::Result<()> {
+    // Accepted sockets inherit the listener's non-blocking mode on Windows
+    stream.set_nonblocking(false)?;
+    stream.set_write_timeout(Some(Duration::from_millis(SERVE_READ_TIMEOUT_MS)))?;
+    let deadline = Instant::now() + Duration::from_millis(SERVE_READ_TIMEOUT_MS);
+
+    // Only the request line matters; read until the headers end
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < SERVE_MAX_REQUEST_BYTES {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "request not received in time"));
+        }
+        stream.set_read_timeout(Some(remaining))?;
+        let read = stream.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+
+    let text = String::from_utf8_lossy(&request);
+    let request_line = text.lines().next().unwrap_or("");
+    stream.write_all(route(request_line, app).to_http().as_bytes())?;
+    stream.flush()
+}
+
+/// Serves the process table over HTTP until the process is stopped,
+/// refreshing every `refresh_interval_ms` between requests.
+///
+/// # Arguments
+/// * `app` - Application state to refresh and serve
+/// * `addr` - Address and port to listen on
+///
+/// # Returns
+/// * `Err(io::Error)` - If the address cannot be bound (runs forever otherwise)
+pub fn serve(app: &mut App, addr: SocketAddr) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    listener.set_nonblocking(true)?;
+
+    // Two samples so the first response has real CPU% and rates
+    app.refresh();
+    thread::sleep(Duration::from_millis(app.refresh_interval_ms.min(1000)));
+    app.refresh();
+    let mut last_refresh = Instant::now();
+
+    loop {
+        let accepted = match listener.accept() {
+            Ok((stream, _)) => {
+                // A client hanging up or timing out only affects its own request
+                let _ = handle_connection(stream, app);
+                true
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => false,
+            Err(e) => {
+                eprintln!("Accept failed: {}", e);
+                false
+            }
+        };
+
+        // Checked after every request too, so a steady stream of clients
+        // cannot hold off refreshes
+        if last_refresh.elapsed() >= Duration::from_millis(app.refresh_interval_ms) {
+            app.refresh();
+            last_refresh = Instant::now();
+        }
+        // Waiting clients are served back to back, without the poll pause
+        if !accepted {
+            thread::sleep(Duration::from_millis(SERVE_POLL_MS));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::source::MockProcessSource;
+
+    #[test]
+    fn test_route() {
+        let source = MockProcessSource::default().with_process(101, 1, "app.exe", 1024);
+        let mut app = App::with_source(Box::new(source));
+        app.refresh();
+
+        let ok = route("GET /processes?pretty=1 HTTP/1.1", &app);
+        assert_eq!(ok.status, "200 OK");
+        assert_eq!(ok.content_type, "application/json");
+        assert!(ok.body.starts_with(r#"[{"pid":101,"#));
+        assert!(ok.to_http().contains(&format!("Content-Length: {}\r\n", ok.body.len())));
+
+        assert_eq!(route("GET / HTTP/1.1", &app).status, "404 Not Found");
+        assert_eq!(route("POST /processes HTTP/1.1", &app).status, "405 Method Not Allowed");
+        assert_eq!(route("", &app).status, "400 Bad Request");
+    }
+
+    #[test]
+    fn test_slow_client_times_out() {
+        let app = App::with_source(Box::new(MockProcessSource::default()));
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // Each byte arrives well within the timeout, but the request never ends
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            for _ in 0..8 {
+                if stream.write_all(b"G").is_err() {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(SERVE_READ_TIMEOUT_MS / 4));
+            }
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let started = Instant::now();
+        let result = handle_connection(stream, &app);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::TimedOut);
+        assert!(started.elapsed() < Duration::from_millis(SERVE_READ_TIMEOUT_MS * 2));
+        client.join().unwrap();
+    }
+}
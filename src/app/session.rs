@@ -0,0 +1,194 @@
+//! Remembering where the user was between launches
+//!
+//! On exit the interactive view saves the selected PID, sort column,
+//! sort direction and filter to `session.txt` under
+//! `%LOCALAPPDATA%\<app name>`; the next launch restores them. Scroll
+//! position is not saved: the list changes between runs, so the selected
+//! PID (resolved after the first refresh, like `--select`) is used to
+//! land near the same place instead.
+//!
+//! The file holds one `key=value` pair per line. Unknown keys and bad
+//! values are ignored, so a damaged file only loses the broken entries.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::constants::{APP_NAME, SESSION_FILE_NAME};
+
+use super::cli::Args;
+use super::state::App;
+use super::SortColumn;
+
+/// What is saved between launches
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Session {
+    /// PID that was selected (None if the list was empty)
+    pub selected_pid: Option<u32>,
+    /// Sort column (None if missing from the file)
+    pub sort_column: Option<SortColumn>,
+    /// Whether the sort was ascending
+    pub sort_ascending: bool,
+    /// Filter text (empty = no filter)
+    pub filter: String,
+}
+
+impl Session {
+    /// Parses the session file's contents.
+    ///
+    /// # Arguments
+    /// * `text` - The file contents
+    ///
+    /// # Returns
+    /// The saved session; missing or invalid entries keep their defaults.
+    pub fn parse(text: &str) -> Self {
+        let mut session = Self::default();
+        for line in text.lines() {
+            let (key, value) = match line.split_once('=') {
+                Some(pair) => pair,
+                None => continue,
+            };
+            match key.trim() {
+                "selected_pid" => session.selected_pid = value.trim().parse().ok(),
+                "sort" => {
+                    session.sort_column =
+                        SortColumn::ALL.iter().copied().find(|c| c.name() == value.trim());
+                }
+                "ascending" => session.sort_ascending = value.trim() == "true",
+                // Not trimmed: leading/trailing spaces may be part of the filter
+                "filter" => session.filter = value.to_string(),
+                _ => {}
+            }
+        }
+        session
+    }
+
+    /// Formats the session for writing to the file.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        if let Some(pid) = self.selected_pid {
+            text.push_str(&format!("selected_pid={}\n", pid));
+        }
+        if let Some(column) = self.sort_column {
+            text.push_str(&format!("sort={}\n", column.name()));
+        }
+        text.push_str(&format!("ascending={}\n", self.sort_ascending));
+        text.push_str(&format!("filter={}\n", self.filter));
+        text
+    }
+}
+
+/// Gets the path of the session file.
+///
+/// # Returns
+/// * `Option<PathBuf>` - The path, or None if `LOCALAPPDATA` is not set
+fn session_path() -> Option<PathBuf> {
+    let base = std::env::var_os("LOCALAPPDATA")?;
+    Some(PathBuf::from(base).join(APP_NAME).join(SESSION_FILE_NAME))
+}
+
+/// Loads the session saved by the previous launch.
+///
+/// # Returns
+/// * `Option<Session>` - The saved session, or None if there is none
+pub fn load_session() -> Option<Session> {
+    let text = fs::read_to_string(session_path()?).ok()?;
+    Some(Session::parse(&text))
+}
+
+/// Saves a session for the next launch, creating its folder if needed.
+///
+/// # Arguments
+/// * `session` - The session to save
+pub fn save_session(session: &Session) -> io::Result<()> {
+    let path = session_path()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "LOCALAPPDATA is not set"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, session.to_text())
+}
+
+impl App {
+    /// Captures the current selection, sort and filter.
+    pub fn capture_session(&self) -> Session {
+        Session {
+            selected_pid: self.selected_process().map(|p| p.info.pid),
+            sort_column: Some(self.sort_column),
+            sort_ascending: self.sort_ascending,
+            filter: self.filter.clone(),
+        }
+    }
+
+    /// Restores a saved session, except where the command line already
+    /// chose the sort, filter or selection.
+    ///
+    /// # Arguments
+    /// * `session` - The session saved by the previous launch
+    /// * `args` - Parsed command-line arguments
+    pub fn restore_session(&mut self, session: &Session, args: &Args) {
+        if let (false, Some(column)) = (args.sort_given, session.sort_column) {
+            self.sort_column = column;
+            self.sort_ascending = session.sort_ascending;
+            self.sort_directions.insert(column, session.sort_ascending);
+        }
+        if args.filter.is_none() {
+            self.filter = session.filter.clone();
+        }
+        if args.select_pid.is_none() {
+            self.pending_select_pid = session.selected_pid;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_round_trip() {
+        let session = Session {
+            selected_pid: Some(1234),
+            sort_column: Some(SortColumn::MemGrowth),
+            sort_ascending: true,
+            filter: " chrome !gpu".to_string(),
+        };
+        assert_eq!(Session::parse(&session.to_text()), session);
+
+        // Damaged entries are dropped, the rest are kept
+        let damaged = Session::parse("selected_pid=abc\nsort=Bogus\nnonsense\nfilter=a=b\n");
+        assert_eq!(damaged.selected_pid, None);
+        assert_eq!(damaged.sort_column, None);
+        assert_eq!(damaged.filter, "a=b");
+    }
+
+    #[test]
+    fn test_restore_session() {
+        let session = Session {
+            selected_pid: Some(105),
+            sort_column: Some(SortColumn::Name),
+            sort_ascending: false,
+            filter: "note".to_string(),
+        };
+
+        let mut app = App::new();
+        app.restore_session(&session, &Args::default());
+        assert_eq!(app.sort_column, SortColumn::Name);
+        assert!(!app.sort_ascending);
+        assert_eq!(app.filter, "note");
+        assert_eq!(app.pending_select_pid, Some(105));
+
+        // The command line wins over the saved session
+        let args = Args {
+            sort_given: true,
+            filter: Some("chrome".to_string()),
+            select_pid: Some(7),
+            ..Args::default()
+        };
+        let mut app = App::with_args(&args);
+        app.restore_session(&session, &args);
+        assert_eq!(app.sort_column, SortColumn::Cpu);
+        assert_eq!(app.filter, "chrome");
+        assert_eq!(app.pending_select_pid, Some(7));
+    }
+}
@@ -1,37 +1,79 @@
 //! Sorting options for the process list
 
+use std::cmp::Ordering;
+
+use super::ProcessEntry;
+
 /// Sort column options
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SortColumn {
     Cpu,
+    CpuTime,
     Memory,
+    MemPercent,
+    MemGrowth,
     Name,
     Pid,
+    Session,
     Priority,
+    BasePriority,
     Threads,
     Handles,
+    Children,
     Uptime,
     DiskReadRate,
     DiskWriteRate,
+    Network,
+    Power,
 }
 
 impl SortColumn {
+    /// Every sort column, in cycle order.
+    pub const ALL: [SortColumn; 18] = [
+        SortColumn::Cpu,
+        SortColumn::CpuTime,
+        SortColumn::Memory,
+        SortColumn::MemPercent,
+        SortColumn::MemGrowth,
+        SortColumn::Name,
+        SortColumn::Pid,
+        SortColumn::Session,
+        SortColumn::Priority,
+        SortColumn::BasePriority,
+        SortColumn::Threads,
+        SortColumn::Handles,
+        SortColumn::Children,
+        SortColumn::Uptime,
+        SortColumn::DiskReadRate,
+        SortColumn::DiskWriteRate,
+        SortColumn::Network,
+        SortColumn::Power,
+    ];
+
     /// Cycles to the next sort column.
     ///
     /// # Returns
     /// The next `SortColumn` in the cycle.
     pub fn next(self) -> Self {
         match self {
-            SortColumn::Cpu => SortColumn::Memory,
-            SortColumn::Memory => SortColumn::Name,
+            SortColumn::Cpu => SortColumn::CpuTime,
+            SortColumn::CpuTime => SortColumn::Memory,
+            SortColumn::Memory => SortColumn::MemPercent,
+            SortColumn::MemPercent => SortColumn::MemGrowth,
+            SortColumn::MemGrowth => SortColumn::Name,
             SortColumn::Name => SortColumn::Pid,
-            SortColumn::Pid => SortColumn::Priority,
-            SortColumn::Priority => SortColumn::Threads,
+            SortColumn::Pid => SortColumn::Session,
+            SortColumn::Session => SortColumn::Priority,
+            SortColumn::Priority => SortColumn::BasePriority,
+            SortColumn::BasePriority => SortColumn::Threads,
             SortColumn::Threads => SortColumn::Handles,
-            SortColumn::Handles => SortColumn::Uptime,
+            SortColumn::Handles => SortColumn::Children,
+            SortColumn::Children => SortColumn::Uptime,
             SortColumn::Uptime => SortColumn::DiskReadRate,
             SortColumn::DiskReadRate => SortColumn::DiskWriteRate,
-            SortColumn::DiskWriteRate => SortColumn::Cpu,
+            SortColumn::DiskWriteRate => SortColumn::Network,
+            SortColumn::Network => SortColumn::Power,
+            SortColumn::Power => SortColumn::Cpu,
         }
     }
 
@@ -42,15 +84,96 @@ impl SortColumn {
     pub fn name(&self) -> &'static str {
         match self {
             SortColumn::Cpu => "CPU%",
+            SortColumn::CpuTime => "CPU Time",
             SortColumn::Memory => "Memory",
+            SortColumn::MemPercent => "Mem%",
+            SortColumn::MemGrowth => "Mem/s",
             SortColumn::Name => "Name",
             SortColumn::Pid => "PID",
+            SortColumn::Session => "Session",
             SortColumn::Priority => "Priority",
+            SortColumn::BasePriority => "Base",
             SortColumn::Threads => "Threads",
             SortColumn::Handles => "Handles",
+            SortColumn::Children => "Children",
             SortColumn::Uptime => "Uptime",
             SortColumn::DiskReadRate => "Read/s",
             SortColumn::DiskWriteRate => "Write/s",
+            SortColumn::Network => "Net/s",
+            SortColumn::Power => "Power",
+        }
+    }
+
+    /// Gets the direction this column sorts in until the user reverses it.
+    ///
+    /// # Returns
+    /// `true` (ascending) for text/ID columns (name, PID, session), `false`
+    /// (largest first) for numeric metrics.
+    pub fn default_ascending(self) -> bool {
+        matches!(self, SortColumn::Name | SortColumn::Pid | SortColumn::Session)
+    }
+
+    /// Compares two processes by this column.
+    ///
+    /// # Arguments
+    /// * `a`, `b` - Processes to compare
+    /// * `ascending` - Order A–Z/smallest first rather than Z–A/largest first
+    /// * `disk_totals` - Compare disk columns by lifetime bytes, not rates
+    pub fn compare(
+        self,
+        a: &ProcessEntry,
+        b: &ProcessEntry,
+        ascending: bool,
+        disk_totals: bool,
+    ) -> Ordering {
+        // Each arm yields the column's default order (see `default_ascending`)
+        let cmp = match self {
+            SortColumn::Cpu => b
+                .cpu_percent
+                .partial_cmp(&a.cpu_percent)
+                .unwrap_or(Ordering::Equal),
+            SortColumn::CpuTime => b.cpu_time_seconds.cmp(&a.cpu_time_seconds),
+            SortColumn::Memory => b.memory_bytes.cmp(&a.memory_bytes),
+            SortColumn::MemPercent => b
+                .mem_percent
+                .partial_cmp(&a.mem_percent)
+                .unwrap_or(Ordering::Equal),
+            SortColumn::MemGrowth => b
+                .memory_growth_rate
+                .partial_cmp(&a.memory_growth_rate)
+                .unwrap_or(Ordering::Equal),
+            SortColumn::Name => a.info.name.to_lowercase().cmp(&b.info.name.to_lowercase()),
+            SortColumn::Pid => a.info.pid.cmp(&b.info.pid),
+            SortColumn::Session => a.session_id.cmp(&b.session_id),
+            SortColumn::Priority => b.priority.cmp(&a.priority),
+            SortColumn::BasePriority => b.info.base_priority.cmp(&a.info.base_priority),
+            SortColumn::Threads => b.thread_count.cmp(&a.thread_count),
+            SortColumn::Handles => b.handle_count.cmp(&a.handle_count),
+            SortColumn::Children => b.child_count.cmp(&a.child_count),
+            SortColumn::Uptime => b.uptime_seconds.cmp(&a.uptime_seconds),
+            // The disk columns sort by whatever they are showing
+            SortColumn::DiskReadRate if disk_totals => b.disk_read.cmp(&a.disk_read),
+            SortColumn::DiskWriteRate if disk_totals => b.disk_write.cmp(&a.disk_write),
+            SortColumn::DiskReadRate => b
+                .disk_read_rate
+                .partial_cmp(&a.disk_read_rate)
+                .unwrap_or(Ordering::Equal),
+            SortColumn::DiskWriteRate => b
+                .disk_write_rate
+                .partial_cmp(&a.disk_write_rate)
+                .unwrap_or(Ordering::Equal),
+            SortColumn::Network => (b.net_recv_rate + b.net_send_rate)
+                .partial_cmp(&(a.net_recv_rate + a.net_send_rate))
+                .unwrap_or(Ordering::Equal),
+            SortColumn::Power => b
+                .power_score()
+                .partial_cmp(&a.power_score())
+                .unwrap_or(Ordering::Equal),
+        };
+        if ascending == self.default_ascending() {
+            cmp
+        } else {
+            cmp.reverse()
         }
     }
 }
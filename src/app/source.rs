@@ -0,0 +1,221 @@
+//! Where `App::refresh` gets its process data
+//!
+//! The `ProcessSource` trait wraps the `system` getters that refresh calls
+//! per process, so `App` logic (filtering, sorting, tree building) can be
+//! exercised with synthetic processes. CPU usage is not part of the
+//! source; it comes from the stateful `CpuTracker`.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::ffi::ProcessHandle;
+use crate::system::{
+    enumerate_processes, get_connection_pids, get_current_user_sid, get_efficiency_mode_with,
+    get_process_disk_info_with, get_process_handle_count_with, get_process_memory_info_with,
+    get_process_path, get_process_path_with, get_process_priority_with, get_process_session_id,
+    get_process_start_time_with, get_process_user_sid_with, get_system_memory_info,
+    is_being_debugged_with, is_wow64_process_with, NetworkIoReader, Priority, ProcessDiskInfo,
+    ProcessInfo, ProcessMemoryInfo, ProcessNetworkInfo, SystemMemoryInfo,
+};
+
+/// Per-process metrics gathered on each refresh
+#[derive(Debug, Clone, Default)]
+pub struct ProcessMetrics {
+    /// Working set and related counters
+    pub memory: ProcessMemoryInfo,
+    /// Cumulative disk I/O counters
+    pub disk: ProcessDiskInfo,
+    /// Priority class
+    pub priority: Priority,
+    /// Creation time as FILETIME (None if inaccessible)
+    pub start_time: Option<u64>,
+    /// Full executable path (None if inaccessible)
+    pub path: Option<String>,
+    /// Open handle count (None if limited queries are denied)
+    pub handle_count: Option<u32>,
+    /// Whether the process is 32-bit under WOW64 (None if inaccessible)
+    pub is_32bit: Option<bool>,
+    /// Whether a debugger is attached (None if inaccessible)
+    pub being_debugged: Option<bool>,
+    /// Whether efficiency mode (EcoQoS) is on (None if inaccessible)
+    pub efficiency_mode: Option<bool>,
+    /// Terminal Services session ID (None if exited)
+    pub session_id: Option<u32>,
+    /// Whether the process is owned by the user running this application
+    pub is_current_user: bool,
+}
+
+/// Supplies the process list and per-process metrics to `App::refresh`
+pub trait ProcessSource {
+    /// Lists running processes.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<ProcessInfo>)` - All running processes
+    /// * `Err` - If the list could not be taken
+    fn enumerate(&self) -> windows::core::Result<Vec<ProcessInfo>>;
+
+    /// Gets system memory usage and capacity (None if unavailable).
+    fn memory_info(&self) -> Option<SystemMemoryInfo>;
+
+    /// Gets cumulative network bytes keyed by PID.
+    fn network_io(&mut self) -> HashMap<u32, ProcessNetworkInfo>;
+
+    /// Gets the PIDs that own TCP connections, listeners or UDP endpoints.
+    fn connection_pids(&self) -> HashSet<u32>;
+
+    /// Gathers the metrics of one process.
+    ///
+    /// # Arguments
+    /// * `pid` - The process ID to query
+    fn metrics(&self, pid: u32) -> ProcessMetrics;
+
+    /// Gets a process's executable path alone (None if inaccessible).
+    ///
+    /// # Arguments
+    /// * `pid` - The process ID to query
+    fn path(&self, pid: u32) -> Option<String>;
+}
+
+/// Reads live data from Windows
+pub struct WinProcessSource {
+    /// SID of the user running this application (for ownership checks)
+    current_user_sid: Option<Vec<u8>>,
+    /// Per-process TCP byte sampler
+    network: NetworkIoReader,
+}
+
+impl WinProcessSource {
+    /// Creates a source for the running system.
+    ///
+    /// # Arguments
+    /// * `net_stats` - Turn TCP statistics collection on for connections
+    ///   that have it off (`--net-stats`), until the source is dropped
+    pub fn new(net_stats: bool) -> Self {
+        Self {
+            current_user_sid: get_current_user_sid(),
+            network: NetworkIoReader::new(net_stats),
+        }
+    }
+}
+
+impl ProcessSource for WinProcessSource {
+    fn enumerate(&self) -> windows::core::Result<Vec<ProcessInfo>> {
+        enumerate_processes()
+    }
+
+    fn memory_info(&self) -> Option<SystemMemoryInfo> {
+        get_system_memory_info().ok()
+    }
+
+    fn network_io(&mut self) -> HashMap<u32, ProcessNetworkInfo> {
+        self.network.read()
+    }
+
+    fn connection_pids(&self) -> HashSet<u32> {
+        get_connection_pids()
+    }
+
+    fn metrics(&self, pid: u32) -> ProcessMetrics {
+        // One handle serves every query; the session needs none
+        let session_id = get_process_session_id(pid);
+        let process = match ProcessHandle::open_for_query(pid) {
+            Ok(process) => process,
+            Err(_) => {
+                return ProcessMetrics {
+                    session_id,
+                    ..Default::default()
+                }
+            }
+        };
+
+        ProcessMetrics {
+            memory: get_process_memory_info_with(&process),
+            disk: get_process_disk_info_with(&process),
+            priority: get_process_priority_with(&process),
+            start_time: get_process_start_time_with(&process),
+            path: get_process_path_with(&process),
+            handle_count: get_process_handle_count_with(&process),
+            is_32bit: is_wow64_process_with(&process),
+            being_debugged: is_being_debugged_with(&process),
+            efficiency_mode: get_efficiency_mode_with(&process),
+            session_id,
+            is_current_user: match self.current_user_sid {
+                Some(ref sid) => get_process_user_sid_with(&process).as_ref() == Some(sid),
+                None => false,
+            },
+        }
+    }
+
+    fn path(&self, pid: u32) -> Option<String> {
+        get_process_path(pid)
+    }
+}
+
+/// Serves a fixed, synthetic process list
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub struct MockProcessSource {
+    /// Processes returned by `enumerate`, with their metrics
+    pub processes: Vec<(ProcessInfo, ProcessMetrics)>,
+    /// PIDs reported as owning sockets
+    pub connections: HashSet<u32>,
+}
+
+#[cfg(test)]
+impl MockProcessSource {
+    /// Adds a process with the given parent and working set.
+    pub fn with_process(mut self, pid: u32, parent_pid: u32, name: &str, working_set: u64) -> Self {
+        let info = ProcessInfo {
+            pid,
+            parent_pid,
+            thread_count: 1,
+            base_priority: 8,
+            name: name.to_string(),
+        };
+        let metrics = ProcessMetrics {
+            memory: ProcessMemoryInfo { working_set, accessible: true, ..Default::default() },
+            priority: Priority::Normal,
+            handle_count: Some(10),
+            session_id: Some(1),
+            is_current_user: true,
+            ..Default::default()
+        };
+        self.processes.push((info, metrics));
+        self
+    }
+}
+
+#[cfg(test)]
+impl ProcessSource for MockProcessSource {
+    fn enumerate(&self) -> windows::core::Result<Vec<ProcessInfo>> {
+        Ok(self.processes.iter().map(|(info, _)| info.clone()).collect())
+    }
+
+    fn memory_info(&self) -> Option<SystemMemoryInfo> {
+        Some(SystemMemoryInfo {
+            memory_load_percent: 50,
+            total_physical: 1024 * 1024 * 1024,
+            available_physical: 512 * 1024 * 1024,
+            ..Default::default()
+        })
+    }
+
+    fn network_io(&mut self) -> HashMap<u32, ProcessNetworkInfo> {
+        HashMap::new()
+    }
+
+    fn connection_pids(&self) -> HashSet<u32> {
+        self.connections.clone()
+    }
+
+    fn metrics(&self, pid: u32) -> ProcessMetrics {
+        self.processes
+            .iter()
+            .find(|(info, _)| info.pid == pid)
+            .map(|(_, metrics)| metrics.clone())
+            .unwrap_or_default()
+    }
+
+    fn path(&self, pid: u32) -> Option<String> {
+        self.metrics(pid).path
+    }
+}
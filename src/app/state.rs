@@ -1,17 +1,24 @@
 //! Application state and core logic
 
-use std::collections::HashMap;
-use std::time::Instant;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
 
-use crate::constants::DEFAULT_REFRESH_MS;
+use crate::constants::{
+    DEFAULT_KILL_EXIT_CODE, DEFAULT_NEW_PROCESS_SECS, DEFAULT_REFRESH_MS, FILTER_HISTORY_MAX,
+    USAGE_HISTORY_SAMPLES,
+};
 use crate::system::cpu::CpuTracker;
 use crate::system::{
-    calculate_uptime_seconds, enumerate_processes, get_process_disk_info,
-    get_process_handle_count, get_process_memory_info, get_process_path,
-    get_process_priority, get_process_start_time, ProcessDetails,
+    calculate_uptime_seconds, get_process_command_line, path_to_filename, ByteUnits, Priority, ProcessDetails, SignatureInfo, SystemInfo,
 };
 
-use super::{ProcessEntry, SortColumn, ViewMode};
+use super::csv_column::DEFAULT_CSV_COLUMNS;
+use super::export::ExportTarget;
+use super::filter_scope::matches_filter;
+use super::kill_hold::KillHold;
+use super::module_view::ModuleView;
+use super::source::{ProcessSource, WinProcessSource};
+use super::{CsvColumn, FilterScope, IdleMode, ProcessEntry, SortColumn, ViewMode};
 
 /// Previous disk I/O snapshot for rate calculation
 #[derive(Debug, Clone, Default)]
@@ -20,6 +27,39 @@ struct DiskIoSnapshot {
     write_bytes: u64,
 }
 
+/// Previous network I/O snapshot for rate calculation
+#[derive(Debug, Clone, Default)]
+struct NetworkIoSnapshot {
+    recv_bytes: u64,
+    send_bytes: u64,
+}
+
+/// Sums of per-process metrics, for checking them against system totals
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MetricTotals {
+    /// Sum of per-process CPU% (should roughly track `system_cpu`)
+    pub cpu_percent: f64,
+    /// Sum of disk read rates in bytes per second
+    pub disk_read_rate: f64,
+    /// Sum of disk write rates in bytes per second
+    pub disk_write_rate: f64,
+    /// Sum of working sets in bytes
+    pub memory_bytes: u64,
+}
+
+impl MetricTotals {
+    /// Sums the metrics over a list of processes.
+    fn sum(processes: &[ProcessEntry]) -> Self {
+        processes.iter().fold(Self::default(), |mut totals, p| {
+            totals.cpu_percent += p.cpu_percent;
+            totals.disk_read_rate += p.disk_read_rate;
+            totals.disk_write_rate += p.disk_write_rate;
+            totals.memory_bytes += p.memory_bytes;
+            totals
+        })
+    }
+}
+
 /// Application state
 pub struct App {
     /// All tracked processes
@@ -30,28 +70,88 @@ pub struct App {
     pub cpu_tracker: CpuTracker,
     /// Currently selected process index
     pub selected_index: usize,
+    /// PID under the cursor, used to keep the selection on the same
+    /// process when the list is re-sorted or refreshed
+    selected_pid: Option<u32>,
+    /// PID to select once the first refresh has loaded the list (`--select`)
+    pub(super) pending_select_pid: Option<u32>,
     /// Scroll offset for the process list
     pub scroll_offset: usize,
     /// System CPU usage percentage
     pub system_cpu: f64,
+    /// Sum of thread counts across all processes
+    pub total_threads: u64,
+    /// Sum of handle counts across all processes (inaccessible ones count as 0)
+    pub total_handles: u64,
+    /// Recent system CPU usage samples, oldest first
+    pub cpu_history: VecDeque<f64>,
+    /// Recent system memory load samples (percent), oldest first
+    pub memory_history: VecDeque<f64>,
     /// Error message to display (if any)
     pub error_message: Option<String>,
     /// Current sort column
     pub sort_column: SortColumn,
     /// Sort in ascending order (false = descending)
     pub sort_ascending: bool,
+    /// Last-used direction of each column sorted by this session, restored
+    /// when cycling back to it
+    pub(super) sort_directions: HashMap<SortColumn, bool>,
     /// Search filter string
     pub filter: String,
+    /// Previously applied filters, oldest first
+    pub filter_history: Vec<String>,
+    /// Position in `filter_history` while recalling with Up/Down
+    filter_history_index: Option<usize>,
+    /// Filter text typed before history recall started
+    filter_history_draft: String,
+    /// Which process fields the filter searches
+    pub filter_scope: FilterScope,
+    /// Find text: matching rows are highlighted but nothing is hidden
+    pub find: String,
+    /// Command lines fetched for filtering, by PID, with the start time
+    /// they were read for (so a reused PID is not matched stale)
+    command_lines: HashMap<u32, (Option<u64>, Option<String>)>,
+    /// Code signatures looked up for the detail view, by executable path
+    pub(super) signatures: HashMap<String, Option<SignatureInfo>>,
     /// Current view/input mode (replaces multiple boolean flags)
     pub view_mode: ViewMode,
     /// PID of process pending kill confirmation
     pub pending_kill_pid: Option<u32>,
     /// Name of process pending kill confirmation
     pub pending_kill_name: Option<String>,
+    /// Multi-selected processes (PID, name) pending kill confirmation
+    pub pending_kill_batch: Vec<(u32, String)>,
+    /// Executable path to hold after the pending kill ("kill and hold")
+    pub pending_kill_hold: Option<String>,
+    /// Running "kill and hold" that kills respawns of an executable
+    pub kill_hold: Option<KillHold>,
+    /// Processes (PID, name, current priority) waiting for confirmation
+    /// before being raised to Realtime
+    pub pending_realtime: Vec<(u32, String, Priority)>,
+    /// Skip the Y/N confirmation when killing a process
+    pub no_confirm_kill: bool,
+    /// Ask for Y/N confirmation before quitting
+    pub confirm_quit: bool,
+    /// Exit code given to killed processes
+    pub kill_exit_code: u32,
+    /// EMA weight for smoothed per-process CPU% (None = raw values)
+    pub cpu_smoothing: Option<f64>,
+    /// Highlight processes younger than this many seconds (0 = off)
+    pub new_process_secs: u64,
     /// Previous disk I/O values for rate calculation
     prev_disk_io: HashMap<u32, DiskIoSnapshot>,
+    /// Previous network I/O values for rate calculation
+    prev_net_io: HashMap<u32, NetworkIoSnapshot>,
+    /// Previous working set sizes for memory growth calculation
+    prev_memory: HashMap<u32, u64>,
     /// Time of last refresh for rate calculation
     last_refresh_time: Instant,
+    /// When monitoring started (app creation)
+    monitoring_started: Instant,
+    /// Number of successful refreshes (samples) since monitoring started
+    pub refresh_count: u64,
+    /// Whether the last refresh failed to take a process snapshot
+    pub snapshot_failed: bool,
     /// Refresh interval in milliseconds
     pub refresh_interval_ms: u64,
     /// PID of process in detail view
@@ -62,20 +162,89 @@ pub struct App {
     pub detail_view_data: Option<ProcessDetails>,
     /// Scroll offset for detail view
     pub detail_scroll_offset: usize,
+    /// Detail view scroll offsets of previously viewed processes, by PID
+    detail_scroll_positions: HashMap<u32, usize>,
+    /// Number of detail view refreshes since it was opened
+    pub detail_refresh_ticks: u32,
+    /// The viewed process has exited; the detail view shows its last data
+    pub detail_view_exited: bool,
+    /// Sort order and search of the detail view's module list
+    pub module_view: ModuleView,
     /// Whether we're in tree view mode
     pub tree_view_mode: bool,
+    /// Use the compact column layout (fewer columns, wider names)
+    pub compact_rows: bool,
+    /// Give every other non-selected row a slightly different background
+    pub row_stripes: bool,
+    /// Give the selected process's path its own footer line
+    pub path_line: bool,
+    /// Maximum display width of process names in the list (`--name-width`)
+    pub name_width: Option<usize>,
+    /// Maximum display width of the footer path (`--path-width`)
+    pub path_width: Option<usize>,
+    /// Show the debug line with metric totals and timings (`--debug`/F12)
+    pub debug_line: bool,
+    /// How long the last refresh took (measured only with `debug_line`)
+    pub refresh_duration: Duration,
+    /// How long the last frame took to draw (measured only with `debug_line`)
+    pub render_duration: Duration,
+    /// Columns written by the CSV export, in order
+    pub csv_columns: Vec<CsvColumn>,
+    /// Directory and filename template for exports
+    pub export_target: ExportTarget,
+    /// PIDs pinned to the top of the list
+    pub pinned: HashSet<u32>,
+    /// PIDs marked for batch actions (kill, suspend, priority)
+    pub selected_pids: HashSet<u32>,
+    /// PIDs this app suspended (Windows has no suspended flag to read back)
+    pub(super) suspended_pids: HashSet<u32>,
+    /// Whether to show only processes owned by the current user
+    pub current_user_only: bool,
+    /// Whether to show only processes that own network sockets
+    pub network_only: bool,
+    /// PIDs owning sockets, from one TCP/UDP table snapshot per refresh
+    /// (only taken while `network_only` is on)
+    pub connection_pids: HashSet<u32>,
+    /// Whether to show the Idle/System and other kernel pseudo-processes
+    pub show_system: bool,
+    /// How processes doing no work are shown
+    pub idle_mode: IdleMode,
+    /// Binary (KiB) or decimal (KB) units for sizes and rates
+    pub units: ByteUnits,
+    /// Whether the disk columns show lifetime bytes instead of rates
+    pub disk_totals: bool,
+    /// Keep rows in place on refresh instead of re-sorting them
+    pub freeze_order: bool,
+    /// Displayed row order while frozen, by PID; rows not in it (new
+    /// processes) are added at the end
+    pub(super) frozen_order: Vec<u32>,
+    /// Where refresh reads processes and their metrics from
+    pub(super) source: Box<dyn ProcessSource>,
     /// PID of process being edited for affinity
     pub affinity_pid: Option<u32>,
     /// Name of process being edited for affinity
     pub affinity_name: Option<String>,
     /// Current core selection bitmask for affinity dialog
     pub affinity_mask: usize,
-    /// Total number of system cores
+    /// Number of cores in the process's processor group
     pub affinity_total_cores: u32,
+    /// Processor group the edited mask applies to
+    pub affinity_group: u16,
+    /// Number of active processor groups on the system
+    pub affinity_group_count: u16,
     /// Currently selected core index in affinity dialog
     pub affinity_selected_core: usize,
+    /// Multi-selected processes (PID, name) the affinity applies to;
+    /// empty when editing a single process
+    pub affinity_batch: Vec<(u32, String)>,
     /// Scroll offset for help overlay
     pub help_scroll_offset: usize,
+    /// Search text narrowing the help overlay's shortcut list
+    pub help_filter: String,
+    /// Machine information shown in the system info panel, gathered on open
+    pub sys_info: Option<SystemInfo>,
+    /// Highlighted row in the sort column picker (index into `SortColumn::ALL`)
+    pub sort_picker_index: usize,
 }
 
 impl App {
@@ -84,34 +253,104 @@ impl App {
     /// # Returns
     /// A new `App` ready for use with default configuration.
     pub fn new() -> Self {
+        Self::with_source(Box::new(WinProcessSource::new(false)))
+    }
+
+    /// Creates a new App instance with default settings that reads
+    /// processes from the given source.
+    ///
+    /// # Arguments
+    /// * `source` - Supplies the process list and per-process metrics
+    pub fn with_source(source: Box<dyn ProcessSource>) -> Self {
         Self {
             processes: Vec::new(),
             filtered_processes: Vec::new(),
             cpu_tracker: CpuTracker::new(),
             selected_index: 0,
+            selected_pid: None,
+            pending_select_pid: None,
             scroll_offset: 0,
             system_cpu: 0.0,
+            total_threads: 0,
+            total_handles: 0,
+            cpu_history: VecDeque::with_capacity(USAGE_HISTORY_SAMPLES),
+            memory_history: VecDeque::with_capacity(USAGE_HISTORY_SAMPLES),
             error_message: None,
             sort_column: SortColumn::Cpu,
-            sort_ascending: false,
+            sort_ascending: SortColumn::Cpu.default_ascending(),
+            sort_directions: HashMap::new(),
             filter: String::new(),
+            filter_history: Vec::new(),
+            filter_history_index: None,
+            filter_history_draft: String::new(),
+            filter_scope: FilterScope::default(),
+            find: String::new(),
+            command_lines: HashMap::new(),
+            signatures: HashMap::new(),
             view_mode: ViewMode::default(),
             pending_kill_pid: None,
             pending_kill_name: None,
+            pending_kill_batch: Vec::new(),
+            pending_kill_hold: None,
+            kill_hold: None,
+            pending_realtime: Vec::new(),
+            no_confirm_kill: false,
+            confirm_quit: false,
+            kill_exit_code: DEFAULT_KILL_EXIT_CODE,
+            cpu_smoothing: None,
+            new_process_secs: DEFAULT_NEW_PROCESS_SECS,
             prev_disk_io: HashMap::new(),
+            prev_net_io: HashMap::new(),
+            prev_memory: HashMap::new(),
             last_refresh_time: Instant::now(),
+            monitoring_started: Instant::now(),
+            refresh_count: 0,
+            snapshot_failed: false,
             refresh_interval_ms: DEFAULT_REFRESH_MS,
             detail_view_pid: None,
             detail_view_name: None,
             detail_view_data: None,
             detail_scroll_offset: 0,
+            detail_scroll_positions: HashMap::new(),
+            detail_refresh_ticks: 0,
+            detail_view_exited: false,
+            module_view: ModuleView::default(),
             tree_view_mode: false,
+            compact_rows: false,
+            row_stripes: false,
+            path_line: false,
+            name_width: None,
+            path_width: None,
+            debug_line: false,
+            refresh_duration: Duration::ZERO,
+            render_duration: Duration::ZERO,
+            csv_columns: DEFAULT_CSV_COLUMNS.to_vec(),
+            export_target: ExportTarget::default(),
+            pinned: HashSet::new(),
+            selected_pids: HashSet::new(),
+            suspended_pids: HashSet::new(),
+            current_user_only: false,
+            network_only: false,
+            connection_pids: HashSet::new(),
+            show_system: true,
+            idle_mode: IdleMode::default(),
+            units: ByteUnits::default(),
+            disk_totals: false,
+            freeze_order: false,
+            frozen_order: Vec::new(),
+            source,
             affinity_pid: None,
             affinity_name: None,
             affinity_mask: 0,
             affinity_total_cores: 0,
+            affinity_group: 0,
+            affinity_group_count: 1,
             affinity_selected_core: 0,
+            affinity_batch: Vec::new(),
             help_scroll_offset: 0,
+            help_filter: String::new(),
+            sys_info: None,
+            sort_picker_index: 0,
         }
     }
 
@@ -123,13 +362,34 @@ impl App {
     /// # Returns
     /// A new `App` configured according to the provided arguments.
     pub fn with_args(args: &super::cli::Args) -> Self {
-        let mut app = Self::new();
+        let mut app = Self::with_source(Box::new(WinProcessSource::new(args.net_stats)));
         
         // Apply CLI configuration
         app.refresh_interval_ms = args.refresh;
         app.sort_column = args.sort;
-        app.sort_ascending = args.ascending;
+        app.filter_scope = args.filter_scope;
+        app.idle_mode = args.idle_mode;
+        app.units = args.units;
+        app.sort_ascending = args.ascending || args.sort.default_ascending();
         app.tree_view_mode = args.tree;
+        app.compact_rows = args.compact;
+        app.row_stripes = args.stripes;
+        app.path_line = args.path_line;
+        app.name_width = args.name_width;
+        app.path_width = args.path_width;
+        app.debug_line = args.debug;
+        if let Some(ref columns) = args.csv_columns {
+            app.csv_columns = columns.clone();
+        }
+        app.export_target.dir = args.export_dir.clone();
+        app.export_target.name_template = args.export_name.clone();
+        app.export_target.csv_encoding = args.csv_encoding.unwrap_or_default();
+        app.no_confirm_kill = args.no_confirm;
+        app.confirm_quit = args.confirm_quit;
+        app.kill_exit_code = args.kill_code;
+        app.cpu_smoothing = args.smooth;
+        app.new_process_secs = args.highlight_new;
+        app.pending_select_pid = args.select_pid;
         
         if let Some(ref filter) = args.filter {
             app.filter = filter.clone();
@@ -186,27 +446,49 @@ impl App {
         let now = Instant::now();
         let time_delta = now.duration_since(self.last_refresh_time).as_secs_f64();
         self.last_refresh_time = now;
+        self.anchor_selection();
 
         self.system_cpu = self.cpu_tracker.get_system_cpu_usage();
+        push_sample(&mut self.cpu_history, self.system_cpu);
+        let memory_info = self.source.memory_info();
+        if let Some(ref info) = memory_info {
+            push_sample(&mut self.memory_history, info.memory_load_percent as f64);
+        }
+        let total_physical = memory_info.map_or(0, |info| info.total_physical);
 
-        let processes = match enumerate_processes() {
+        let processes = match self.source.enumerate() {
             Ok(procs) => procs,
             Err(e) => {
-                self.error_message = Some(format!("Failed to enumerate processes: {}", e));
+                // Keep showing the previous list; the next refresh may succeed
+                self.snapshot_failed = true;
+                self.error_message = Some(format!(
+                    "Process snapshot failed, showing last list: {}",
+                    e
+                ));
                 return;
             }
         };
+        self.refresh_count += 1;
+        self.snapshot_failed = false;
 
         let mut new_disk_io: HashMap<u32, DiskIoSnapshot> = HashMap::new();
+        let mut new_net_io: HashMap<u32, NetworkIoSnapshot> = HashMap::new();
+        let mut new_memory: HashMap<u32, u64> = HashMap::new();
+        let net_io = self.source.network_io();
 
         self.processes = processes
             .into_iter()
-            .map(|info| {
+            .map(|mut info| {
                 let pid = info.pid;
-                let cpu_percent = self.cpu_tracker.get_process_cpu_usage(pid);
-                let mem_info = get_process_memory_info(pid);
-                let disk_info = get_process_disk_info(pid);
-                let priority = get_process_priority(pid);
+                let cpu_percent = match self.cpu_smoothing {
+                    Some(alpha) => self.cpu_tracker.get_process_cpu_usage_smoothed(pid, alpha),
+                    None => self.cpu_tracker.get_process_cpu_usage(pid),
+                };
+                let cpu_stats = self.cpu_tracker.get_process_cpu_stats(pid);
+                let cpu_time_seconds = self.cpu_tracker.get_process_cpu_time_seconds(pid);
+                let metrics = self.source.metrics(pid);
+                let mem_info = metrics.memory;
+                let disk_info = metrics.disk;
                 let thread_count = info.thread_count;
 
                 let (disk_read_rate, disk_write_rate) = if time_delta > 0.0 {
@@ -232,49 +514,196 @@ impl App {
                     },
                 );
 
-                let start_time = get_process_start_time(pid);
+                let net_info = net_io.get(&pid).copied().unwrap_or_default();
+                let (net_recv_rate, net_send_rate) = if time_delta > 0.0 {
+                    if let Some(prev) = self.prev_net_io.get(&pid) {
+                        let recv_delta = net_info.recv_bytes.saturating_sub(prev.recv_bytes);
+                        let send_delta = net_info.send_bytes.saturating_sub(prev.send_bytes);
+                        (
+                            recv_delta as f64 / time_delta,
+                            send_delta as f64 / time_delta,
+                        )
+                    } else {
+                        (0.0, 0.0)
+                    }
+                } else {
+                    (0.0, 0.0)
+                };
+
+                new_net_io.insert(
+                    pid,
+                    NetworkIoSnapshot {
+                        recv_bytes: net_info.recv_bytes,
+                        send_bytes: net_info.send_bytes,
+                    },
+                );
+
+                // Signed growth rate, so shrinking working sets sort last
+                let memory_growth_rate = match self.prev_memory.get(&pid) {
+                    Some(&prev) if time_delta > 0.0 && mem_info.accessible => {
+                        (mem_info.working_set as f64 - prev as f64) / time_delta
+                    }
+                    _ => 0.0,
+                };
+
+                if mem_info.accessible {
+                    new_memory.insert(pid, mem_info.working_set);
+                }
+
+                let start_time = metrics.start_time;
                 let uptime_seconds = start_time
                     .map(|st| calculate_uptime_seconds(st))
                     .unwrap_or(0);
 
-                let path = get_process_path(pid);
-                let handle_count = get_process_handle_count(pid);
+                let path = metrics.path;
+
+                // The snapshot's szExeFile can be truncated or differ from the
+                // on-disk image; prefer the name from the full path when known
+                if let Some(filename) = path.as_deref().map(path_to_filename) {
+                    if !filename.is_empty() {
+                        info.name = filename.to_string();
+                    }
+                }
+                let query_access_denied = metrics.handle_count.is_none();
+                let handle_count = metrics.handle_count.unwrap_or(0);
 
                 ProcessEntry {
                     info,
                     cpu_percent,
+                    cpu_avg_percent: cpu_stats.average(),
+                    cpu_peak_percent: cpu_stats.peak,
+                    cpu_time_seconds,
                     memory_bytes: mem_info.working_set,
+                    mem_percent: memory_percent(mem_info.working_set, total_physical),
+                    memory_growth_rate,
+                    memory_access_denied: !mem_info.accessible,
+                    query_access_denied,
                     disk_read: disk_info.read_bytes,
                     disk_write: disk_info.write_bytes,
                     disk_read_rate,
                     disk_write_rate,
-                    priority,
+                    net_recv_rate,
+                    net_send_rate,
+                    priority: metrics.priority,
                     thread_count,
                     start_time,
                     uptime_seconds,
                     path,
                     handle_count,
+                    child_count: 0,
+                    is_32bit: metrics.is_32bit,
+                    being_debugged: metrics.being_debugged,
+                    efficiency_mode: metrics.efficiency_mode,
+                    session_id: metrics.session_id,
+                    is_current_user: metrics.is_current_user,
                     tree_depth: 0,
                 }
             })
             .collect();
 
         self.prev_disk_io = new_disk_io;
+        self.prev_net_io = new_net_io;
+        self.prev_memory = new_memory;
+        if self.network_only {
+            self.connection_pids = self.source.connection_pids();
+        }
+
+        count_children(&mut self.processes);
+
+        self.total_threads = self.processes.iter().map(|p| p.thread_count as u64).sum();
+        self.total_handles = self.processes.iter().map(|p| p.handle_count as u64).sum();
         
         // Apply sorting/tree structure and filtering
-        if self.tree_view_mode {
-            // Tree view handles its own structure and calls apply_filter
-            self.build_process_tree();
-        } else {
-            self.sort_processes();
-            self.apply_filter();
-        }
+        self.resort();
 
         let active_pids: Vec<u32> = self.processes.iter().map(|p| p.info.pid).collect();
         self.cpu_tracker.cleanup_stale_processes(&active_pids);
+        // Windows reuses PIDs, so forget suspends of processes that exited
+        self.suspended_pids.retain(|pid| active_pids.contains(pid));
+        self.pinned.retain(|pid| active_pids.contains(pid));
+        self.selected_pids.retain(|pid| active_pids.contains(pid));
+        self.detail_scroll_positions.retain(|pid, _| active_pids.contains(pid));
+        self.command_lines.retain(|pid, _| active_pids.contains(pid));
+        self.frozen_order.retain(|pid| active_pids.contains(pid));
 
-        if self.selected_index >= self.filtered_processes.len() {
-            self.selected_index = self.filtered_processes.len().saturating_sub(1);
+        self.restore_selection();
+        self.resolve_pending_select();
+    }
+
+    /// Sums CPU%, disk rates and memory over all processes, filtered or not.
+    ///
+    /// Per-process CPU% is measured against the same system interval as
+    /// `system_cpu`, so the two should roughly agree (unless `--smooth`
+    /// is averaging the per-process values).
+    pub fn metric_totals(&self) -> MetricTotals {
+        MetricTotals::sum(&self.processes)
+    }
+
+    /// Sums the same metrics over only the rows the filter lets through,
+    /// answering "how much is everything named chrome using?".
+    pub fn filtered_totals(&self) -> MetricTotals {
+        MetricTotals::sum(&self.filtered_processes)
+    }
+
+    /// Remembers the detail view scroll offset for a process.
+    pub(super) fn save_detail_scroll(&mut self, pid: u32, offset: usize) {
+        self.detail_scroll_positions.insert(pid, offset);
+    }
+
+    /// Returns the saved detail view scroll offset for a process (0 if none).
+    pub(super) fn saved_detail_scroll(&self, pid: u32) -> usize {
+        self.detail_scroll_positions.get(&pid).copied().unwrap_or(0)
+    }
+
+    /// Returns the process under the cursor.
+    ///
+    /// Every action on "the selected process" goes through this, so an
+    /// empty or shrunken list is handled in one place rather than by
+    /// indexing `filtered_processes` directly.
+    ///
+    /// # Returns
+    /// `None` when the list is empty (e.g. the filter matches nothing).
+    pub fn selected_process(&self) -> Option<&ProcessEntry> {
+        self.filtered_processes.get(self.selected_index)
+    }
+
+    /// Remembers the PID under the cursor before the list is rebuilt.
+    fn anchor_selection(&mut self) {
+        self.selected_pid = self.selected_process().map(|p| p.info.pid);
+    }
+
+    /// Moves the cursor back to the anchored PID after the list is rebuilt.
+    ///
+    /// If that process exited or was filtered out, the index is kept and
+    /// clamped to the list bounds instead.
+    fn restore_selection(&mut self) {
+        let position = self
+            .selected_pid
+            .and_then(|pid| self.filtered_processes.iter().position(|p| p.info.pid == pid));
+
+        match position {
+            Some(index) => self.selected_index = index,
+            None if self.selected_index >= self.filtered_processes.len() => {
+                self.selected_index = self.filtered_processes.len().saturating_sub(1);
+            }
+            None => {}
+        }
+    }
+
+    /// Selects the PID requested with `--select`, once, after the list has
+    /// been loaded. Falls back to the top of the list if it is not shown.
+    fn resolve_pending_select(&mut self) {
+        let pid = match self.pending_select_pid.take() {
+            Some(pid) => pid,
+            None => return,
+        };
+
+        match self.filtered_processes.iter().position(|p| p.info.pid == pid) {
+            Some(index) => self.selected_index = index,
+            None => {
+                self.selected_index = 0;
+                self.error_message = Some(format!("PID {} not found; starting at the top", pid));
+            }
         }
     }
 
@@ -282,73 +711,491 @@ impl App {
     fn sort_processes(&mut self) {
         let ascending = self.sort_ascending;
         let sort_column = self.sort_column;
+        let disk_totals = self.disk_totals;
 
-        self.processes.sort_by(|a, b| {
-            let cmp = match sort_column {
-                SortColumn::Cpu => b
-                    .cpu_percent
-                    .partial_cmp(&a.cpu_percent)
-                    .unwrap_or(std::cmp::Ordering::Equal),
-                SortColumn::Memory => b.memory_bytes.cmp(&a.memory_bytes),
-                SortColumn::Name => a.info.name.to_lowercase().cmp(&b.info.name.to_lowercase()),
-                SortColumn::Pid => a.info.pid.cmp(&b.info.pid),
-                SortColumn::Priority => b.priority.cmp(&a.priority),
-                SortColumn::Threads => b.thread_count.cmp(&a.thread_count),
-                SortColumn::Handles => b.handle_count.cmp(&a.handle_count),
-                SortColumn::Uptime => b.uptime_seconds.cmp(&a.uptime_seconds),
-                SortColumn::DiskReadRate => b
-                    .disk_read_rate
-                    .partial_cmp(&a.disk_read_rate)
-                    .unwrap_or(std::cmp::Ordering::Equal),
-                SortColumn::DiskWriteRate => b
-                    .disk_write_rate
-                    .partial_cmp(&a.disk_write_rate)
-                    .unwrap_or(std::cmp::Ordering::Equal),
-            };
-            if ascending {
-                cmp.reverse()
-            } else {
-                cmp
-            }
-        });
+        self.processes.sort_by(|a, b| sort_column.compare(a, b, ascending, disk_totals));
+    }
+
+    /// Re-applies the current sort, rebuilding the tree in tree view so
+    /// the hierarchy survives and siblings follow the new order.
+    pub(super) fn resort(&mut self) {
+        if self.tree_view_mode {
+            self.build_process_tree();
+        } else {
+            self.sort_processes();
+            self.apply_filter();
+        }
     }
 
     /// Apply the current filter to the process list.
     ///
-    /// Filters processes by name (case-insensitive) and updates
-    /// the `filtered_processes` vector. Adjusts selection if needed.
+    /// Filters processes by name (case-insensitive; also path and command
+    /// line depending on `filter_scope`) and, when enabled, by owner,
+    /// then updates the `filtered_processes` vector. Pinned
+    /// processes are moved to the top (except in tree view, where the
+    /// hierarchy is kept intact). Adjusts selection if needed.
     pub fn apply_filter(&mut self) {
-        self.filtered_processes = if self.filter.is_empty() {
-            self.processes.clone()
-        } else {
-            let filter_lower = self.filter.to_lowercase();
-            self.processes
-                .iter()
-                .filter(|p| p.info.name.to_lowercase().contains(&filter_lower))
-                .cloned()
-                .collect()
-        };
+        let filter_lower = self.filter.to_lowercase();
+        let current_user_only = self.current_user_only;
+        let network_only = self.network_only;
+        let show_system = self.show_system;
+        let hide_idle = self.idle_mode == IdleMode::Hide && self.idle_known();
+        let scope = self.filter_scope;
+
+        if scope.includes_command_line() && !filter_lower.is_empty() {
+            self.cache_command_lines();
+        }
+
+        let command_lines = &self.command_lines;
+        self.filtered_processes = self
+            .processes
+            .iter()
+            .filter(|p| {
+                if filter_lower.is_empty() {
+                    return true;
+                }
+                let mut fields = vec![p.info.name.as_str()];
+                if scope.includes_path() {
+                    fields.extend(p.path.as_deref());
+                }
+                if scope.includes_command_line() {
+                    fields.extend(
+                        command_lines
+                            .get(&p.info.pid)
+                            .and_then(|(_, cmd)| cmd.as_deref()),
+                    );
+                }
+                matches_filter(&filter_lower, p.info.pid, p.priority, &fields)
+            })
+            .filter(|p| !current_user_only || p.is_current_user)
+            .filter(|p| !network_only || self.connection_pids.contains(&p.info.pid))
+            .filter(|p| show_system || !p.info.is_system_pseudo_process())
+            .filter(|p| !hide_idle || !p.is_idle() || self.pinned.contains(&p.info.pid))
+            .cloned()
+            .collect();
+
+        if !self.pinned.is_empty() && !self.tree_view_mode {
+            // Stable partition keeps the current sort within each group
+            let (mut pinned, rest): (Vec<_>, Vec<_>) = self
+                .filtered_processes
+                .drain(..)
+                .partition(|p| self.pinned.contains(&p.info.pid));
+            pinned.extend(rest);
+            self.filtered_processes = pinned;
+        }
+
+        if self.freeze_order {
+            self.apply_frozen_order();
+        }
 
         if self.selected_index >= self.filtered_processes.len() {
             self.selected_index = self.filtered_processes.len().saturating_sub(1);
         }
     }
 
+    /// Puts the filtered rows back in their frozen order.
+    ///
+    /// Rows seen for the first time are remembered so they also stay put
+    /// from now on: at the end of the list, or in tree view right after the
+    /// row they follow in the sorted tree, which keeps them under their
+    /// parent. Pinned rows still come first in the flat list.
+    fn apply_frozen_order(&mut self) {
+        let sorted: Vec<u32> = self.filtered_processes.iter().map(|p| p.info.pid).collect();
+        let mut known: HashSet<u32> = self.frozen_order.iter().copied().collect();
+        for (i, &pid) in sorted.iter().enumerate() {
+            if !known.insert(pid) {
+                continue;
+            }
+            let at = if self.tree_view_mode {
+                // The previous row is already placed, being older or new
+                // and handled just before this one
+                i.checked_sub(1)
+                    .and_then(|prev| self.frozen_order.iter().position(|&p| p == sorted[prev]))
+                    .map_or(0, |pos| pos + 1)
+            } else {
+                self.frozen_order.len()
+            };
+            self.frozen_order.insert(at, pid);
+        }
+
+        let ranks: HashMap<u32, usize> = self
+            .frozen_order
+            .iter()
+            .enumerate()
+            .map(|(rank, pid)| (*pid, rank))
+            .collect();
+        let pin_first = !self.tree_view_mode;
+        self.filtered_processes.sort_by_key(|p| {
+            (
+                !(pin_first && self.pinned.contains(&p.info.pid)),
+                ranks.get(&p.info.pid).copied().unwrap_or(usize::MAX),
+            )
+        });
+    }
+
+    /// Freezes or unfreezes the row order. While frozen, refresh still
+    /// updates every metric, but rows keep their place so the selection
+    /// does not move; changing the sort re-sorts once and freezes again.
+    pub fn toggle_freeze_order(&mut self) {
+        self.anchor_selection();
+        self.freeze_order = !self.freeze_order;
+        self.frozen_order.clear();
+        self.resort();
+        self.restore_selection();
+        self.error_message = Some(if self.freeze_order {
+            "Row order frozen; metrics keep updating".to_string()
+        } else {
+            "Row order unfrozen".to_string()
+        });
+    }
+
+    /// Reads the command line of every process not yet cached (or whose
+    /// PID now belongs to a different process).
+    fn cache_command_lines(&mut self) {
+        for p in &self.processes {
+            let pid = p.info.pid;
+            let fresh = matches!(self.command_lines.get(&pid), Some((start, _)) if *start == p.start_time);
+            if !fresh {
+                self.command_lines
+                    .insert(pid, (p.start_time, get_process_command_line(pid)));
+            }
+        }
+    }
+
+    /// Cycles which fields the filter searches and re-applies it.
+    pub fn cycle_filter_scope(&mut self) {
+        self.filter_scope = self.filter_scope.next();
+        self.apply_filter();
+    }
+
+    /// Records the current filter in the history.
+    ///
+    /// Empty filters and repeats of the most recent entry are skipped,
+    /// and the oldest entries are dropped beyond `FILTER_HISTORY_MAX`.
+    pub fn push_filter_history(&mut self) {
+        self.filter_history_index = None;
+
+        if self.filter.is_empty() || self.filter_history.last() == Some(&self.filter) {
+            return;
+        }
+
+        self.filter_history.push(self.filter.clone());
+        if self.filter_history.len() > FILTER_HISTORY_MAX {
+            let excess = self.filter_history.len() - FILTER_HISTORY_MAX;
+            self.filter_history.drain(..excess);
+        }
+    }
+
+    /// Recalls the previous (older) filter from the history
+    pub fn filter_history_prev(&mut self) {
+        if self.filter_history.is_empty() {
+            return;
+        }
+
+        let index = match self.filter_history_index {
+            None => {
+                self.filter_history_draft = self.filter.clone();
+                self.filter_history.len() - 1
+            }
+            Some(i) => i.saturating_sub(1),
+        };
+
+        self.filter_history_index = Some(index);
+        self.filter = self.filter_history[index].clone();
+        self.apply_filter();
+    }
+
+    /// Recalls the next (newer) filter, returning to the typed text at the end
+    pub fn filter_history_next(&mut self) {
+        let index = match self.filter_history_index {
+            Some(i) => i,
+            None => return,
+        };
+
+        if index + 1 < self.filter_history.len() {
+            self.filter_history_index = Some(index + 1);
+            self.filter = self.filter_history[index + 1].clone();
+        } else {
+            self.filter_history_index = None;
+            self.filter = std::mem::take(&mut self.filter_history_draft);
+        }
+        self.apply_filter();
+    }
+
+    /// Stops history recall so further edits apply to the current text
+    pub fn reset_filter_history_position(&mut self) {
+        self.filter_history_index = None;
+    }
+
     /// Cycles to the next sort column.
     ///
-    /// Order: CPU → Memory → Name → PID → Priority → Threads → Handles → Uptime → Read/s → Write/s
+    /// Order: CPU → CPU Time → Memory → Mem% → Mem/s → Name → PID → Session → Priority → Base → Threads →
+    /// Handles → Children → Uptime → Read/s → Write/s → Net/s → Power
+    ///
+    /// Each column keeps the direction it was last sorted in, starting
+    /// from its default (names/IDs ascending, metrics descending).
     pub fn cycle_sort(&mut self) {
-        self.sort_column = self.sort_column.next();
-        self.sort_processes();
+        self.set_sort_column(self.sort_column.next());
+    }
+
+    /// Sorts by the given column in the direction it was last sorted in.
+    ///
+    /// # Arguments
+    /// * `column` - The column to sort by
+    pub fn set_sort_column(&mut self, column: SortColumn) {
+        self.anchor_selection();
+        self.sort_directions.insert(self.sort_column, self.sort_ascending);
+        self.sort_column = column;
+        self.sort_ascending = self.sort_direction(column);
+        // An explicit sort change re-sorts even while the order is frozen
+        self.frozen_order.clear();
+        self.resort();
+        self.restore_selection();
+    }
+
+    /// Gets the direction a column sorts in: the current direction for the
+    /// active column, otherwise the one it was last used with or its default.
+    ///
+    /// # Returns
+    /// `true` if the column sorts ascending.
+    pub fn sort_direction(&self, column: SortColumn) -> bool {
+        if column == self.sort_column {
+            return self.sort_ascending;
+        }
+        self.sort_directions
+            .get(&column)
+            .copied()
+            .unwrap_or_else(|| column.default_ascending())
+    }
+
+    /// Toggles showing only processes owned by the current user.
+    pub fn toggle_current_user_only(&mut self) {
+        self.current_user_only = !self.current_user_only;
+        self.selected_index = 0;
+        self.scroll_offset = 0;
+        self.apply_filter();
+    }
+
+    /// Toggles showing only processes that own network sockets, taking a
+    /// fresh socket snapshot when turned on.
+    pub fn toggle_network_only(&mut self) {
+        self.network_only = !self.network_only;
+        self.connection_pids = if self.network_only {
+            self.source.connection_pids()
+        } else {
+            HashSet::new()
+        };
+        self.selected_index = 0;
+        self.scroll_offset = 0;
         self.apply_filter();
     }
 
-    /// Toggles sort order between ascending and descending.
+    /// Toggles the disk columns between per-second rates and lifetime
+    /// totals, re-sorting if they are the sort column.
+    pub fn toggle_disk_totals(&mut self) {
+        self.anchor_selection();
+        self.disk_totals = !self.disk_totals;
+        self.frozen_order.clear();
+        self.resort();
+        self.restore_selection();
+        self.error_message = Some(if self.disk_totals {
+            "Disk columns: total bytes since process start".to_string()
+        } else {
+            "Disk columns: bytes per second".to_string()
+        });
+    }
+
+    /// Toggles showing the Idle/System and other kernel pseudo-processes.
+    pub fn toggle_show_system(&mut self) {
+        self.show_system = !self.show_system;
+        self.selected_index = 0;
+        self.scroll_offset = 0;
+        self.apply_filter();
+    }
+
+    /// Toggles the debug line (metric totals and refresh/render timings).
+    pub fn toggle_debug_line(&mut self) {
+        self.debug_line = !self.debug_line;
+        self.refresh_duration = Duration::ZERO;
+        self.render_duration = Duration::ZERO;
+    }
+
+    /// Cycles how idle processes are shown (shown → dimmed → hidden).
+    pub fn cycle_idle_mode(&mut self) {
+        self.idle_mode = self.idle_mode.next();
+        self.selected_index = 0;
+        self.scroll_offset = 0;
+        self.apply_filter();
+    }
+
+    /// Returns true once there are enough samples to tell idle processes
+    /// apart: the first refresh has no rates to compare against.
+    pub fn idle_known(&self) -> bool {
+        self.refresh_count > 1
+    }
+
+    /// Checks whether a process should be drawn dimmed as idle.
+    pub fn is_dimmed_idle(&self, entry: &ProcessEntry) -> bool {
+        self.idle_mode == IdleMode::Dim && self.idle_known() && entry.is_idle()
+    }
+
+    /// Resets every process's average and peak CPU usage, so they
+    /// cover only what happens from now on.
+    pub fn reset_cpu_stats(&mut self) {
+        self.cpu_tracker.reset_cpu_stats();
+        for process in &mut self.processes {
+            process.cpu_avg_percent = 0.0;
+            process.cpu_peak_percent = 0.0;
+        }
+        if let Some(ref mut details) = self.detail_view_data {
+            details.cpu_avg_percent = 0.0;
+            details.cpu_peak_percent = 0.0;
+        }
+        self.error_message = Some("CPU average and peak reset".to_string());
+    }
+
+    /// Switches between the full and compact column layouts.
+    pub fn toggle_compact_rows(&mut self) {
+        self.compact_rows = !self.compact_rows;
+    }
+
+    /// Turns alternating row backgrounds on or off.
+    pub fn toggle_row_stripes(&mut self) {
+        self.row_stripes = !self.row_stripes;
+    }
+
+    /// Pins or unpins the selected process at the top of the list.
+    pub fn toggle_pin(&mut self) {
+        self.anchor_selection();
+        let pid = match self.selected_pid {
+            Some(pid) => pid,
+            None => return,
+        };
+
+        if !self.pinned.remove(&pid) {
+            self.pinned.insert(pid);
+        }
+        self.apply_filter();
+
+        // Keep the cursor on the process that was just (un)pinned
+        self.restore_selection();
+    }
+
+    /// Marks or unmarks the process under the cursor for batch actions,
+    /// then moves the cursor down so consecutive rows can be marked quickly.
+    pub fn toggle_selected(&mut self) {
+        let pid = match self.selected_process() {
+            Some(p) => p.info.pid,
+            None => return,
+        };
+
+        if !self.selected_pids.remove(&pid) {
+            self.selected_pids.insert(pid);
+        }
+        self.move_down();
+    }
+
+    /// Returns true if a process started recently enough to be highlighted.
+    ///
+    /// Processes without a readable start time report an uptime of 0, so
+    /// they are never treated as new.
+    pub fn is_new_process(&self, entry: &ProcessEntry) -> bool {
+        self.new_process_secs > 0
+            && entry.start_time.is_some()
+            && entry.uptime_seconds < self.new_process_secs
+    }
+
+    /// Returns true if any filter (name, owner, network, or system) is
+    /// narrowing the list.
+    pub fn is_filtered(&self) -> bool {
+        !self.filter.is_empty() || self.current_user_only || self.network_only || !self.show_system
+    }
+
+    /// Returns short tags for the toggles that change what the list shows
+    /// or how actions behave, for display as a breadcrumb (e.g. `[USER]`).
+    pub fn active_mode_tags(&self) -> Vec<&'static str> {
+        let mut tags = Vec::new();
+        if self.current_user_only {
+            tags.push("USER");
+        }
+        if self.network_only {
+            tags.push("NET");
+        }
+        if self.disk_totals {
+            tags.push("DISK-TOTAL");
+        }
+        if self.freeze_order {
+            tags.push("FROZEN");
+        }
+        if !self.show_system {
+            tags.push("NO-SYS");
+        }
+        if self.no_confirm_kill {
+            tags.push("NO-CONFIRM");
+        }
+        if self.new_process_secs == 0 {
+            tags.push("NO-HIGHLIGHT");
+        }
+        if self.cpu_smoothing.is_some() {
+            tags.push("SMOOTH");
+        }
+        if self.kill_hold.is_some() {
+            tags.push("HOLD");
+        }
+        match self.idle_mode {
+            IdleMode::Show => {}
+            IdleMode::Dim => tags.push("IDLE-DIM"),
+            IdleMode::Hide => tags.push("IDLE-HIDE"),
+        }
+        tags
+    }
+
+    /// Gets how long the app has been monitoring, in whole seconds.
+    pub fn monitoring_seconds(&self) -> u64 {
+        self.monitoring_started.elapsed().as_secs()
+    }
+
+    /// Toggles sort order between ascending and descending for the
+    /// current column only.
     pub fn toggle_sort_order(&mut self) {
+        self.anchor_selection();
         self.sort_ascending = !self.sort_ascending;
-        self.sort_processes();
-        self.apply_filter();
+        self.sort_directions.insert(self.sort_column, self.sort_ascending);
+        self.frozen_order.clear();
+        self.resort();
+        self.restore_selection();
+    }
+}
+
+/// Sets each process's `child_count` from the other entries' parent PIDs.
+fn count_children(processes: &mut [ProcessEntry]) {
+    let mut counts: HashMap<u32, u32> = HashMap::new();
+    for p in processes.iter() {
+        // The Idle process (PID 0) reports itself as its own parent
+        if p.info.parent_pid != p.info.pid {
+            *counts.entry(p.info.parent_pid).or_default() += 1;
+        }
     }
+    for p in processes.iter_mut() {
+        p.child_count = counts.get(&p.info.pid).copied().unwrap_or(0);
+    }
+}
+
+/// Gets a working set as a percentage of physical memory (0.0 if the
+/// total is unknown).
+fn memory_percent(working_set: u64, total_physical: u64) -> f64 {
+    if total_physical == 0 {
+        return 0.0;
+    }
+    working_set as f64 / total_physical as f64 * 100.0
+}
+
+/// Appends a usage sample, dropping the oldest beyond `USAGE_HISTORY_SAMPLES`.
+fn push_sample(history: &mut VecDeque<f64>, value: f64) {
+    if history.len() == USAGE_HISTORY_SAMPLES {
+        history.pop_front();
+    }
+    history.push_back(value);
 }
 
 impl Default for App {
@@ -356,3 +1203,476 @@ impl Default for App {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::source::MockProcessSource;
+
+    // PIDs are odd so they never collide with a real (multiple-of-4) PID
+    // that the CPU tracker could open
+    fn mock_source() -> MockProcessSource {
+        MockProcessSource::default()
+            .with_process(101, 1, "explorer.exe", 80 * 1024 * 1024)
+            .with_process(103, 101, "notepad.exe", 20 * 1024 * 1024)
+            .with_process(105, 101, "chrome.exe", 300 * 1024 * 1024)
+            .with_process(107, 1, "svchost.exe", 10 * 1024 * 1024)
+    }
+
+    fn names(processes: &[ProcessEntry]) -> Vec<&str> {
+        processes.iter().map(|p| p.info.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_refresh_from_source() {
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.refresh();
+
+        assert_eq!(app.processes.len(), 4);
+        assert_eq!(app.filtered_processes.len(), 4);
+        let explorer = app.processes.iter().find(|p| p.info.pid == 101).unwrap();
+        assert_eq!(explorer.memory_bytes, 80 * 1024 * 1024);
+        assert_eq!(explorer.mem_percent, 7.8125); // of the mock's 1 GiB
+        assert_eq!(explorer.child_count, 2);
+        assert_eq!(app.total_handles, 40);
+        assert_eq!(app.memory_history.back(), Some(&50.0));
+    }
+
+    #[test]
+    fn test_apply_filter() {
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.refresh();
+
+        app.filter = "NOTE".to_string();
+        app.apply_filter();
+        assert_eq!(names(&app.filtered_processes), ["notepad.exe"]);
+
+        app.filter = "pid:105-107".to_string();
+        app.sort_column = SortColumn::Pid;
+        app.sort_ascending = true;
+        app.resort();
+        assert_eq!(names(&app.filtered_processes), ["chrome.exe", "svchost.exe"]);
+    }
+
+    #[test]
+    fn test_sort_processes() {
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.refresh();
+
+        app.sort_column = SortColumn::Memory;
+        app.sort_ascending = false;
+        app.resort();
+        assert_eq!(
+            names(&app.filtered_processes),
+            ["chrome.exe", "explorer.exe", "notepad.exe", "svchost.exe"]
+        );
+
+        app.sort_column = SortColumn::Name;
+        app.sort_ascending = true;
+        app.resort();
+        assert_eq!(
+            names(&app.filtered_processes),
+            ["chrome.exe", "explorer.exe", "notepad.exe", "svchost.exe"]
+        );
+    }
+
+    #[test]
+    fn test_build_process_tree() {
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.tree_view_mode = true;
+        app.sort_column = SortColumn::Memory;
+        app.sort_ascending = false;
+        app.refresh();
+
+        // Children follow their parent, siblings keep the sort order
+        assert_eq!(
+            names(&app.filtered_processes),
+            ["explorer.exe", "chrome.exe", "notepad.exe", "svchost.exe"]
+        );
+        let depths: Vec<usize> = app.filtered_processes.iter().map(|p| p.tree_depth).collect();
+        assert_eq!(depths, [0, 1, 1, 0]);
+    }
+
+    #[test]
+    fn test_tree_siblings_tie_broken_by_pid() {
+        let mut source = MockProcessSource::default().with_process(201, 1, "cmd.exe", 1024);
+        for pid in [231, 209, 217, 203, 225, 211] {
+            source = source.with_process(pid, 201, "conhost.exe", 4096);
+        }
+        let mut app = App::with_source(Box::new(source));
+        app.tree_view_mode = true;
+        app.sort_column = SortColumn::Name;
+        app.sort_ascending = true;
+        app.refresh();
+
+        let pids: Vec<u32> = app.filtered_processes.iter().map(|p| p.info.pid).collect();
+        assert_eq!(pids, [201, 203, 209, 211, 217, 225, 231]);
+    }
+
+    #[test]
+    fn test_sort_picker() {
+        use crossterm::event::KeyCode;
+
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.refresh();
+
+        // The cycle order and the picker's list agree
+        for pair in SortColumn::ALL.windows(2) {
+            assert_eq!(pair[0].next(), pair[1]);
+        }
+
+        // Memory is reversed, then left for another column
+        app.set_sort_column(SortColumn::Memory);
+        app.toggle_sort_order();
+        app.set_sort_column(SortColumn::Name);
+        assert!(app.sort_direction(SortColumn::Memory));
+        assert!(!app.sort_direction(SortColumn::Cpu));
+
+        app.open_sort_picker();
+        assert_eq!(SortColumn::ALL[app.sort_picker_index], SortColumn::Name);
+        app.handle_sort_picker_key(KeyCode::Home);
+        app.handle_sort_picker_key(KeyCode::Down);
+        app.handle_sort_picker_key(KeyCode::Down);
+        app.handle_sort_picker_key(KeyCode::Enter);
+        assert_eq!(app.view_mode, ViewMode::ProcessList);
+        assert_eq!(app.sort_column, SortColumn::Memory);
+        assert!(app.sort_ascending);
+        assert_eq!(
+            names(&app.filtered_processes),
+            ["svchost.exe", "notepad.exe", "explorer.exe", "chrome.exe"]
+        );
+
+        // Esc leaves the sort alone
+        app.open_sort_picker();
+        app.handle_sort_picker_key(KeyCode::End);
+        app.handle_sort_picker_key(KeyCode::Esc);
+        assert_eq!(app.sort_column, SortColumn::Memory);
+    }
+
+    #[test]
+    fn test_pending_select() {
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.sort_column = SortColumn::Pid;
+        app.sort_ascending = true;
+        app.pending_select_pid = Some(105);
+        app.refresh();
+        assert_eq!(app.filtered_processes[app.selected_index].info.pid, 105);
+        assert!(app.pending_select_pid.is_none());
+
+        // A missing PID starts at the top with a message
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.pending_select_pid = Some(999);
+        app.refresh();
+        assert_eq!(app.selected_index, 0);
+        assert!(app.error_message.is_some());
+    }
+
+    #[test]
+    fn test_idle_mode() {
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.idle_mode = IdleMode::Hide;
+
+        // One sample has no rates to go on, so nothing is hidden yet
+        app.refresh();
+        assert_eq!(app.filtered_processes.len(), 4);
+
+        app.refresh();
+        assert!(app.filtered_processes.is_empty());
+
+        // Busy and pinned processes stay listed
+        app.processes.iter_mut().find(|p| p.info.pid == 105).unwrap().cpu_percent = 5.0;
+        app.pinned.insert(107);
+        app.apply_filter();
+        let mut shown = names(&app.filtered_processes);
+        shown.sort();
+        assert_eq!(shown, ["chrome.exe", "svchost.exe"]);
+
+        // Dim lists everything but marks the idle ones
+        app.cycle_idle_mode();
+        assert_eq!(app.idle_mode, IdleMode::Show);
+        app.idle_mode = IdleMode::Dim;
+        app.apply_filter();
+        assert_eq!(app.filtered_processes.len(), 4);
+        let dimmed: Vec<u32> = app
+            .filtered_processes
+            .iter()
+            .filter(|p| app.is_dimmed_idle(p))
+            .map(|p| p.info.pid)
+            .collect();
+        assert!(!dimmed.contains(&105));
+        assert_eq!(dimmed.len(), 3);
+    }
+
+    #[test]
+    fn test_network_only() {
+        let mut source = mock_source();
+        source.connections = [103, 107].into_iter().collect();
+        let mut app = App::with_source(Box::new(source));
+        app.refresh();
+        assert!(app.connection_pids.is_empty());
+
+        app.toggle_network_only();
+        let mut shown = names(&app.filtered_processes);
+        shown.sort();
+        assert_eq!(shown, ["notepad.exe", "svchost.exe"]);
+        assert!(app.active_mode_tags().contains(&"NET"));
+
+        app.refresh();
+        assert_eq!(app.filtered_processes.len(), 2);
+
+        app.toggle_network_only();
+        assert_eq!(app.filtered_processes.len(), 4);
+        assert!(app.connection_pids.is_empty());
+    }
+
+    #[test]
+    fn test_disk_totals_sort() {
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.refresh();
+        app.sort_column = SortColumn::DiskReadRate;
+        for p in app.processes.iter_mut() {
+            // explorer reads the most now, svchost the most overall
+            let (rate, total) = match p.info.name.as_str() {
+                "explorer.exe" => (500.0, 10),
+                "svchost.exe" => (1.0, 9000),
+                _ => (0.0, 0),
+            };
+            p.disk_read_rate = rate;
+            p.disk_read = total;
+        }
+        app.resort();
+        assert_eq!(app.filtered_processes[0].info.name, "explorer.exe");
+
+        app.toggle_disk_totals();
+        assert_eq!(app.filtered_processes[0].info.name, "svchost.exe");
+        assert!(app.active_mode_tags().contains(&"DISK-TOTAL"));
+    }
+
+    #[test]
+    fn test_freeze_order() {
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.set_sort_column(SortColumn::Memory);
+        app.refresh();
+        assert_eq!(names(&app.filtered_processes), ["chrome.exe", "explorer.exe", "notepad.exe", "svchost.exe"]);
+
+        app.toggle_freeze_order();
+        let svchost = app.processes.iter_mut().find(|p| p.info.pid == 107).unwrap();
+        svchost.memory_bytes = 900 * 1024 * 1024;
+        app.resort();
+        // The new value is shown, but the row stays where it was
+        assert_eq!(app.filtered_processes[3].memory_bytes, 900 * 1024 * 1024);
+        assert_eq!(names(&app.filtered_processes)[3], "svchost.exe");
+
+        // Reversing the sort re-sorts once, then freezes that order
+        app.toggle_sort_order();
+        assert_eq!(names(&app.filtered_processes)[0], "notepad.exe");
+        assert!(app.active_mode_tags().contains(&"FROZEN"));
+
+        app.toggle_freeze_order();
+        app.toggle_sort_order();
+        assert_eq!(names(&app.filtered_processes)[0], "svchost.exe");
+    }
+
+    #[test]
+    fn test_freeze_order_pins_and_tree() {
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.set_sort_column(SortColumn::Memory);
+        app.refresh();
+        app.toggle_freeze_order();
+
+        // Pinning still moves the row to the top while frozen
+        app.selected_index = 3;
+        app.toggle_pin();
+        assert_eq!(names(&app.filtered_processes), ["svchost.exe", "chrome.exe", "explorer.exe", "notepad.exe"]);
+        app.toggle_pin();
+        assert_eq!(names(&app.filtered_processes)[3], "svchost.exe");
+
+        // In tree view, a new child appears under its parent, not at the bottom
+        app.toggle_tree_view();
+        assert!(app.freeze_order);
+        let tree = ["explorer.exe", "chrome.exe", "notepad.exe", "svchost.exe"];
+        assert_eq!(names(&app.filtered_processes), tree);
+        app.source = Box::new(mock_source().with_process(109, 101, "calc.exe", 1024 * 1024));
+        app.refresh();
+        assert_eq!(
+            names(&app.filtered_processes),
+            ["explorer.exe", "chrome.exe", "notepad.exe", "calc.exe", "svchost.exe"]
+        );
+        app.refresh();
+        assert_eq!(names(&app.filtered_processes)[3], "calc.exe");
+    }
+
+    #[test]
+    fn test_kill_impact() {
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.refresh();
+        let explorer = app.processes.iter_mut().find(|p| p.info.pid == 101).unwrap();
+        explorer.cpu_percent = 23.0;
+        assert_eq!(explorer.kill_impact(ByteUnits::Binary), "23.0% CPU, 80.0 MiB, 2 children");
+
+        explorer.child_count = 1;
+        explorer.memory_access_denied = true;
+        assert_eq!(explorer.kill_impact(ByteUnits::Binary), "23.0% CPU, 1 child");
+
+        let svchost = app.processes.iter().find(|p| p.info.pid == 107).unwrap();
+        assert!(svchost.kill_impact(ByteUnits::Decimal).ends_with("10.5 MB, no children"));
+    }
+
+    #[test]
+    fn test_refresh_count() {
+        let mut app = App::with_source(Box::new(mock_source()));
+        assert_eq!(app.refresh_count, 0);
+        app.refresh();
+        app.refresh();
+        assert_eq!(app.refresh_count, 2);
+        assert!(app.monitoring_seconds() < 60);
+    }
+
+    #[test]
+    fn test_selected_process_on_empty_list() {
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.refresh();
+        assert!(app.selected_process().is_some());
+
+        // A filter that matches nothing leaves no selection; actions on
+        // the selected process do nothing instead of panicking
+        app.filter = "missing".to_string();
+        app.apply_filter();
+        assert_eq!(app.selected_index, 0);
+        assert!(app.selected_process().is_none());
+        app.request_kill();
+        app.open_detail_view();
+        app.toggle_selected();
+        app.copy_process_path();
+        assert_eq!(app.view_mode, ViewMode::ProcessList);
+        assert!(app.selected_pids.is_empty());
+
+        // An out-of-range index is treated the same way
+        app.selected_index = usize::MAX;
+        assert!(app.selected_process().is_none());
+    }
+
+    #[test]
+    fn test_metric_totals() {
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.refresh();
+        for (i, p) in app.processes.iter_mut().enumerate() {
+            p.cpu_percent = 10.0 * (i + 1) as f64;
+            p.disk_read_rate = 100.0;
+            p.disk_write_rate = 50.0;
+        }
+
+        // Filtered-out processes still count
+        app.filter = "note".to_string();
+        app.apply_filter();
+        let totals = app.metric_totals();
+        assert_eq!(totals.cpu_percent, 100.0);
+        assert_eq!(totals.disk_read_rate, 400.0);
+        assert_eq!(totals.disk_write_rate, 200.0);
+        assert_eq!(totals.memory_bytes, 410 * 1024 * 1024);
+
+        // Filtered totals only count the rows that are shown
+        let filtered = app.filtered_totals();
+        let notepad = &app.filtered_processes[0];
+        assert_eq!(app.filtered_processes.len(), 1);
+        assert_eq!(filtered.cpu_percent, notepad.cpu_percent);
+        assert_eq!(filtered.memory_bytes, 20 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_live_cpu_sum_tracks_system() {
+        let mut app = App::new();
+        app.refresh();
+        std::thread::sleep(std::time::Duration::from_millis(250));
+        app.refresh();
+
+        // Both are shares of the same interval; allow for rounding and
+        // processes that started or exited between the samples
+        let totals = app.metric_totals();
+        assert!(totals.cpu_percent <= 100.0 + 5.0, "sum {}", totals.cpu_percent);
+        assert!(
+            totals.cpu_percent <= app.system_cpu + 5.0,
+            "sum {} vs system {}",
+            totals.cpu_percent,
+            app.system_cpu
+        );
+    }
+
+    #[test]
+    fn test_realtime_needs_confirmation() {
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.refresh();
+        app.filtered_processes[0].priority = Priority::High;
+        let pid = app.filtered_processes[0].info.pid;
+
+        app.raise_priority();
+        assert_eq!(app.view_mode, ViewMode::ConfirmRealtime);
+        assert_eq!(app.pending_realtime.len(), 1);
+        assert_eq!(app.pending_realtime[0].0, pid);
+
+        app.cancel_realtime();
+        assert_eq!(app.view_mode, ViewMode::ProcessList);
+        assert!(app.pending_realtime.is_empty());
+
+        // A batch containing a High process asks too
+        app.processes.iter_mut().for_each(|p| p.priority = Priority::High);
+        app.selected_pids.extend([101, 103]);
+        app.raise_priority();
+        assert_eq!(app.view_mode, ViewMode::ConfirmRealtime);
+        assert_eq!(app.pending_realtime.len(), 2);
+    }
+
+    #[test]
+    fn test_detail_view_kept_after_exit() {
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.sort_column = SortColumn::Pid;
+        app.sort_ascending = true;
+        app.refresh();
+        app.selected_index = app.filtered_processes.iter().position(|p| p.info.pid == 103).unwrap();
+        app.open_detail_view();
+
+        // notepad.exe exits while its details are open
+        let mut source = mock_source();
+        source.processes.retain(|(info, _)| info.pid != 103);
+        app.source = Box::new(source);
+        app.refresh();
+        app.refresh_detail_view();
+
+        assert_eq!(app.view_mode, ViewMode::DetailView);
+        assert!(app.detail_view_exited);
+        assert_eq!(app.detail_view_data.as_ref().map(|d| d.pid), Some(103));
+
+        // A new process reusing the PID does not replace the frozen data
+        app.source = Box::new(mock_source().with_process(103, 0, "reused.exe", 1024));
+        app.refresh();
+        app.reload_detail_view();
+        assert_eq!(app.detail_view_data.as_ref().map(|d| d.name.as_str()), Some("notepad.exe"));
+
+        app.close_detail_view();
+        assert!(!app.detail_view_exited);
+    }
+
+    #[test]
+    fn test_exited_process_state_dropped() {
+        let mut app = App::with_source(Box::new(mock_source()));
+        app.refresh();
+        app.pinned.insert(103);
+        app.selected_pids.insert(103);
+        app.suspended_pids.insert(103);
+        // Suspended before it ever appeared in the list
+        app.suspended_pids.insert(199);
+        assert!(app.is_process_suspended(103));
+
+        // notepad.exe exits
+        let mut source = mock_source();
+        source.processes.retain(|(info, _)| info.pid != 103);
+        app.source = Box::new(source);
+        app.refresh();
+
+        assert_eq!(app.processes.len(), 3);
+        assert!(app.pinned.is_empty());
+        assert!(app.selected_pids.is_empty());
+        assert!(!app.is_process_suspended(103));
+        assert!(!app.is_process_suspended(199));
+    }
+}
@@ -35,8 +35,11 @@ impl App {
             .filter(|p| p.info.parent_pid == 0 || !all_pids.contains(&p.info.parent_pid))
             .collect();
 
-        // Sort roots by name
-        roots.sort_by(|a, b| a.info.name.to_lowercase().cmp(&b.info.name.to_lowercase()));
+        // Sort roots by the current sort column, ties by PID
+        let (column, ascending, totals) = (self.sort_column, self.sort_ascending, self.disk_totals);
+        roots.sort_by(|a, b| {
+            column.compare(a, b, ascending, totals).then_with(|| a.info.pid.cmp(&b.info.pid))
+        });
 
         // Recursively build tree
         let mut result = Vec::new();
@@ -70,8 +73,13 @@ impl App {
                 .cloned()
                 .collect();
 
-            // Sort children by name
-            children.sort_by(|a, b| a.info.name.to_lowercase().cmp(&b.info.name.to_lowercase()));
+            // Sort siblings by the current sort column. Children are collected
+            // in hash map order, so ties are broken by PID to keep equal
+            // siblings (e.g. several idle conhost.exe) from swapping places
+            let (column, ascending, totals) = (self.sort_column, self.sort_ascending, self.disk_totals);
+            children.sort_by(|a, b| {
+                column.compare(a, b, ascending, totals).then_with(|| a.info.pid.cmp(&b.info.pid))
+            });
 
             // Recursively add children
             for child in children {
@@ -86,17 +94,17 @@ impl App {
     /// relationships. When disabled, processes are shown in a flat list.
     pub fn toggle_tree_view(&mut self) {
         self.tree_view_mode = !self.tree_view_mode;
+        self.frozen_order.clear();
         self.selected_index = 0;
         self.scroll_offset = 0;
 
-        if self.tree_view_mode {
-            self.build_process_tree();
-        } else {
+        if !self.tree_view_mode {
             // Reset tree depth
             for proc in &mut self.processes {
                 proc.tree_depth = 0;
             }
-            self.apply_filter();
         }
+        // Restore the flat sort, or build the tree in sorted sibling order
+        self.resort();
     }
 }
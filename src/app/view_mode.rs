@@ -16,10 +16,19 @@ pub enum ViewMode {
     
     /// Filter input mode - typing a process name filter
     FilterInput,
+
+    /// Find input mode - typing text to highlight without filtering
+    FindInput,
     
     /// Kill confirmation dialog - waiting for Y/N
     ConfirmKill,
     
+    /// Quit confirmation prompt - waiting for Y/N
+    ConfirmQuit,
+
+    /// Realtime priority confirmation - waiting for Y/N
+    ConfirmRealtime,
+    
     /// Detailed process information view
     DetailView,
     
@@ -28,6 +37,15 @@ pub enum ViewMode {
     
     /// CPU affinity editing dialog
     Affinity,
+    
+    /// System information panel
+    SysInfo,
+
+    /// Sort column picker listing every column
+    SortPicker,
+
+    /// Process counts by CPU and memory band
+    Histogram,
 }
 
 #[allow(dead_code)]
@@ -50,6 +68,24 @@ impl ViewMode {
         matches!(self, ViewMode::Help)
     }
 
+    /// Returns true if showing the system information panel
+    #[inline]
+    pub fn is_sys_info(&self) -> bool {
+        matches!(self, ViewMode::SysInfo)
+    }
+
+    /// Returns true if showing the sort column picker
+    #[inline]
+    pub fn is_sort_picker(&self) -> bool {
+        matches!(self, ViewMode::SortPicker)
+    }
+
+    /// Returns true if showing the CPU/memory histogram
+    #[inline]
+    pub fn is_histogram(&self) -> bool {
+        matches!(self, ViewMode::Histogram)
+    }
+
     /// Returns true if in affinity edit mode
     #[inline]
     pub fn is_affinity(&self) -> bool {
@@ -62,9 +98,27 @@ impl ViewMode {
         matches!(self, ViewMode::FilterInput)
     }
 
+    /// Returns true if in find input mode
+    #[inline]
+    pub fn is_find_input(&self) -> bool {
+        matches!(self, ViewMode::FindInput)
+    }
+
     /// Returns true if in kill confirmation mode
     #[inline]
     pub fn is_confirm_kill(&self) -> bool {
         matches!(self, ViewMode::ConfirmKill)
     }
+
+    /// Returns true if confirming a raise to Realtime priority
+    #[inline]
+    pub fn is_confirm_realtime(&self) -> bool {
+        matches!(self, ViewMode::ConfirmRealtime)
+    }
+
+    /// Returns true if in quit confirmation mode
+    #[inline]
+    pub fn is_confirm_quit(&self) -> bool {
+        matches!(self, ViewMode::ConfirmQuit)
+    }
 }
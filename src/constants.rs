@@ -32,6 +32,18 @@ pub const MIN_REFRESH_MS: u64 = 250;
 /// Maximum allowed refresh interval
 pub const MAX_REFRESH_MS: u64 = 10000;
 
+/// Number of refresh ticks between re-fetching expensive detail view data
+/// (modules, connections, affinity); live metrics update every tick
+pub const DETAIL_SLOW_REFRESH_TICKS: u32 = 5;
+
+/// Fraction of the process CPU time by which the summed thread CPU time
+/// must differ before the detail view flags it
+pub const THREAD_CPU_DIVERGENCE_RATIO: f64 = 0.10;
+
+/// Seconds by which the summed thread CPU time must differ before the
+/// detail view flags it (keeps short-lived processes from being flagged)
+pub const THREAD_CPU_DIVERGENCE_MIN_SECONDS: f64 = 1.0;
+
 
 // ============================================================================
 // Navigation
@@ -51,6 +63,97 @@ pub const VISIBLE_ROWS_OVERHEAD: usize = 6;
 /// Maximum indentation depth for tree view display
 pub const MAX_TREE_INDENT_DEPTH: usize = 5;
 
+// ============================================================================
+// New Process Highlight
+// ============================================================================
+
+/// Processes younger than this many seconds are highlighted as new
+pub const DEFAULT_NEW_PROCESS_SECS: u64 = 10;
+
+// ============================================================================
+// CPU Smoothing
+// ============================================================================
+
+/// Default EMA weight of the newest CPU sample when `--smooth` is given
+pub const DEFAULT_CPU_SMOOTHING_ALPHA: f64 = 0.3;
+
+// ============================================================================
+// Process Termination
+// ============================================================================
+
+/// Exit code given to processes terminated by the kill action
+pub const DEFAULT_KILL_EXIT_CODE: u32 = 1;
+
+/// How long "kill and hold" keeps killing respawns of the same executable
+pub const KILL_HOLD_SECS: u64 = 5;
+
+/// How often (milliseconds) "kill and hold" checks for respawns
+pub const KILL_HOLD_POLL_MS: u64 = 250;
+
+// ============================================================================
+// Exit Codes
+// ============================================================================
+
+/// Normal exit, including quitting the interactive view
+pub const EXIT_OK: u8 = 0;
+
+/// An I/O error, such as failing to set up the terminal or write an export
+pub const EXIT_IO_ERROR: u8 = 1;
+
+/// Invalid command-line arguments
+pub const EXIT_USAGE: u8 = 2;
+
+/// The process snapshot could not be taken
+pub const EXIT_SNAPSHOT_FAILED: u8 = 3;
+
+/// The PID given to `--detail` does not exist
+pub const EXIT_PID_NOT_FOUND: u8 = 4;
+
+// ============================================================================
+// Filter History
+// ============================================================================
+
+/// Maximum number of previous filters remembered for Up/Down recall
+pub const FILTER_HISTORY_MAX: usize = 20;
+
+// ============================================================================
+// HTTP Endpoint
+// ============================================================================
+
+/// How often `--serve` checks for connections between refreshes, in ms
+pub const SERVE_POLL_MS: u64 = 50;
+
+/// How long `--serve` waits in total for a client to send its request
+/// (and, separately, to take the response), in ms. Requests are handled
+/// on the refresh thread, so this bounds how long one client can stall it
+pub const SERVE_READ_TIMEOUT_MS: u64 = 2000;
+
+/// Largest request head `--serve` reads before answering
+pub const SERVE_MAX_REQUEST_BYTES: usize = 8192;
+
+// ============================================================================
+// Socket Tables
+// ============================================================================
+
+/// Times a TCP/UDP owner table is re-read when it grew between the size
+/// query and the read, before giving up for this refresh
+pub const SOCKET_TABLE_READ_ATTEMPTS: usize = 4;
+
+// ============================================================================
+// Session
+// ============================================================================
+
+/// File under `%LOCALAPPDATA%\<app name>` holding the last selection,
+/// sort and filter
+pub const SESSION_FILE_NAME: &str = "session.txt";
+
+// ============================================================================
+// Usage History
+// ============================================================================
+
+/// Number of system CPU and memory samples kept for the header sparklines
+pub const USAGE_HISTORY_SAMPLES: usize = 120;
+
 // ============================================================================
 // Byte Size Conversions
 // ============================================================================
@@ -74,9 +177,48 @@ pub const HELP_DIALOG_WIDTH: usize = 52;
 /// Width of the CPU affinity dialog box
 pub const AFFINITY_DIALOG_WIDTH: usize = 60;
 
+/// Most cores per row in the CPU affinity dialog grid (fewer on narrow terminals)
+pub const AFFINITY_MAX_CORES_PER_ROW: usize = 8;
+
+/// Width of one core cell in the CPU affinity grid, e.g. ">[X]Core 12  "
+pub const AFFINITY_CORE_CELL_WIDTH: usize = 13;
+
+/// Width of the system information panel
+pub const SYSINFO_DIALOG_WIDTH: usize = 56;
+
+/// Width of the label column in the system information panel
+pub const SYSINFO_LABEL_WIDTH: usize = 14;
+
+/// Width of the sort column picker
+pub const SORT_PICKER_DIALOG_WIDTH: usize = 36;
+
+/// Width of the CPU/memory histogram panel
+pub const HISTOGRAM_DIALOG_WIDTH: usize = 60;
+
+/// Width of the band label column in the histogram panel
+pub const HISTOGRAM_LABEL_WIDTH: usize = 12;
+
 /// Minimum margin from screen edge for dialogs
 pub const DIALOG_MARGIN: usize = 4;
 
+/// 256-color palette index of the background of striped (odd) rows:
+/// a dark grey just off black, so the CPU colors stay readable
+pub const ROW_STRIPE_ANSI_COLOR: u8 = 236;
+
+// ============================================================================
+// Terminal Size
+// ============================================================================
+
+/// Minimum terminal width (columns) for the normal UI
+pub const MIN_TERMINAL_WIDTH: usize = 40;
+
+/// Minimum terminal height (rows) for the normal UI
+pub const MIN_TERMINAL_HEIGHT: usize = 10;
+
+/// Minimum terminal height (rows) to give the path its own footer line
+/// when `--path-line` is set
+pub const PATH_LINE_MIN_HEIGHT: usize = 20;
+
 // ============================================================================
 // Help Dialog Formatting
 // ============================================================================
@@ -84,6 +226,48 @@ pub const DIALOG_MARGIN: usize = 4;
 /// Width of the key column in help dialog
 pub const HELP_KEY_COL_WIDTH: usize = 14;
 
+// ============================================================================
+// Power Usage Estimate
+// ============================================================================
+
+/// Score added per MB/s of disk throughput (CPU% counts one point each)
+pub const POWER_DISK_MB_WEIGHT: f64 = 2.0;
+
+/// Minimum scores for Low, Moderate, High and Very High power usage
+pub const POWER_SCORE_THRESHOLDS: [f64; 4] = [1.0, 5.0, 15.0, 40.0];
+
+// ============================================================================
+// Histogram
+// ============================================================================
+
+/// Upper bounds (exclusive) of the 0%, 0-5%, 5-20% and 20-50% CPU bands;
+/// anything above falls in 50%+. The first bound is where CPU% still
+/// displays as 0.0.
+pub const HISTOGRAM_CPU_BOUNDS: [f64; 4] = [0.05, 5.0, 20.0, 50.0];
+
+/// Upper bounds (exclusive, in MB) of the <10 MB, 10-100 MB and
+/// 100 MB-1 GB memory bands; anything above falls in 1 GB+
+pub const HISTOGRAM_MEMORY_BOUNDS_MB: [f64; 3] = [10.0, 100.0, 1024.0];
+
+// ============================================================================
+// Memory Warnings
+// ============================================================================
+
+/// System commit charge (percent of the commit limit) above which a
+/// warning banner is shown
+pub const COMMIT_WARNING_PERCENT: f64 = 95.0;
+
+// ============================================================================
+// Idle Processes
+// ============================================================================
+
+/// CPU usage (percent) below which a process counts as idle
+pub const IDLE_CPU_PERCENT: f64 = 0.1;
+
+/// Working set change (bytes per second, either direction) below which
+/// a process's memory counts as stable
+pub const IDLE_MEMORY_GROWTH_BYTES: f64 = 64.0 * 1024.0;
+
 // ============================================================================
 // CPU Usage Thresholds (for coloring)
 // ============================================================================
@@ -5,14 +5,31 @@
 
 use windows::Win32::Foundation::{CloseHandle, HANDLE};
 use windows::Win32::System::Diagnostics::ToolHelp::{
-    CreateToolhelp32Snapshot, TH32CS_SNAPPROCESS,
+    CreateToolhelp32Snapshot, TH32CS_SNAPPROCESS, TH32CS_SNAPTHREAD,
 };
-use windows::Win32::System::Threading::{OpenProcess, PROCESS_ACCESS_RIGHTS};
+use windows::Win32::System::Threading::{
+    OpenProcess, OpenThread, PROCESS_ACCESS_RIGHTS, PROCESS_QUERY_INFORMATION,
+    PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_VM_READ, THREAD_ACCESS_RIGHTS,
+};
+
+/// Access rights tried by `ProcessHandle::open_for_query`, widest first.
+/// Protected and elevated processes refuse the wider ones to unelevated
+/// callers.
+const QUERY_ACCESS_TIERS: [PROCESS_ACCESS_RIGHTS; 3] = [
+    PROCESS_ACCESS_RIGHTS(PROCESS_QUERY_INFORMATION.0 | PROCESS_VM_READ.0),
+    PROCESS_ACCESS_RIGHTS(PROCESS_QUERY_LIMITED_INFORMATION.0 | PROCESS_VM_READ.0),
+    PROCESS_QUERY_LIMITED_INFORMATION,
+];
 
 /// A safe wrapper around a Windows process HANDLE.
 /// Automatically closes the handle when dropped.
 #[allow(dead_code)]
-pub struct ProcessHandle(HANDLE);
+pub struct ProcessHandle {
+    /// The open handle
+    handle: HANDLE,
+    /// The process identifier the handle was opened for
+    pid: u32,
+}
 
 #[allow(dead_code)]
 impl ProcessHandle {
@@ -29,7 +46,32 @@ impl ProcessHandle {
         // SAFETY: OpenProcess is safe to call with valid parameters.
         // We handle the error case where the handle is invalid.
         let handle = unsafe { OpenProcess(access, false, pid)? };
-        Ok(Self(handle))
+        Ok(Self { handle, pid })
+    }
+
+    /// Opens a process with the widest query rights it grants, so that
+    /// one handle serves every query the process allows.
+    ///
+    /// # Arguments
+    /// * `pid` - The process identifier
+    ///
+    /// # Returns
+    /// * `Ok(ProcessHandle)` - A wrapped handle, with at least limited query rights
+    /// * `Err` - If not even limited queries are allowed (or the process exited)
+    pub fn open_for_query(pid: u32) -> windows::core::Result<Self> {
+        let mut result = Self::open(pid, QUERY_ACCESS_TIERS[0]);
+        for &access in &QUERY_ACCESS_TIERS[1..] {
+            if result.is_ok() {
+                break;
+            }
+            result = Self::open(pid, access);
+        }
+        result
+    }
+
+    /// Returns the process identifier the handle was opened for.
+    pub fn pid(&self) -> u32 {
+        self.pid
     }
 
     /// Returns the raw HANDLE for use with Win32 APIs.
@@ -37,7 +79,7 @@ impl ProcessHandle {
     /// # Safety
     /// The caller must ensure the handle is not used after the ProcessHandle is dropped.
     pub fn as_raw(&self) -> HANDLE {
-        self.0
+        self.handle
     }
 }
 
@@ -46,7 +88,7 @@ impl Drop for ProcessHandle {
         // SAFETY: We own this handle and it's valid (we got it from OpenProcess).
         // CloseHandle is safe to call on a valid handle exactly once.
         unsafe {
-            let _ = CloseHandle(self.0);
+            let _ = CloseHandle(self.handle);
         }
     }
 }
@@ -69,6 +111,18 @@ impl SnapshotHandle {
         Ok(Self(handle))
     }
 
+    /// Creates a snapshot of all threads in the system.
+    ///
+    /// # Returns
+    /// * `Ok(SnapshotHandle)` - A wrapped handle to the snapshot
+    /// * `Err` - If the snapshot cannot be created
+    pub fn create_thread_snapshot() -> windows::core::Result<Self> {
+        // SAFETY: CreateToolhelp32Snapshot is safe to call.
+        // Thread snapshots always cover every process; the PID is ignored.
+        let handle = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0)? };
+        Ok(Self(handle))
+    }
+
     /// Returns the raw HANDLE for use with Win32 APIs.
     pub fn as_raw(&self) -> HANDLE {
         self.0
@@ -83,3 +137,38 @@ impl Drop for SnapshotHandle {
         }
     }
 }
+
+/// A safe wrapper around a Windows thread HANDLE.
+/// Automatically closes the handle when dropped.
+pub struct ThreadHandle(HANDLE);
+
+impl ThreadHandle {
+    /// Opens a thread by ID with the specified access rights.
+    ///
+    /// # Arguments
+    /// * `tid` - The thread identifier
+    /// * `access` - The access rights requested for the thread
+    ///
+    /// # Returns
+    /// * `Ok(ThreadHandle)` - A wrapped handle to the thread
+    /// * `Err` - If the thread cannot be opened (access denied, thread exited, etc.)
+    pub fn open(tid: u32, access: THREAD_ACCESS_RIGHTS) -> windows::core::Result<Self> {
+        // SAFETY: OpenThread is safe to call with valid parameters.
+        let handle = unsafe { OpenThread(access, false, tid)? };
+        Ok(Self(handle))
+    }
+
+    /// Returns the raw HANDLE for use with Win32 APIs.
+    pub fn as_raw(&self) -> HANDLE {
+        self.0
+    }
+}
+
+impl Drop for ThreadHandle {
+    fn drop(&mut self) {
+        // SAFETY: We own this handle and it's valid (we got it from OpenThread).
+        unsafe {
+            let _ = CloseHandle(self.0);
+        }
+    }
+}
@@ -5,8 +5,4 @@
 
 mod handles;
 
-pub use handles::SnapshotHandle;
-
-// ProcessHandle is available for future use
-#[allow(unused_imports)]
-pub use handles::ProcessHandle;
+pub use handles::{ProcessHandle, SnapshotHandle, ThreadHandle};
@@ -0,0 +1,39 @@
+//! Process-gathering library behind the CLI Windows Task Manager
+//!
+//! The `task_manager_cli` binary is a thin terminal front end over this
+//! crate; the same modules can be used to build other tools:
+//! - `system` - Safe wrappers over Win32 process, memory, CPU and I/O APIs
+//! - `ffi` - Raw declarations for APIs the `windows` crate does not cover
+//! - `app` - Application state, sorting, filtering, process tree and exports
+//! - `constants` - Shared configuration values
+//!
+//! # Example
+//!
+//! ```no_run
+//! use task_manager_cli::{enumerate_processes, get_process_memory_info, CpuTracker};
+//!
+//! let mut cpu = CpuTracker::new();
+//! cpu.get_system_cpu_usage(); // first call sets the baseline
+//! for process in enumerate_processes().unwrap_or_default() {
+//!     let memory = get_process_memory_info(process.pid);
+//!     // 0.0 until a second reading, one refresh interval later
+//!     let cpu_percent = cpu.get_process_cpu_usage(process.pid);
+//!     println!("{} {} {} {:.1}%", process.pid, process.name, memory.working_set, cpu_percent);
+//! }
+//! ```
+
+pub mod app;
+pub mod constants;
+pub mod ffi;
+pub mod system;
+
+// ============================================================================
+// Re-exports of the most commonly used types
+// ============================================================================
+
+pub use app::ProcessEntry;
+pub use system::cpu::CpuTracker;
+pub use system::{
+    enumerate_processes, get_process_memory_info, get_process_path, get_process_priority,
+    get_system_memory_info, Priority, ProcessInfo, ProcessMemoryInfo, SystemMemoryInfo,
+};
@@ -11,9 +11,37 @@
 //! Options:
 //!   -r, --refresh <MS>    Refresh interval in milliseconds [default: 2000]
 //!   -f, --filter <NAME>   Initial filter string to match process names
+//!       --filter-in <FIELDS>  Fields the filter searches: name, path, cmdline
+//!       --idle <MODE>     Show, dim or hide idle processes [default: show]
+//!       --units <UNITS>   binary (KiB, 1024) or decimal (KB, 1000) [default: binary]
+//!       --select <PID>    Start with this process selected (nothing filtered out)
 //!   -s, --sort <COLUMN>   Initial sort column [default: cpu]
-//!   -a, --ascending       Sort in ascending order (default is descending)
+//!   -a, --ascending       Sort ascending (default: names/IDs up, metrics down)
 //!   -t, --tree            Start in tree view mode
+//!       --compact         Start with the compact column layout
+//!       --stripes         Start with alternating row backgrounds
+//!       --path-line       Keep the selected path on its own footer line
+//!       --name-width <N>  Cut process names at N columns [default: row width]
+//!       --path-width <N>  Cut the footer path at N columns [default: line width]
+//!       --debug           Show refresh/render timings and summed CPU%/disk totals
+//!   -x, --export          Export process list to CSV and exit
+//!       --export-detail   Also export connections and modules per process
+//!       --stdout          Write the CSV export to stdout instead of a file
+//!       --csv-columns <LIST>  CSV columns in order, e.g. pid,name,memory
+//!       --csv-encoding <ENC>  CSV encoding: utf8, utf8-bom, utf16 [default: utf8-bom]
+//!       --export-dir <PATH>   Directory for exports [default: current directory]
+//!       --export-name <TEMPLATE>  Export filename with {date}, {time}, {count}
+//!       --no-confirm      Kill processes without Y/N confirmation
+//!       --confirm-quit    Ask Y/N before quitting (Ctrl+C twice forces exit)
+//!       --no-session      Don't restore or save the last selection, sort and filter
+//!       --kill-code <N>   Exit code given to killed processes [default: 1]
+//!       --smooth[=ALPHA]  Smooth per-process CPU% (EMA weight, default 0.3)
+//!       --net-stats       Turn on TCP statistics so Net/s counts all connections
+//!       --detail <PID>    Print details of one process and exit
+//!       --format <FMT>    Output format for --detail: text, json [default: text]
+//!       --serve <PORT>    Serve GET /processes as JSON over HTTP (no TUI)
+//!       --bind <ADDR>     Address for --serve [default: 127.0.0.1]
+//!       --highlight-new <SECS>  Highlight processes started within SECS [default: 10]
 //!   -h, --help            Print help
 //!   -V, --version         Print version
 //! ```
@@ -34,6 +62,16 @@
 //! task_manager_cli --tree
 //! ```
 //!
+//! # Exit Codes
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 0 | Success |
+//! | 1 | I/O error (terminal setup, export write) |
+//! | 2 | Invalid command-line arguments |
+//! | 3 | Process snapshot failed |
+//! | 4 | `--detail` PID not found |
+//!
 //! # Controls
 //!
 //! | Key | Action |
@@ -41,25 +79,52 @@
 //! | `q` | Quit |
 //! | `Enter` | View process details |
 //! | `k` | Kill selected process (with confirmation) |
+//! | `h` | Kill and hold: also kill respawns of the same executable for 5s |
+//! | `!` | Toggle kill confirmation |
 //! | `p` | Suspend/Resume selected process |
+//! | `o` | Open executable's folder in Explorer |
+//! | `c` | Copy executable path to clipboard |
 //! | `t` | Toggle tree view (show parent-child hierarchy) |
-//! | `+`/`-` | Raise/lower process priority |
+//! | `w` | Toggle compact columns |
+//! | `v` | Toggle row stripes |
+//! | `z` | Reset per-process CPU average/peak |
+//! | `+`/`-` | Raise/lower process priority (Realtime asks Y/N first) |
+//! | `a` | Set CPU affinity |
+//! | `f` | Toggle efficiency mode |
+//! | `b` | Toggle priority boost |
 //! | `s` | Cycle sort column |
+//! | `S` | Pick sort column from a list |
 //! | `r` | Reverse sort order |
-//! | `/` | Filter by process name |
+//! | `x` | Freeze row order while metrics keep updating |
+//! | `/` | Filter by process name or PID digits (Tab: also path/command line) |
+//! | `Ctrl+F` | Find: highlight matching rows without hiding the rest |
+//! | `F3` / `Shift+F3` | Jump to next/previous find match |
+//! | `u` | Show only current user's processes |
+//! | `n` | Show only processes with TCP/UDP sockets |
+//! | `i` | Show/hide Idle, System and other pseudo-processes |
+//! | `l` | Show, dim or hide idle processes (no CPU, I/O or memory change) |
 //! | `[`/`]` | Slow down/speed up refresh rate |
+//! | `F5` / `Ctrl+R` | Refresh now |
 //! | `↑`/`↓` | Navigate process list |
 //! | `PgUp`/`PgDn` | Scroll by page |
 //! | `Home`/`End` | Jump to start/end |
-//! | `?` | Show help overlay |
+//! | `*` | Pin/unpin process at top |
+//! | `Space` | Mark process for batch kill/suspend/priority/affinity |
+//! | `?` | Show help overlay (type to search) |
+//! | `d` | Toggle disk columns between bytes/s and lifetime bytes |
+//! | `g` | Show a histogram of processes by CPU and memory band |
+//! | `F1` | Show system information |
+//! | `F12` | Toggle the debug line (refresh/render timings, totals) |
 
-mod app;
-mod constants;
-mod ffi;
-mod system;
 mod ui;
 
-use std::io;
+// The library's modules, imported at the crate root so `crate::app` and
+// friends resolve the same way for the UI as inside the library
+use task_manager_cli::{app, constants, system};
+
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::process::ExitCode;
 use std::time::{Duration, Instant};
 
 use crossterm::{
@@ -72,17 +137,60 @@ use crossterm::{
     },
 };
 
-use app::{export_to_csv, parse_args, App, KeyAction, ViewMode};
+use app::{
+    details_to_json, export_details_to_csv, export_to_csv, format_details_report, load_session,
+    parse_args, save_session, serve, write_process_csv,
+    App, KeyAction, OutputFormat, ViewMode,
+};
+use constants::{
+    EXIT_IO_ERROR, EXIT_OK, EXIT_PID_NOT_FOUND, EXIT_SNAPSHOT_FAILED, KILL_HOLD_POLL_MS,
+    MIN_REFRESH_MS,
+};
 use ui::render;
 
-fn main() -> io::Result<()> {
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => ExitCode::from(code),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(EXIT_IO_ERROR)
+        }
+    }
+}
+
+/// Runs the selected mode and returns the process exit code.
+///
+/// # Returns
+/// * `Ok(u8)` - One of the `EXIT_*` codes from `constants`
+/// * `Err(io::Error)` - A terminal or output error (exit code 1)
+fn run() -> io::Result<u8> {
     // Parse command-line arguments
     let args = parse_args();
     let mut app = App::with_args(&args);
     
+    // Handle single-process detail dump (non-interactive)
+    if let Some(pid) = args.detail_pid {
+        return run_detail_mode(&mut app, pid, args.format);
+    }
+    
     // Handle export mode (non-interactive)
     if args.export {
-        return run_export_mode(&mut app);
+        return run_export_mode(&mut app, args.export_detail, args.stdout);
+    }
+    
+    // Handle HTTP endpoint mode (non-interactive, runs until stopped)
+    if let Some(port) = args.serve_port {
+        let addr = SocketAddr::new(args.bind, port);
+        println!("Serving http://{}/processes (Ctrl+C to stop)", addr);
+        serve(&mut app, addr)?;
+        return Ok(EXIT_OK);
+    }
+    
+    // Land near where the previous launch left off
+    if !args.no_session {
+        if let Some(session) = load_session() {
+            app.restore_session(&session, &args);
+        }
     }
     
     // Set up terminal and run main loop
@@ -90,35 +198,108 @@ fn main() -> io::Result<()> {
     let result = run_event_loop(&mut app);
     restore_terminal()?;
     
+    if !args.no_session {
+        // Losing the session is harmless; don't turn it into an error exit
+        let _ = save_session(&app.capture_session());
+    }
+    
     println!("Task Manager closed.");
-    result
+    result.map(|_| EXIT_OK)
 }
 
-/// Runs in export mode: loads processes, exports to CSV, and exits
-fn run_export_mode(app: &mut App) -> io::Result<()> {
-    // Load process data
+/// Loads two samples for the non-interactive modes, so CPU% and rates
+/// cover a real interval.
+///
+/// # Returns
+/// `false` (after printing why) if the process snapshot failed.
+fn load_samples(app: &mut App) -> bool {
+    app.refresh();
+    std::thread::sleep(Duration::from_millis(MIN_REFRESH_MS));
     app.refresh();
+    if app.snapshot_failed {
+        eprintln!("{}", app.error_message.as_deref().unwrap_or("Process snapshot failed"));
+        return false;
+    }
+    true
+}
+
+/// Runs in export mode: loads processes, exports to CSV, and exits.
+/// With `detail`, also writes a second CSV of connections and modules;
+/// with `to_stdout`, writes the CSV to stdout instead of a file.
+fn run_export_mode(app: &mut App, detail: bool, to_stdout: bool) -> io::Result<u8> {
+    if !load_samples(app) {
+        return Ok(EXIT_SNAPSHOT_FAILED);
+    }
     
     // Get the appropriate process list (filtered or all)
-    let processes = if app.filtered_processes.is_empty() && app.filter.is_empty() {
+    let processes = if app.filtered_processes.is_empty() && !app.is_filtered() {
         &app.processes
     } else {
         &app.filtered_processes
     };
     
+    // Piped output carries only the CSV, without the "Exported to" message
+    if to_stdout {
+        let mut stdout = io::stdout().lock();
+        let written = write_process_csv(&mut stdout, processes, &app.csv_columns)
+            .and_then(|_| stdout.flush());
+        // A reader that stops early (e.g. `| more`) is not an error
+        return match written {
+            Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e),
+            _ => Ok(EXIT_OK),
+        };
+    }
+
     // Export to CSV
-    match export_to_csv(processes) {
+    match export_to_csv(processes, &app.csv_columns, &app.export_target) {
         Ok(path) => {
             println!("Exported {} processes to {}", processes.len(), path.display());
-            Ok(())
         }
         Err(e) => {
             eprintln!("Export failed: {}", e);
-            Err(e)
+            return Ok(EXIT_IO_ERROR);
+        }
+    }
+
+    if !detail {
+        return Ok(EXIT_OK);
+    }
+
+    // Connections and modules are queried per process, only on this path
+    match export_details_to_csv(processes, &app.export_target) {
+        Ok(path) => {
+            println!("Exported connections and modules to {}", path.display());
+            Ok(EXIT_OK)
+        }
+        Err(e) => {
+            eprintln!("Detail export failed: {}", e);
+            Ok(EXIT_IO_ERROR)
         }
     }
 }
 
+/// Runs in detail mode: prints one process's details to stdout and exits.
+/// Returns `EXIT_PID_NOT_FOUND` if the PID does not exist.
+fn run_detail_mode(app: &mut App, pid: u32, format: OutputFormat) -> io::Result<u8> {
+    if !load_samples(app) {
+        return Ok(EXIT_SNAPSHOT_FAILED);
+    }
+    
+    let details = match app.process_details(pid) {
+        Some(d) => d,
+        None => {
+            eprintln!("No process with PID {}", pid);
+            return Ok(EXIT_PID_NOT_FOUND);
+        }
+    };
+    
+    match format {
+        OutputFormat::Text => print!("{}", format_details_report(&details, app.units)),
+        OutputFormat::Json => println!("{}", details_to_json(&details)),
+    }
+    Ok(EXIT_OK)
+}
+
 /// Configures the terminal for TUI mode
 fn setup_terminal() -> io::Result<()> {
     crossterm::terminal::enable_raw_mode()?;
@@ -147,17 +328,24 @@ fn run_event_loop(app: &mut App) -> io::Result<()> {
     let mut last_refresh = Instant::now();
     
     // Initial data load
-    app.refresh();
+    refresh_tick(app);
 
     loop {
-        // Render current state
+        // Render current state (timed for the debug line)
+        let render_started = app.debug_line.then(Instant::now);
         render(&mut stdout, app)?;
+        if let Some(started) = render_started {
+            app.render_duration = started.elapsed();
+        }
 
-        // Calculate timeout until next refresh
+        // Calculate timeout until next refresh (sooner while holding a kill)
         let refresh_interval = Duration::from_millis(app.refresh_interval_ms);
-        let timeout = refresh_interval
+        let mut timeout = refresh_interval
             .checked_sub(last_refresh.elapsed())
             .unwrap_or(Duration::ZERO);
+        if app.kill_hold.is_some() {
+            timeout = timeout.min(Duration::from_millis(KILL_HOLD_POLL_MS));
+        }
 
         // Poll for input events
         if event::poll(timeout)? {
@@ -173,21 +361,33 @@ fn run_event_loop(app: &mut App) -> io::Result<()> {
                 // Dispatch to appropriate handler based on current mode
                 let action = dispatch_key_event(app, key_event.code, key_event.modifiers)?;
                 
-                if matches!(action, KeyAction::Exit) {
-                    break;
+                match action {
+                    KeyAction::Exit => break,
+                    KeyAction::Refresh => {
+                        // Refresh now; the next timed refresh is a full interval away
+                        refresh_tick(app);
+                        last_refresh = Instant::now();
+                    }
+                    KeyAction::CopyDetails => {
+                        if let Some(ref details) = app.detail_view_data {
+                            let text = ui::detail_view_text(details, &app.module_view, app.units);
+                            app.copy_detail_text(&text);
+                        }
+                    }
+                    KeyAction::Continue => {}
                 }
             }
         }
 
+        // Kill respawns of a held process, showing the kill right away
+        if app.kill_hold.is_some() && app.enforce_kill_hold() {
+            refresh_tick(app);
+            last_refresh = Instant::now();
+        }
+
         // Time-based refresh
         if last_refresh.elapsed() >= Duration::from_millis(app.refresh_interval_ms) {
-            app.refresh();
-            
-            // Also refresh detail view if active
-            if app.view_mode.is_detail_view() {
-                app.refresh_detail_view();
-            }
-            
+            refresh_tick(app);
             last_refresh = Instant::now();
         }
     }
@@ -195,6 +395,20 @@ fn run_event_loop(app: &mut App) -> io::Result<()> {
     Ok(())
 }
 
+/// Refreshes the process list, and the detail view if it is open.
+///
+/// With the debug line on, records how long it took.
+fn refresh_tick(app: &mut App) {
+    let started = app.debug_line.then(Instant::now);
+    app.refresh();
+    if app.view_mode.is_detail_view() {
+        app.refresh_detail_view();
+    }
+    if let Some(started) = started {
+        app.refresh_duration = started.elapsed();
+    }
+}
+
 /// Dispatches key events to the appropriate handler based on app mode
 fn dispatch_key_event(
     app: &mut App,
@@ -203,10 +417,16 @@ fn dispatch_key_event(
 ) -> io::Result<KeyAction> {
     match app.view_mode {
         ViewMode::Help => Ok(app.handle_help_key(code)),
-        ViewMode::Affinity => Ok(app.handle_affinity_key(code)),
+        ViewMode::Affinity => app.handle_affinity_key(code),
+        ViewMode::SysInfo => Ok(app.handle_sys_info_key(code)),
+        ViewMode::SortPicker => Ok(app.handle_sort_picker_key(code)),
+        ViewMode::Histogram => Ok(app.handle_histogram_key(code)),
         ViewMode::ConfirmKill => Ok(app.handle_confirm_kill_key(code)),
+        ViewMode::ConfirmQuit => Ok(app.handle_confirm_quit_key(code, modifiers)),
+        ViewMode::ConfirmRealtime => Ok(app.handle_confirm_realtime_key(code)),
         ViewMode::DetailView => app.handle_detail_view_key(code),
         ViewMode::FilterInput => Ok(app.handle_filter_key(code)),
+        ViewMode::FindInput => Ok(app.handle_find_key(code)),
         ViewMode::ProcessList => app.handle_normal_key(code, modifiers),
     }
 }
@@ -2,11 +2,17 @@
 //!
 //! This module provides functions to get and set process CPU affinity,
 //! which determines which CPU cores a process is allowed to use.
+//!
+//! On systems with more than 64 logical processors, Windows splits them
+//! into processor groups of up to 64. An affinity mask only covers the
+//! process's own group, so core indices here are relative to that group.
+//! Processes with threads in several groups have no single mask and are
+//! reported as spanning groups instead.
 
-use windows::Win32::Foundation::CloseHandle;
-use windows::Win32::System::SystemInformation::GetSystemInfo;
+use windows::Win32::Foundation::{CloseHandle, HANDLE};
 use windows::Win32::System::Threading::{
-    GetProcessAffinityMask, OpenProcess, SetProcessAffinityMask,
+    GetActiveProcessorCount, GetActiveProcessorGroupCount, GetProcessAffinityMask,
+    GetProcessGroupAffinity, OpenProcess, SetProcessAffinityMask, ALL_PROCESSOR_GROUPS,
     PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SET_INFORMATION,
 };
 
@@ -16,24 +22,56 @@ use super::error::{AffinityError, AffinityResult};
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct CpuAffinity {
-    /// Bitmask of cores the process can use
+    /// Bitmask of cores the process can use (0 if it spans groups)
     pub process_mask: usize,
-    /// Bitmask of cores available on the system
+    /// Bitmask of cores available in the process's group
     pub system_mask: usize,
     /// Number of cores the process can use
     pub allowed_cores: u32,
-    /// Total number of cores on the system
+    /// Total number of cores in the process's group (or on the whole
+    /// system if the process spans groups)
     pub total_cores: u32,
-    /// List of allowed core indices (0-based)
+    /// List of allowed core indices (0-based, relative to the group)
     pub core_list: Vec<u32>,
+    /// Processor groups the process has threads in
+    pub groups: Vec<u16>,
+    /// Number of active processor groups on the system
+    pub system_group_count: u16,
 }
 
 impl CpuAffinity {
     /// Returns a formatted string describing the affinity.
     ///
     /// # Returns
-    /// A human-readable string like "4/8 cores (Cores: 0, 1, 2, 3)"
+    /// A human-readable string like "4/8 cores (Cores: 0, 1, 2, 3)",
+    /// with the group appended on multi-group systems
     pub fn format(&self) -> String {
+        if self.spans_groups() {
+            let groups = self.groups
+                .iter()
+                .map(|g| g.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            return format!("Spans processor groups {} ({} cores)", groups, self.total_cores);
+        }
+
+        let base = self.format_cores();
+        match self.groups.first() {
+            Some(group) if self.system_group_count > 1 => {
+                format!("{} in group {} of {}", base, group, self.system_group_count)
+            }
+            _ => base,
+        }
+    }
+
+    /// Returns true if the process has threads in more than one processor
+    /// group, in which case it has no single affinity mask.
+    pub fn spans_groups(&self) -> bool {
+        self.groups.len() > 1
+    }
+
+    /// Formats the allowed cores within the process's group.
+    fn format_cores(&self) -> String {
         if self.allowed_cores == self.total_cores {
             format!("{}/{} cores (All cores)", self.allowed_cores, self.total_cores)
         } else if self.allowed_cores == 0 {
@@ -51,15 +89,40 @@ impl CpuAffinity {
 
 /// Get the total number of logical processors (cores) on the system.
 ///
+/// Counts processors across all processor groups, so it can exceed 64.
+///
 /// # Returns
 /// The number of logical CPUs available on the system.
 #[must_use]
 pub fn get_system_core_count() -> u32 {
-    unsafe {
-        let mut sys_info = std::mem::zeroed();
-        GetSystemInfo(&mut sys_info);
-        sys_info.dwNumberOfProcessors
+    unsafe { GetActiveProcessorCount(ALL_PROCESSOR_GROUPS) }
+}
+
+/// Get the number of active processor groups on the system.
+///
+/// # Returns
+/// 1 on systems with 64 or fewer logical processors.
+#[must_use]
+pub fn get_processor_group_count() -> u16 {
+    unsafe { GetActiveProcessorGroupCount() }.max(1)
+}
+
+/// Reads the processor groups a process has threads in.
+///
+/// # Returns
+/// Group numbers, or an empty list if the query fails.
+fn get_process_groups(handle: HANDLE) -> Vec<u16> {
+    let mut groups = vec![0u16; get_processor_group_count() as usize];
+    let mut count = groups.len() as u16;
+
+    // SAFETY: `groups` holds `count` elements, the maximum the call can write.
+    let ok = unsafe { GetProcessGroupAffinity(handle, &mut count, groups.as_mut_ptr()) };
+    if !ok.as_bool() {
+        return Vec::new();
     }
+
+    groups.truncate(count as usize);
+    groups
 }
 
 /// Get CPU affinity information for a process.
@@ -97,31 +160,32 @@ pub fn get_process_affinity(pid: u32) -> Option<CpuAffinity> {
             &mut process_mask,
             &mut system_mask,
         );
+        let groups = get_process_groups(handle);
 
         let _ = CloseHandle(handle);
 
+        // Both masks are zero when the process spans processor groups
         if result.is_err() {
             return None;
         }
 
-        // Count bits and build core list
-        let total_cores = get_system_core_count();
-        let mut allowed_cores = 0u32;
-        let mut core_list = Vec::new();
-
-        for i in 0..64 {
-            if (process_mask >> i) & 1 == 1 {
-                allowed_cores += 1;
-                core_list.push(i as u32);
-            }
-        }
+        // Count bits and build core list (masks only cover the process's group)
+        let core_list: Vec<u32> = (0..usize::BITS)
+            .filter(|i| (process_mask >> i) & 1 == 1)
+            .collect();
 
         Some(CpuAffinity {
             process_mask,
             system_mask,
-            allowed_cores,
-            total_cores,
+            allowed_cores: process_mask.count_ones(),
+            total_cores: if groups.len() > 1 {
+                get_system_core_count()
+            } else {
+                system_mask.count_ones()
+            },
             core_list,
+            groups,
+            system_group_count: get_processor_group_count(),
         })
     }
 }
@@ -163,6 +227,12 @@ pub fn set_process_affinity(pid: u32, core_mask: usize) -> AffinityResult<()> {
             return Err(AffinityError::AccessDenied);
         }
 
+        // A single mask cannot describe a process spread across groups
+        if get_process_groups(handle).len() > 1 {
+            let _ = CloseHandle(handle);
+            return Err(AffinityError::SpansProcessorGroups);
+        }
+
         // Get system mask to validate the requested mask
         let mut _process_mask: usize = 0;
         let mut system_mask: usize = 0;
@@ -185,3 +255,44 @@ pub fn set_process_affinity(pid: u32, core_mask: usize) -> AffinityResult<()> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn affinity(process_mask: usize, groups: Vec<u16>, system_group_count: u16) -> CpuAffinity {
+        CpuAffinity {
+            process_mask,
+            system_mask: 0xFF,
+            allowed_cores: process_mask.count_ones(),
+            total_cores: 8,
+            core_list: (0..usize::BITS).filter(|i| (process_mask >> i) & 1 == 1).collect(),
+            groups,
+            system_group_count,
+        }
+    }
+
+    #[test]
+    fn test_format_single_group() {
+        assert_eq!(affinity(0xFF, vec![0], 1).format(), "8/8 cores (All cores)");
+        assert_eq!(affinity(0b101, vec![0], 1).format(), "2/8 cores (Cores: 0, 2)");
+    }
+
+    #[test]
+    fn test_format_multiple_groups() {
+        assert_eq!(
+            affinity(0b11, vec![1], 2).format(),
+            "2/8 cores (Cores: 0, 1) in group 1 of 2"
+        );
+        let spanning = affinity(0, vec![0, 1], 2);
+        assert!(spanning.spans_groups());
+        assert_eq!(spanning.format(), "Spans processor groups 0, 1 (8 cores)");
+    }
+
+    #[test]
+    fn test_own_process_affinity() {
+        let own = get_process_affinity(std::process::id()).expect("Should read own affinity");
+        assert!(own.allowed_cores > 0);
+        assert!(own.total_cores <= get_system_core_count());
+    }
+}
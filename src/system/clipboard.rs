@@ -0,0 +1,64 @@
+//! Clipboard access via the Win32 clipboard API
+//!
+//! This module places Unicode text on the system clipboard so paths and
+//! other details can be pasted into other applications.
+
+use windows::Win32::Foundation::{GlobalFree, HANDLE, HWND};
+use windows::Win32::System::DataExchange::{
+    CloseClipboard, EmptyClipboard, OpenClipboard, SetClipboardData,
+};
+use windows::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+
+use super::error::{ProcessError, ProcessResult};
+
+/// Standard clipboard format for UTF-16 text (avoids pulling in the Ole feature)
+const CF_UNICODETEXT: u32 = 13;
+
+/// Copies text to the system clipboard, replacing its contents.
+///
+/// # Arguments
+/// * `text` - The text to copy
+///
+/// # Returns
+/// * `Ok(())` - Text was placed on the clipboard
+/// * `Err(ProcessError)` - Error if a clipboard call failed
+pub fn copy_to_clipboard(text: &str) -> ProcessResult<()> {
+    let wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+    let byte_len = wide.len() * std::mem::size_of::<u16>();
+
+    let api_error = |api: &'static str, e: windows::core::Error| ProcessError::WinApiError {
+        api,
+        code: e.code().0,
+    };
+
+    // SAFETY: The clipboard is closed on every path after opening, and the
+    // global memory is only written while locked and within its allocation.
+    unsafe {
+        OpenClipboard(HWND::default()).map_err(|e| api_error("OpenClipboard", e))?;
+
+        let result = (|| {
+            EmptyClipboard().map_err(|e| api_error("EmptyClipboard", e))?;
+
+            let hmem = GlobalAlloc(GMEM_MOVEABLE, byte_len)
+                .map_err(|e| api_error("GlobalAlloc", e))?;
+
+            let ptr = GlobalLock(hmem) as *mut u16;
+            if ptr.is_null() {
+                let _ = GlobalFree(hmem);
+                return Err(ProcessError::InvalidHandle);
+            }
+            std::ptr::copy_nonoverlapping(wide.as_ptr(), ptr, wide.len());
+            let _ = GlobalUnlock(hmem);
+
+            // On success the system owns the memory; otherwise we free it
+            if let Err(e) = SetClipboardData(CF_UNICODETEXT, HANDLE(hmem.0)) {
+                let _ = GlobalFree(hmem);
+                return Err(api_error("SetClipboardData", e));
+            }
+            Ok(())
+        })();
+
+        let _ = CloseClipboard();
+        result
+    }
+}
@@ -3,7 +3,9 @@
 //! This module provides functions to calculate CPU usage for the system
 //! and individual processes using GetSystemTimes and GetProcessTimes.
 //! 
-//! CPU usage requires delta measurements between two time points.
+//! CPU usage requires delta measurements between two time points, so the
+//! first reading of the system and of each process only records a baseline
+//! and reports 0.0.
 
 use std::collections::HashMap;
 use windows::Win32::Foundation::{CloseHandle, FILETIME};
@@ -12,6 +14,8 @@ use windows::Win32::System::Threading::{
     PROCESS_QUERY_LIMITED_INFORMATION,
 };
 
+use super::affinity::get_system_core_count;
+
 /// Snapshot of system-wide CPU times
 #[derive(Debug, Clone, Default)]
 pub struct SystemCpuSnapshot {
@@ -32,58 +36,104 @@ pub struct ProcessCpuSnapshot {
     pub user_time: u64,
 }
 
+/// Running CPU usage statistics for one process since tracking began
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CpuStats {
+    /// Highest CPU usage sample, as a percentage
+    pub peak: f64,
+    /// Sum of all samples, for the average
+    total: f64,
+    /// Number of samples recorded
+    pub samples: u32,
+}
+
+impl CpuStats {
+    /// Adds a CPU usage sample
+    fn record(&mut self, sample: f64) {
+        self.peak = self.peak.max(sample);
+        self.total += sample;
+        self.samples += 1;
+    }
+
+    /// Mean CPU usage over all samples, as a percentage (0.0 if none)
+    pub fn average(&self) -> f64 {
+        if self.samples == 0 {
+            0.0
+        } else {
+            self.total / self.samples as f64
+        }
+    }
+}
+
 /// Holds CPU snapshots for calculating deltas
 #[derive(Debug, Clone, Default)]
 #[allow(dead_code)]
 pub struct CpuTracker {
-    /// Previous system CPU snapshot
-    prev_system: SystemCpuSnapshot,
+    /// Previous system CPU snapshot (None until the first sample)
+    prev_system: Option<SystemCpuSnapshot>,
+    /// Total system CPU time elapsed between the last two system samples
+    system_delta: u64,
     /// Previous per-process CPU snapshots (keyed by PID)
     prev_processes: HashMap<u32, ProcessCpuSnapshot>,
+    /// Exponential moving average of per-process CPU usage (keyed by PID)
+    smoothed_processes: HashMap<u32, f64>,
+    /// Peak and average per-process CPU usage (keyed by PID)
+    process_stats: HashMap<u32, CpuStats>,
     /// Number of logical processors
     num_cpus: u32,
 }
 
 impl CpuTracker {
-    /// Creates a new CPU tracker and takes initial measurements.
+    /// Creates a new CPU tracker.
+    ///
+    /// No baseline is taken here: the first `get_system_cpu_usage` call
+    /// records it, so the first interval is a full refresh rather than the
+    /// few microseconds between construction and the first refresh.
     ///
     /// # Returns
     /// A new `CpuTracker` ready to calculate CPU usage.
     pub fn new() -> Self {
-        let num_cpus = get_num_cpus();
-        let mut tracker = Self {
-            prev_system: SystemCpuSnapshot::default(),
+        Self {
+            prev_system: None,
+            system_delta: 0,
             prev_processes: HashMap::new(),
-            num_cpus,
-        };
-        
-        // Take initial snapshot
-        if let Ok(snapshot) = get_system_cpu_snapshot() {
-            tracker.prev_system = snapshot;
+            smoothed_processes: HashMap::new(),
+            process_stats: HashMap::new(),
+            num_cpus: get_system_core_count().max(1),
         }
-        
-        tracker
     }
     
     /// Updates the system snapshot and returns the CPU usage percentage.
     ///
+    /// Must be called once per refresh, before the per-process readings,
+    /// since those are measured against the same system interval.
+    ///
     /// # Returns
-    /// System-wide CPU usage as a percentage (0.0-100.0).
+    /// System-wide CPU usage as a percentage (0.0-100.0). The first call
+    /// only records a baseline and returns 0.0.
     pub fn get_system_cpu_usage(&mut self) -> f64 {
         let current = match get_system_cpu_snapshot() {
             Ok(s) => s,
             Err(_) => return 0.0,
         };
         
-        let idle_delta = current.idle_time.saturating_sub(self.prev_system.idle_time);
-        let kernel_delta = current.kernel_time.saturating_sub(self.prev_system.kernel_time);
-        let user_delta = current.user_time.saturating_sub(self.prev_system.user_time);
+        let prev = match self.prev_system.replace(current.clone()) {
+            Some(prev) => prev,
+            None => {
+                self.system_delta = 0;
+                return 0.0;
+            }
+        };
+        
+        let idle_delta = current.idle_time.saturating_sub(prev.idle_time);
+        let kernel_delta = current.kernel_time.saturating_sub(prev.kernel_time);
+        let user_delta = current.user_time.saturating_sub(prev.user_time);
         
         // Total time = kernel + user (kernel includes idle)
         let total_time = kernel_delta + user_delta;
         let busy_time = total_time.saturating_sub(idle_delta);
         
-        self.prev_system = current;
+        self.system_delta = total_time;
         
         if total_time == 0 {
             return 0.0;
@@ -94,42 +144,94 @@ impl CpuTracker {
     
     /// Gets CPU usage for a specific process as a percentage.
     ///
+    /// Measured over the interval of the last `get_system_cpu_usage` call.
+    ///
     /// # Arguments
     /// * `pid` - The process ID to query
     ///
     /// # Returns
-    /// CPU usage as a percentage (0.0-100.0), or 0.0 if inaccessible.
+    /// CPU usage as a percentage (0.0-100.0), or 0.0 if inaccessible or
+    /// seen for the first time.
     pub fn get_process_cpu_usage(&mut self, pid: u32) -> f64 {
-        let current_system = match get_system_cpu_snapshot() {
-            Ok(s) => s,
-            Err(_) => return 0.0,
-        };
-        
         let current_process = get_process_cpu_snapshot(pid);
         
-        // Calculate deltas
-        let prev_process = self.prev_processes.get(&pid).cloned()
-            .unwrap_or_default();
-        
-        let system_delta = (current_system.kernel_time + current_system.user_time)
-            .saturating_sub(self.prev_system.kernel_time + self.prev_system.user_time);
-        
-        let process_delta = (current_process.kernel_time + current_process.user_time)
-            .saturating_sub(prev_process.kernel_time + prev_process.user_time);
-        
         // Store current snapshot for next calculation
-        self.prev_processes.insert(pid, current_process);
+        let prev_process = match self.prev_processes.insert(pid, current_process.clone()) {
+            Some(prev) => prev,
+            // First sight: lifetime CPU time is not an interval
+            None => return 0.0,
+        };
         
-        if system_delta == 0 {
+        if self.system_delta == 0 {
             return 0.0;
         }
         
+        let process_delta = (current_process.kernel_time + current_process.user_time)
+            .saturating_sub(prev_process.kernel_time + prev_process.user_time);
+        
         // Calculate CPU usage as percentage of total system CPU (0-100%)
         // This matches Windows Task Manager behavior
-        let usage = (process_delta as f64 / system_delta as f64) * 100.0;
+        let usage = (process_delta as f64 / self.system_delta as f64) * 100.0;
         
         // Clamp to reasonable range
-        usage.min(100.0).max(0.0)
+        let usage = usage.min(100.0).max(0.0);
+        self.process_stats.entry(pid).or_default().record(usage);
+        usage
+    }
+    
+    /// Gets CPU usage for a process, smoothed with an exponential moving
+    /// average so that short spikes and dips do not make the value jump.
+    ///
+    /// Takes a new raw sample like `get_process_cpu_usage`, so call only
+    /// one of the two per refresh.
+    ///
+    /// # Arguments
+    /// * `pid` - The process ID to query
+    /// * `alpha` - Weight of the newest sample (0.0-1.0; 1.0 = no smoothing)
+    ///
+    /// # Returns
+    /// Smoothed CPU usage as a percentage (0.0-100.0).
+    pub fn get_process_cpu_usage_smoothed(&mut self, pid: u32, alpha: f64) -> f64 {
+        let sample = self.get_process_cpu_usage(pid);
+        let smoothed = ema(self.smoothed_processes.get(&pid).copied(), sample, alpha);
+        self.smoothed_processes.insert(pid, smoothed);
+        smoothed
+    }
+    
+    /// Gets the total CPU time (kernel + user) a process has consumed.
+    ///
+    /// Reads the snapshot stored by the last `get_process_cpu_usage` call,
+    /// so no extra system call is made.
+    ///
+    /// # Arguments
+    /// * `pid` - The process ID to query
+    ///
+    /// # Returns
+    /// Cumulative CPU time in whole seconds, or 0 if not tracked.
+    pub fn get_process_cpu_time_seconds(&self, pid: u32) -> u64 {
+        self.prev_processes
+            .get(&pid)
+            .map(|s| (s.kernel_time + s.user_time) / FILETIME_TICKS_PER_SECOND)
+            .unwrap_or(0)
+    }
+    
+    /// Gets the peak and average CPU usage of a process.
+    ///
+    /// Built from the raw (unsmoothed) samples of `get_process_cpu_usage`
+    /// since the process was first seen or the last `reset_cpu_stats`.
+    ///
+    /// # Arguments
+    /// * `pid` - The process ID to query
+    ///
+    /// # Returns
+    /// The statistics, all zero if no sample was recorded yet.
+    pub fn get_process_cpu_stats(&self, pid: u32) -> CpuStats {
+        self.process_stats.get(&pid).copied().unwrap_or_default()
+    }
+    
+    /// Forgets all peak and average CPU statistics.
+    pub fn reset_cpu_stats(&mut self) {
+        self.process_stats.clear();
     }
     
     /// Clears tracked processes that no longer exist.
@@ -138,21 +240,44 @@ impl CpuTracker {
     /// * `active_pids` - List of currently active process IDs
     pub fn cleanup_stale_processes(&mut self, active_pids: &[u32]) {
         self.prev_processes.retain(|pid, _| active_pids.contains(pid));
+        self.smoothed_processes.retain(|pid, _| active_pids.contains(pid));
+        self.process_stats.retain(|pid, _| active_pids.contains(pid));
     }
 }
 
-/// Converts a FILETIME to a u64 (100-nanosecond intervals since 1601)
-fn filetime_to_u64(ft: &FILETIME) -> u64 {
-    ((ft.dwHighDateTime as u64) << 32) | (ft.dwLowDateTime as u64)
+/// Blends a new sample into an exponential moving average.
+///
+/// The first sample (no previous average) is taken as-is.
+fn ema(previous: Option<f64>, sample: f64, alpha: f64) -> f64 {
+    match previous {
+        Some(prev) => alpha * sample + (1.0 - alpha) * prev,
+        None => sample,
+    }
 }
 
-/// Gets the number of logical processors
-fn get_num_cpus() -> u32 {
-    // Use environment variable or default to 1
-    std::env::var("NUMBER_OF_PROCESSORS")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(1)
+/// Number of 100-nanosecond FILETIME intervals in one second
+pub(super) const FILETIME_TICKS_PER_SECOND: u64 = 10_000_000;
+
+/// Formats cumulative CPU time as "HH:MM:SS" (hours may exceed 99)
+///
+/// # Arguments
+/// * `seconds` - CPU time in seconds
+///
+/// # Returns
+/// * `String` - Formatted string like "00:00:05" or "123:45:06"
+#[must_use]
+pub fn format_cpu_time(seconds: u64) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
+}
+
+/// Converts a FILETIME to a u64 (100-nanosecond intervals since 1601)
+pub(super) fn filetime_to_u64(ft: &FILETIME) -> u64 {
+    ((ft.dwHighDateTime as u64) << 32) | (ft.dwLowDateTime as u64)
 }
 
 /// Gets a snapshot of system-wide CPU times.
@@ -234,10 +359,42 @@ mod tests {
         assert!(snapshot.kernel_time > 0, "Should have some kernel time");
     }
     
+    #[test]
+    fn test_format_cpu_time() {
+        assert_eq!(format_cpu_time(0), "00:00:00");
+        assert_eq!(format_cpu_time(5), "00:00:05");
+        assert_eq!(format_cpu_time(5025), "01:23:45");
+        assert_eq!(format_cpu_time(445_506), "123:45:06");
+    }
+    
+    #[test]
+    fn test_ema() {
+        assert_eq!(ema(None, 40.0, 0.3), 40.0);
+        assert!((ema(Some(10.0), 40.0, 0.5) - 25.0).abs() < 1e-9);
+        assert_eq!(ema(Some(10.0), 40.0, 1.0), 40.0);
+    }
+    
+    #[test]
+    fn test_cpu_stats() {
+        let mut stats = CpuStats::default();
+        assert_eq!(stats.average(), 0.0);
+        
+        stats.record(10.0);
+        stats.record(90.0);
+        stats.record(20.0);
+        assert_eq!(stats.peak, 90.0);
+        assert_eq!(stats.samples, 3);
+        assert!((stats.average() - 40.0).abs() < 1e-9);
+    }
+    
     #[test]
     fn test_cpu_tracker() {
         let mut tracker = CpuTracker::new();
         
+        // First samples only record a baseline
+        assert_eq!(tracker.get_system_cpu_usage(), 0.0);
+        assert_eq!(tracker.get_process_cpu_usage(std::process::id()), 0.0);
+        
         // Wait a bit to accumulate CPU time
         thread::sleep(Duration::from_millis(100));
         
@@ -245,4 +402,28 @@ mod tests {
         assert!(usage >= 0.0, "CPU usage should be non-negative");
         assert!(usage <= 100.0, "CPU usage should be at most 100%");
     }
+    
+    #[test]
+    fn test_busy_loop_usage() {
+        let mut tracker = CpuTracker::new();
+        let pid = std::process::id();
+        tracker.get_system_cpu_usage();
+        tracker.get_process_cpu_usage(pid);
+        
+        // Keep one core busy for a few scheduler ticks
+        let start = std::time::Instant::now();
+        let mut x: u64 = 0;
+        while start.elapsed() < Duration::from_millis(300) {
+            x = std::hint::black_box(x.wrapping_add(1));
+        }
+        
+        let system = tracker.get_system_cpu_usage();
+        let process = tracker.get_process_cpu_usage(pid);
+        
+        // One busy thread is 100 / num_cpus percent of the machine
+        let one_core = 100.0 / tracker.num_cpus as f64;
+        assert!(process >= one_core * 0.5, "process {:.1}% below one core {:.1}%", process, one_core);
+        assert!(process <= 100.0);
+        assert!(system + 1.0 >= process, "system {:.1}% below process {:.1}%", system, process);
+    }
 }
@@ -0,0 +1,58 @@
+//! Debugger attachment detection
+//!
+//! `CheckRemoteDebuggerPresent` reports whether a user-mode debugger is
+//! attached to another process. It needs `PROCESS_QUERY_INFORMATION`, so
+//! protected and elevated processes are unknown to unelevated callers.
+
+use windows::Win32::Foundation::BOOL;
+use windows::Win32::System::Diagnostics::Debug::CheckRemoteDebuggerPresent;
+use windows::Win32::System::Threading::PROCESS_QUERY_INFORMATION;
+
+use crate::ffi::ProcessHandle;
+
+/// Checks whether a debugger is attached to a process.
+///
+/// # Arguments
+/// * `pid` - The process ID to query
+///
+/// # Returns
+/// * `Option<bool>` - Whether a debugger is attached, or None if inaccessible
+#[must_use]
+pub fn is_being_debugged(pid: u32) -> Option<bool> {
+    ProcessHandle::open(pid, PROCESS_QUERY_INFORMATION)
+        .ok()
+        .and_then(|process| is_being_debugged_with(&process))
+}
+
+/// Checks for an attached debugger through an open process handle.
+///
+/// # Arguments
+/// * `process` - Handle with `PROCESS_QUERY_INFORMATION` rights
+///
+/// # Returns
+/// * `Option<bool>` - Whether a debugger is attached, or None if the
+///   handle lacks the rights
+#[must_use]
+pub fn is_being_debugged_with(process: &ProcessHandle) -> Option<bool> {
+    if process.pid() == 0 || process.pid() == 4 {
+        return None;
+    }
+
+    let mut present = BOOL::default();
+    // SAFETY: present is a valid out-parameter for the call
+    let result = unsafe { CheckRemoteDebuggerPresent(process.as_raw(), &mut present) };
+
+    result.ok().map(|_| present.as_bool())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_own_process_not_debugged() {
+        // Test binaries run without a debugger attached
+        assert_eq!(is_being_debugged(std::process::id()), Some(false));
+        assert_eq!(is_being_debugged(4), None);
+    }
+}
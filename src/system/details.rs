@@ -5,16 +5,20 @@
 //! - Command line arguments  
 //! - Network connections
 
-use std::ffi::OsString;
+use std::ffi::{c_void, OsString};
 use std::mem;
 use std::os::windows::ffi::OsStringExt;
 
-use windows::Win32::Foundation::{CloseHandle, MAX_PATH, HMODULE};
+use windows::Win32::Foundation::{
+    CloseHandle, MAX_PATH, HMODULE, NTSTATUS, STATUS_INVALID_INFO_CLASS, STATUS_SUCCESS, UNICODE_STRING,
+};
+use windows::Win32::System::Diagnostics::Debug::ReadProcessMemory;
 use windows::Win32::System::ProcessStatus::{
     EnumProcessModules, GetModuleBaseNameW, GetModuleFileNameExW,
 };
 use windows::Win32::System::Threading::{
-    OpenProcess, PROCESS_QUERY_INFORMATION, PROCESS_VM_READ,
+    OpenProcess, PEB, PROCESS_BASIC_INFORMATION, PROCESS_QUERY_INFORMATION,
+    PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_VM_READ, RTL_USER_PROCESS_PARAMETERS,
 };
 use windows::Win32::NetworkManagement::IpHelper::{
     GetExtendedTcpTable, GetExtendedUdpTable,
@@ -22,6 +26,15 @@ use windows::Win32::NetworkManagement::IpHelper::{
 };
 use windows::Win32::Networking::WinSock::AF_INET;
 
+use crate::ffi::ProcessHandle;
+
+use super::ntdll::{
+    get_nt_query_information_process, NtQueryInformationProcess,
+    PROCESS_BASIC_INFORMATION_CLASS, PROCESS_COMMAND_LINE_INFORMATION_CLASS,
+};
+use super::signature::SignatureInfo;
+use super::threads::ThreadCpuTimes;
+
 /// Information about a loaded module/DLL
 #[derive(Debug, Clone)]
 pub struct ModuleInfo {
@@ -68,6 +81,22 @@ pub struct ProcessDetails {
     pub path: Option<String>,
     /// Command line (if accessible)
     pub command_line: Option<String>,
+    /// Names of services hosted by this process
+    pub services: Vec<String>,
+    /// Whether the process is 32-bit under WOW64 (None if inaccessible)
+    pub is_32bit: Option<bool>,
+    /// Whether a debugger is attached (None if inaccessible)
+    pub being_debugged: Option<bool>,
+    /// Whether efficiency mode (EcoQoS) is on (None if inaccessible)
+    pub efficiency_mode: Option<bool>,
+    /// Whether dynamic priority boost is enabled (None if inaccessible)
+    pub priority_boost: Option<bool>,
+    /// Package family name of a Store/UWP app (None for classic processes)
+    pub package_family: Option<String>,
+    /// Application User Model ID of a Store/UWP app (None for classic processes)
+    pub app_user_model_id: Option<String>,
+    /// Terminal Services session ID (0 = services)
+    pub session_id: Option<u32>,
     /// Loaded modules/DLLs
     pub modules: Vec<ModuleInfo>,
     /// TCP connections owned by this process
@@ -76,22 +105,48 @@ pub struct ProcessDetails {
     pub udp_endpoints: Vec<UdpEndpointInfo>,
     /// CPU percentage
     pub cpu_percent: f64,
+    /// Average CPU percentage since first seen (or last reset)
+    pub cpu_avg_percent: f64,
+    /// Peak CPU percentage since first seen (or last reset)
+    pub cpu_peak_percent: f64,
+    /// Total CPU time consumed, in seconds
+    pub cpu_time_seconds: u64,
     /// Memory in bytes
     pub memory_bytes: u64,
+    /// Memory as a percentage of physical memory
+    pub mem_percent: f64,
+    /// Memory counters could not be read
+    pub memory_access_denied: bool,
+    /// CPU, CPU time and handle count could not be read
+    pub query_access_denied: bool,
     /// Thread count
     pub thread_count: u32,
     /// Handle count
     pub handle_count: u32,
     /// Priority
     pub priority: String,
+    /// Base priority (numeric scheduling priority from the snapshot)
+    pub base_priority: i32,
     /// Uptime
     pub uptime_seconds: u64,
     /// Disk read rate
     pub disk_read_rate: f64,
     /// Disk write rate
     pub disk_write_rate: f64,
+    /// Network (TCP) receive rate
+    pub net_recv_rate: f64,
+    /// Network (TCP) send rate
+    pub net_send_rate: f64,
     /// CPU affinity (which cores can be used)
     pub cpu_affinity: Option<String>,
+    /// I/O priority hint (e.g. "Normal")
+    pub io_priority: Option<String>,
+    /// Protection signer of a protected (PP/PPL) process, None if unprotected
+    pub protection: Option<String>,
+    /// Summed CPU time of the live threads (None if unreadable)
+    pub thread_cpu: Option<ThreadCpuTimes>,
+    /// Code signature of the executable (None if the path is unknown or unreadable)
+    pub signature: Option<SignatureInfo>,
 }
 
 /// Get loaded modules for a process
@@ -328,14 +383,145 @@ fn format_ipv4(addr: u32) -> String {
     format!("{}.{}.{}.{}", bytes[0], bytes[1], bytes[2], bytes[3])
 }
 
-/// Get command line for a process (simplified - returns path as fallback)
+/// Gets the command line a process was started with.
+///
+/// `ProcessCommandLineInformation` (Windows 8.1+) has the kernel copy
+/// `RTL_USER_PROCESS_PARAMETERS.CommandLine` out of the target's PEB,
+/// whatever its bitness, with only limited query rights. Older systems
+/// reject that class, so the PEB is read directly instead.
+///
+/// # Arguments
+/// * `pid` - The process ID to query
+///
+/// # Returns
+/// * `Option<String>` - The command line, or None if inaccessible
 #[must_use]
 pub fn get_process_command_line(pid: u32) -> Option<String> {
-    // Getting the actual command line requires reading the PEB from the process
-    // which is complex. For now, we'll use the executable path.
-    // A full implementation would use NtQueryInformationProcess with ProcessBasicInformation
-    // then read RTL_USER_PROCESS_PARAMETERS from the PEB.
-    super::get_process_path(pid)
+    if pid == 0 || pid == 4 {
+        return None;
+    }
+
+    let nt_query = get_nt_query_information_process()?;
+    let process = ProcessHandle::open(pid, PROCESS_QUERY_LIMITED_INFORMATION).ok()?;
+
+    match query_command_line(nt_query, &process) {
+        Ok(command_line) => Some(command_line),
+        Err(status) if status == STATUS_INVALID_INFO_CLASS => read_peb_command_line(nt_query, pid),
+        Err(_) => None,
+    }
+}
+
+/// Reads a command line with `ProcessCommandLineInformation`.
+///
+/// # Returns
+/// * `Ok(String)` - The command line
+/// * `Err(NTSTATUS)` - The failing status (`STATUS_INVALID_INFO_CLASS`
+///   before Windows 8.1)
+fn query_command_line(
+    nt_query: NtQueryInformationProcess,
+    process: &ProcessHandle,
+) -> Result<String, NTSTATUS> {
+    // SAFETY: The first call only reports the size. The second fills a
+    // buffer of that size, aligned for the UNICODE_STRING at its start,
+    // whose Buffer points into the same allocation.
+    unsafe {
+        let mut needed: u32 = 0;
+        let _ = nt_query(
+            process.as_raw(),
+            PROCESS_COMMAND_LINE_INFORMATION_CLASS,
+            std::ptr::null_mut(),
+            0,
+            &mut needed,
+        );
+        if needed == 0 {
+            needed = mem::size_of::<UNICODE_STRING>() as u32;
+        }
+
+        let mut buffer = vec![0u64; (needed as usize).div_ceil(8)];
+        let status = nt_query(
+            process.as_raw(),
+            PROCESS_COMMAND_LINE_INFORMATION_CLASS,
+            buffer.as_mut_ptr() as *mut c_void,
+            (buffer.len() * 8) as u32,
+            &mut needed,
+        );
+        if status != STATUS_SUCCESS {
+            return Err(status);
+        }
+
+        let text = &*(buffer.as_ptr() as *const UNICODE_STRING);
+        if text.Buffer.is_null() {
+            return Ok(String::new());
+        }
+        let chars = std::slice::from_raw_parts(text.Buffer.0, text.Length as usize / 2);
+        Ok(String::from_utf16_lossy(chars))
+    }
+}
+
+/// Reads a command line from the process's PEB with `ReadProcessMemory`.
+///
+/// Follows `PEB.ProcessParameters` to `RTL_USER_PROCESS_PARAMETERS` using
+/// this build's pointer size, which matches the native PEB that every
+/// process on a 64-bit OS has (WOW64 ones included) when this is a 64-bit
+/// build.
+fn read_peb_command_line(nt_query: NtQueryInformationProcess, pid: u32) -> Option<String> {
+    let process = ProcessHandle::open(pid, PROCESS_QUERY_LIMITED_INFORMATION | PROCESS_VM_READ).ok()?;
+
+    let mut basic = PROCESS_BASIC_INFORMATION::default();
+    // SAFETY: basic is plain-old-data and sized correctly
+    let status = unsafe {
+        nt_query(
+            process.as_raw(),
+            PROCESS_BASIC_INFORMATION_CLASS,
+            &mut basic as *mut _ as *mut c_void,
+            mem::size_of::<PROCESS_BASIC_INFORMATION>() as u32,
+            std::ptr::null_mut(),
+        )
+    };
+    if status != STATUS_SUCCESS || basic.PebBaseAddress.is_null() {
+        return None;
+    }
+
+    let peb: PEB = read_remote(&process, basic.PebBaseAddress)?;
+    let params: RTL_USER_PROCESS_PARAMETERS = read_remote(&process, peb.ProcessParameters)?;
+    let text = params.CommandLine;
+    if text.Buffer.is_null() {
+        return None;
+    }
+
+    let mut chars = vec![0u16; text.Length as usize / 2];
+    // SAFETY: chars is writable for exactly the bytes requested
+    unsafe {
+        ReadProcessMemory(
+            process.as_raw(),
+            text.Buffer.0 as *const c_void,
+            chars.as_mut_ptr() as *mut c_void,
+            chars.len() * 2,
+            None,
+        )
+    }
+    .ok()?;
+    Some(String::from_utf16_lossy(&chars))
+}
+
+/// Copies a plain-old-data struct out of another process's memory.
+fn read_remote<T: Default>(process: &ProcessHandle, address: *const T) -> Option<T> {
+    if address.is_null() {
+        return None;
+    }
+    let mut value = T::default();
+    // SAFETY: value is writable for size_of::<T>() bytes
+    unsafe {
+        ReadProcessMemory(
+            process.as_raw(),
+            address as *const c_void,
+            &mut value as *mut T as *mut c_void,
+            mem::size_of::<T>(),
+            None,
+        )
+    }
+    .ok()?;
+    Some(value)
 }
 
 #[cfg(test)]
@@ -348,6 +534,20 @@ mod tests {
         assert_eq!(format_ipv4(0), "0.0.0.0");
     }
 
+    #[test]
+    fn test_own_command_line() {
+        // The command line names our executable, not just its path
+        let exe = std::env::current_exe().unwrap();
+        let name = exe.file_name().unwrap().to_string_lossy().into_owned();
+        let command_line = get_process_command_line(std::process::id()).unwrap();
+        assert!(command_line.contains(&name));
+
+        // The PEB fallback reads the same string
+        let nt_query = get_nt_query_information_process().unwrap();
+        assert_eq!(read_peb_command_line(nt_query, std::process::id()), Some(command_line));
+        assert_eq!(get_process_command_line(0), None);
+    }
+
     #[test]
     fn test_get_current_process_modules() {
         let pid = std::process::id();
@@ -3,12 +3,12 @@
 //! This module provides functions to query per-process disk I/O
 //! using GetProcessIoCounters.
 
-use windows::Win32::Foundation::CloseHandle;
 use windows::Win32::System::Threading::{
-    GetProcessIoCounters, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
-    IO_COUNTERS,
+    GetProcessIoCounters, PROCESS_QUERY_LIMITED_INFORMATION, IO_COUNTERS,
 };
 
+use crate::ffi::ProcessHandle;
+
 /// Per-process disk I/O statistics
 #[derive(Debug, Clone, Default)]
 #[allow(dead_code)]
@@ -42,27 +42,24 @@ impl ProcessDiskInfo {
 /// # Returns
 /// * `ProcessDiskInfo` - Disk I/O statistics (zeros if access denied)
 pub fn get_process_disk_info(pid: u32) -> ProcessDiskInfo {
-    // Try to open the process with limited query rights
-    // SAFETY: OpenProcess is safe to call with valid parameters.
-    let handle = unsafe {
-        OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid)
-    };
-
-    let handle = match handle {
-        Ok(h) => h,
-        Err(_) => return ProcessDiskInfo::default(), // Access denied or process gone
-    };
+    match ProcessHandle::open(pid, PROCESS_QUERY_LIMITED_INFORMATION) {
+        Ok(process) => get_process_disk_info_with(&process),
+        Err(_) => ProcessDiskInfo::default(), // Access denied or process gone
+    }
+}
 
+/// Gets disk I/O information through an open process handle.
+///
+/// # Arguments
+/// * `process` - Handle with at least limited query rights
+///
+/// # Returns
+/// * `ProcessDiskInfo` - Disk I/O statistics (zeros if the query fails)
+pub fn get_process_disk_info_with(process: &ProcessHandle) -> ProcessDiskInfo {
     let mut io_counters = IO_COUNTERS::default();
 
     // SAFETY: GetProcessIoCounters is safe with a valid handle and initialized struct.
-    let result = unsafe { GetProcessIoCounters(handle, &mut io_counters) };
-
-    // Always close the handle
-    // SAFETY: We own this handle and it's valid.
-    unsafe {
-        let _ = CloseHandle(handle);
-    }
+    let result = unsafe { GetProcessIoCounters(process.as_raw(), &mut io_counters) };
 
     if result.is_ok() {
         ProcessDiskInfo {
@@ -0,0 +1,202 @@
+//! Efficiency mode (EcoQoS) and dynamic priority boost
+//!
+//! Efficiency mode is execution-speed power throttling, set with
+//! `SetProcessInformation(ProcessPowerThrottling)`; Windows 11 schedules
+//! throttled processes on efficient cores at lower clocks. Priority boost
+//! is the short-lived priority raise Windows gives threads that wake from
+//! a wait, on by default for every process.
+
+use std::ffi::c_void;
+
+use windows::Win32::Foundation::{CloseHandle, BOOL, HANDLE};
+use windows::Win32::System::Threading::{
+    GetProcessInformation, GetProcessPriorityBoost, OpenProcess, ProcessPowerThrottling,
+    SetProcessInformation, SetProcessPriorityBoost, PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+    PROCESS_POWER_THROTTLING_EXECUTION_SPEED, PROCESS_POWER_THROTTLING_STATE,
+    PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SET_INFORMATION,
+};
+
+use super::error::{ProcessError, ProcessResult};
+use crate::ffi::ProcessHandle;
+
+/// Size of the throttling state passed to Get/SetProcessInformation
+const THROTTLING_STATE_SIZE: u32 = std::mem::size_of::<PROCESS_POWER_THROTTLING_STATE>() as u32;
+
+/// Opens a process for changing its information.
+fn open_for_set(pid: u32) -> ProcessResult<HANDLE> {
+    if pid == 0 || pid == 4 {
+        return Err(ProcessError::SystemProcess);
+    }
+    // SAFETY: OpenProcess is safe with valid parameters
+    unsafe { OpenProcess(PROCESS_SET_INFORMATION, false, pid) }
+        .map_err(|_| ProcessError::AccessDenied)
+}
+
+/// Checks whether a process runs in efficiency mode.
+///
+/// # Arguments
+/// * `pid` - The process ID to query
+///
+/// # Returns
+/// * `Option<bool>` - Whether execution-speed throttling is on, or None if
+///   the process cannot be queried (or Windows predates the setting)
+#[must_use]
+pub fn get_efficiency_mode(pid: u32) -> Option<bool> {
+    ProcessHandle::open(pid, PROCESS_QUERY_LIMITED_INFORMATION)
+        .ok()
+        .and_then(|process| get_efficiency_mode_with(&process))
+}
+
+/// Checks for efficiency mode through an open process handle.
+///
+/// # Arguments
+/// * `process` - Handle with at least limited query rights
+///
+/// # Returns
+/// * `Option<bool>` - Whether execution-speed throttling is on, or None if
+///   the query fails (or Windows predates the setting)
+#[must_use]
+pub fn get_efficiency_mode_with(process: &ProcessHandle) -> Option<bool> {
+    if process.pid() == 0 || process.pid() == 4 {
+        return None;
+    }
+
+    let mut state = PROCESS_POWER_THROTTLING_STATE {
+        Version: PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+        ..Default::default()
+    };
+    // SAFETY: state is plain-old-data and sized correctly
+    let result = unsafe {
+        GetProcessInformation(
+            process.as_raw(),
+            ProcessPowerThrottling,
+            &mut state as *mut _ as *mut c_void,
+            THROTTLING_STATE_SIZE,
+        )
+    };
+
+    result
+        .ok()
+        .map(|_| state.StateMask & PROCESS_POWER_THROTTLING_EXECUTION_SPEED != 0)
+}
+
+/// Turns efficiency mode on or off for a process.
+///
+/// Turning it off sets an explicit "not throttled" state rather than
+/// handing the decision back to Windows' heuristics.
+///
+/// # Arguments
+/// * `pid` - The process ID to modify
+/// * `enable` - Whether to throttle the process
+///
+/// # Returns
+/// * `Ok(())` - The setting was changed
+/// * `Err(ProcessError)` - Why it could not be changed
+pub fn set_efficiency_mode(pid: u32, enable: bool) -> ProcessResult<()> {
+    let handle = open_for_set(pid)?;
+
+    let state = PROCESS_POWER_THROTTLING_STATE {
+        Version: PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+        ControlMask: PROCESS_POWER_THROTTLING_EXECUTION_SPEED,
+        StateMask: if enable { PROCESS_POWER_THROTTLING_EXECUTION_SPEED } else { 0 },
+    };
+    // SAFETY: state is plain-old-data and sized correctly
+    let result = unsafe {
+        SetProcessInformation(
+            handle,
+            ProcessPowerThrottling,
+            &state as *const _ as *const c_void,
+            THROTTLING_STATE_SIZE,
+        )
+    };
+
+    // Always close the handle
+    unsafe {
+        let _ = CloseHandle(handle);
+    }
+
+    result.map_err(|e| ProcessError::WinApiError { api: "SetProcessInformation", code: e.code().0 })
+}
+
+/// Checks whether dynamic priority boost is enabled for a process.
+///
+/// # Arguments
+/// * `pid` - The process ID to query
+///
+/// # Returns
+/// * `Option<bool>` - Whether boosting is enabled, or None if inaccessible
+#[must_use]
+pub fn get_priority_boost(pid: u32) -> Option<bool> {
+    if pid == 0 || pid == 4 {
+        return None;
+    }
+
+    // SAFETY: OpenProcess is safe with valid parameters
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) }.ok()?;
+
+    let mut disabled = BOOL::default();
+    // SAFETY: disabled is a valid out-parameter for the call
+    let result = unsafe { GetProcessPriorityBoost(handle, &mut disabled) };
+
+    // Always close the handle
+    unsafe {
+        let _ = CloseHandle(handle);
+    }
+
+    result.ok().map(|_| !disabled.as_bool())
+}
+
+/// Enables or disables dynamic priority boost for a process.
+///
+/// # Arguments
+/// * `pid` - The process ID to modify
+/// * `enable` - Whether Windows may boost the process's threads
+///
+/// # Returns
+/// * `Ok(())` - The setting was changed
+/// * `Err(ProcessError)` - Why it could not be changed
+pub fn set_priority_boost(pid: u32, enable: bool) -> ProcessResult<()> {
+    let handle = open_for_set(pid)?;
+
+    // SAFETY: handle was opened with PROCESS_SET_INFORMATION
+    let result = unsafe { SetProcessPriorityBoost(handle, BOOL::from(!enable)) };
+
+    // Always close the handle
+    unsafe {
+        let _ = CloseHandle(handle);
+    }
+
+    result.map_err(|e| ProcessError::WinApiError { api: "SetProcessPriorityBoost", code: e.code().0 })
+}
+
+/// Describes an on/off setting for display.
+///
+/// # Arguments
+/// * `value` - The setting, or None if it could not be read
+#[must_use]
+pub fn format_on_off(value: Option<bool>) -> &'static str {
+    match value {
+        Some(true) => "On",
+        Some(false) => "Off",
+        None => "Unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_own_process_defaults() {
+        let pid = std::process::id();
+        assert_eq!(get_priority_boost(pid), Some(true));
+        assert_ne!(get_efficiency_mode(pid), Some(true));
+    }
+
+    #[test]
+    fn test_system_process_rejected() {
+        assert_eq!(set_efficiency_mode(4, true), Err(ProcessError::SystemProcess));
+        assert_eq!(set_priority_boost(0, false), Err(ProcessError::SystemProcess));
+        assert_eq!(get_priority_boost(4), None);
+    }
+}
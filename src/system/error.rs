@@ -52,6 +52,12 @@ pub enum ProcessError {
         /// Description of what was invalid
         reason: &'static str,
     },
+    
+    /// Process is protected (PPL) and rejects termination even when elevated
+    Protected,
+    
+    /// Process is critical; terminating it would crash Windows
+    Critical,
 }
 
 impl fmt::Display for ProcessError {
@@ -81,6 +87,12 @@ impl fmt::Display for ProcessError {
             ProcessError::InvalidParameter { reason } => {
                 write!(f, "Invalid parameter: {}", reason)
             }
+            ProcessError::Protected => {
+                write!(f, "Cannot terminate a protected process (PPL), even as Administrator")
+            }
+            ProcessError::Critical => {
+                write!(f, "Cannot terminate a critical system process")
+            }
         }
     }
 }
@@ -111,6 +123,9 @@ pub enum AffinityError {
     
     /// Selected cores are not available on this system
     InvalidCoreSelection,
+    
+    /// Process has threads in several processor groups
+    SpansProcessorGroups,
 }
 
 impl fmt::Display for AffinityError {
@@ -134,6 +149,9 @@ impl fmt::Display for AffinityError {
             AffinityError::InvalidCoreSelection => {
                 write!(f, "Invalid core selection")
             }
+            AffinityError::SpansProcessorGroups => {
+                write!(f, "Process spans multiple processor groups")
+            }
         }
     }
 }
@@ -0,0 +1,179 @@
+//! Per-process I/O priority
+//!
+//! Windows schedules disk and network I/O by a priority hint separate from
+//! the CPU priority class. It is only exposed through the native
+//! `NtQueryInformationProcess` / `NtSetInformationProcess` calls with the
+//! `ProcessIoPriority` information class (see `ntdll`).
+
+use std::ffi::c_void;
+
+use windows::Win32::Foundation::{CloseHandle, STATUS_SUCCESS};
+use windows::Win32::System::Threading::{
+    OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SET_INFORMATION,
+};
+
+use super::error::{ProcessError, ProcessResult};
+use super::ntdll::{
+    get_nt_query_information_process, get_nt_set_information_process, PROCESS_IO_PRIORITY_CLASS,
+};
+
+/// Windows I/O priority hints.
+///
+/// # Levels (lowest to highest)
+/// * `VeryLow` - Background I/O, e.g. indexing and defrag
+/// * `Low` - Low-importance foreground I/O
+/// * `Normal` - Default for all processes
+/// * `High` - Reserved for the system; cannot be set from user mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IoPriority {
+    VeryLow = 0,
+    Low = 1,
+    Normal = 2,
+    High = 3,
+}
+
+impl IoPriority {
+    /// Convert from the raw `IO_PRIORITY_HINT` value
+    pub fn from_raw(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(IoPriority::VeryLow),
+            1 => Some(IoPriority::Low),
+            2 => Some(IoPriority::Normal),
+            3 => Some(IoPriority::High),
+            _ => None,
+        }
+    }
+
+    /// Get the display name
+    pub fn name(self) -> &'static str {
+        match self {
+            IoPriority::VeryLow => "Very Low",
+            IoPriority::Low => "Low",
+            IoPriority::Normal => "Normal",
+            IoPriority::High => "High",
+        }
+    }
+
+    /// Get the next level in the cycle of settable priorities
+    /// (Very Low → Low → Normal → Very Low)
+    pub fn cycle(self) -> Self {
+        match self {
+            IoPriority::VeryLow => IoPriority::Low,
+            IoPriority::Low => IoPriority::Normal,
+            IoPriority::Normal | IoPriority::High => IoPriority::VeryLow,
+        }
+    }
+}
+
+/// Reads the I/O priority of a process.
+///
+/// # Arguments
+/// * `pid` - The process ID to query
+///
+/// # Returns
+/// * `Option<IoPriority>` - None if the process cannot be opened or queried
+#[must_use]
+pub fn query_process_io_priority(pid: u32) -> Option<IoPriority> {
+    if pid == 0 || pid == 4 {
+        return None;
+    }
+
+    let nt_query = get_nt_query_information_process()?;
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+
+        let mut value: u32 = 0;
+        let mut returned: u32 = 0;
+        let status = nt_query(
+            handle,
+            PROCESS_IO_PRIORITY_CLASS,
+            &mut value as *mut u32 as *mut c_void,
+            std::mem::size_of::<u32>() as u32,
+            &mut returned,
+        );
+        let _ = CloseHandle(handle);
+
+        if status == STATUS_SUCCESS {
+            IoPriority::from_raw(value)
+        } else {
+            None
+        }
+    }
+}
+
+/// Gets the I/O priority of a process for display.
+///
+/// # Arguments
+/// * `pid` - The process ID to query
+///
+/// # Returns
+/// * `Option<String>` - e.g. "Normal", or None if it cannot be read
+#[must_use]
+pub fn get_process_io_priority(pid: u32) -> Option<String> {
+    query_process_io_priority(pid).map(|p| p.name().to_string())
+}
+
+/// Sets the I/O priority of a process.
+///
+/// # Arguments
+/// * `pid` - The process ID to modify
+/// * `priority` - The new I/O priority (High is rejected by Windows)
+///
+/// # Returns
+/// * `Ok(())` - The priority was changed
+/// * `Err(ProcessError)` - Why it could not be changed
+pub fn set_process_io_priority(pid: u32, priority: IoPriority) -> ProcessResult<()> {
+    if pid == 0 || pid == 4 {
+        return Err(ProcessError::SystemProcess);
+    }
+
+    let nt_set = get_nt_set_information_process()
+        .ok_or(ProcessError::NtdllLoadFailed { function: "NtSetInformationProcess" })?;
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_SET_INFORMATION, false, pid)
+            .map_err(|_| ProcessError::AccessDenied)?;
+
+        let value = priority as u32;
+        let status = nt_set(
+            handle,
+            PROCESS_IO_PRIORITY_CLASS,
+            &value as *const u32 as *const c_void,
+            std::mem::size_of::<u32>() as u32,
+        );
+        let _ = CloseHandle(handle);
+
+        if status == STATUS_SUCCESS {
+            Ok(())
+        } else {
+            Err(ProcessError::WinApiError { api: "NtSetInformationProcess", code: status.0 })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_own_process_normal() {
+        assert_eq!(query_process_io_priority(std::process::id()), Some(IoPriority::Normal));
+    }
+
+    #[test]
+    fn test_cycle_skips_high() {
+        assert_eq!(IoPriority::VeryLow.cycle(), IoPriority::Low);
+        assert_eq!(IoPriority::Low.cycle(), IoPriority::Normal);
+        assert_eq!(IoPriority::Normal.cycle(), IoPriority::VeryLow);
+        assert_eq!(IoPriority::High.cycle(), IoPriority::VeryLow);
+    }
+
+    #[test]
+    fn test_system_process_rejected() {
+        assert_eq!(
+            set_process_io_priority(4, IoPriority::Low),
+            Err(ProcessError::SystemProcess)
+        );
+    }
+}
@@ -11,12 +11,13 @@ use windows::Win32::System::SystemInformation::{
     GlobalMemoryStatusEx, MEMORYSTATUSEX,
 };
 use windows::Win32::System::Threading::{
-    OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_VM_READ,
+    PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_VM_READ,
 };
-use windows::Win32::Foundation::CloseHandle;
+
+use crate::ffi::ProcessHandle;
 
 /// System-wide memory statistics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 #[allow(dead_code)]
 pub struct SystemMemoryInfo {
     /// Percentage of physical memory in use (0-100)
@@ -43,6 +44,21 @@ impl SystemMemoryInfo {
         self.total_physical - self.available_physical
     }
 
+    /// Returns system commit charge as a percentage of the commit limit.
+    ///
+    /// The page-file fields of `MEMORYSTATUSEX` are really the commit
+    /// limit (RAM plus page files) and how much of it is still free.
+    ///
+    /// # Returns
+    /// Percent of the commit limit in use (0.0 if the limit is unknown).
+    pub fn commit_percent(&self) -> f64 {
+        if self.total_page_file == 0 {
+            return 0.0;
+        }
+        let used = self.total_page_file.saturating_sub(self.available_page_file);
+        used as f64 * 100.0 / self.total_page_file as f64
+    }
+
     /// Returns used memory as a formatted string.
     ///
     /// # Returns
@@ -68,6 +84,8 @@ pub struct ProcessMemoryInfo {
     pub peak_working_set: u64,
     /// Private bytes (committed memory)
     pub private_bytes: u64,
+    /// Whether the counters were read (false if access was denied)
+    pub accessible: bool,
 }
 
 impl ProcessMemoryInfo {
@@ -76,8 +94,8 @@ impl ProcessMemoryInfo {
     /// # Returns
     /// A string like "125.4 MB"
     #[allow(dead_code)]
-    pub fn format_working_set(&self) -> String {
-        format_bytes(self.working_set)
+    pub fn format_working_set(&self, units: ByteUnits) -> String {
+        format_bytes(self.working_set, units)
     }
 }
 
@@ -87,21 +105,87 @@ fn bytes_to_gb(bytes: u64) -> f64 {
     bytes as f64 / (1024.0 * 1024.0 * 1024.0)
 }
 
+/// Unit system for displayed byte sizes and rates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ByteUnits {
+    /// Powers of 1024, labeled KiB/MiB/GiB
+    #[default]
+    Binary,
+    /// Powers of 1000, labeled KB/MB/GB (disk-vendor convention)
+    Decimal,
+}
+
+impl ByteUnits {
+    /// Parses a unit system name as given to `--units`.
+    ///
+    /// # Returns
+    /// * `Option<ByteUnits>` - The units, or None if the name is unknown
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "binary" | "iec" => Some(ByteUnits::Binary),
+            "decimal" | "si" => Some(ByteUnits::Decimal),
+            _ => None,
+        }
+    }
+
+    /// Scales a byte count to the largest unit it reaches.
+    ///
+    /// # Arguments
+    /// * `bytes` - Byte count (or bytes per second)
+    ///
+    /// # Returns
+    /// * `Option<(f64, &str)>` - The scaled value and unit label, or None
+    ///   if the value is under one kilobyte
+    pub fn scale(self, bytes: f64) -> Option<(f64, &'static str)> {
+        let (base, labels) = match self {
+            ByteUnits::Binary => (1024.0, ["KiB", "MiB", "GiB"]),
+            ByteUnits::Decimal => (1000.0, ["KB", "MB", "GB"]),
+        };
+        let mut scaled = None;
+        let mut divisor = base;
+        for label in labels {
+            if bytes < divisor {
+                break;
+            }
+            scaled = Some((bytes / divisor, label));
+            divisor *= base;
+        }
+        scaled
+    }
+}
+
 /// Formats bytes into a human-readable string
+///
+/// # Arguments
+/// * `bytes` - Byte count
+/// * `units` - Binary (KiB) or decimal (KB) units
 #[must_use]
-pub fn format_bytes(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-    
-    if bytes >= GB {
-        format!("{:.1} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.1} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.1} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{} B", bytes)
+pub fn format_bytes(bytes: u64, units: ByteUnits) -> String {
+    match units.scale(bytes as f64) {
+        Some((value, label)) => format!("{:.1} {}", value, label),
+        None => format!("{} B", bytes),
+    }
+}
+
+/// Formats a byte rate (bytes/sec) as a human-readable string.
+///
+/// Automatically selects appropriate unit (B/s, KiB/s, MiB/s, GiB/s, or
+/// KB/s, MB/s, GB/s with decimal units).
+///
+/// # Arguments
+/// * `bytes_per_sec` - Transfer rate in bytes per second
+/// * `units` - Binary (KiB) or decimal (KB) units
+///
+/// # Returns
+/// Formatted string with appropriate unit suffix
+#[must_use]
+pub fn format_rate(bytes_per_sec: f64, units: ByteUnits) -> String {
+    if bytes_per_sec < 1.0 {
+        return "0 B/s".to_string();
+    }
+    match units.scale(bytes_per_sec) {
+        Some((value, label)) => format!("{:.1} {}/s", value, label),
+        None => format!("{:.0} B/s", bytes_per_sec),
     }
 }
 
@@ -145,23 +229,24 @@ pub fn get_system_memory_info() -> windows::core::Result<SystemMemoryInfo> {
 /// * `pid` - The process ID to query
 ///
 /// # Returns
-/// * `ProcessMemoryInfo` - Memory statistics (zeros if access denied)
+/// * `ProcessMemoryInfo` - Memory statistics (zeros and `accessible: false`
+///   if access denied)
 pub fn get_process_memory_info(pid: u32) -> ProcessMemoryInfo {
-    // Try to open the process with limited query rights
-    // SAFETY: OpenProcess is safe to call with valid parameters.
-    let handle = unsafe {
-        OpenProcess(
-            PROCESS_QUERY_LIMITED_INFORMATION | PROCESS_VM_READ,
-            false,
-            pid,
-        )
-    };
-    
-    let handle = match handle {
-        Ok(h) => h,
-        Err(_) => return ProcessMemoryInfo::default(), // Access denied or process gone
-    };
-    
+    match ProcessHandle::open(pid, PROCESS_QUERY_LIMITED_INFORMATION | PROCESS_VM_READ) {
+        Ok(process) => get_process_memory_info_with(&process),
+        Err(_) => ProcessMemoryInfo::default(), // Access denied or process gone
+    }
+}
+
+/// Gets memory information through an open process handle.
+///
+/// # Arguments
+/// * `process` - Handle with query and `PROCESS_VM_READ` rights
+///
+/// # Returns
+/// * `ProcessMemoryInfo` - Memory statistics (zeros and `accessible: false`
+///   if the handle lacks the rights)
+pub fn get_process_memory_info_with(process: &ProcessHandle) -> ProcessMemoryInfo {
     // Initialize the counters struct
     let mut counters = PROCESS_MEMORY_COUNTERS {
         cb: mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
@@ -171,23 +256,18 @@ pub fn get_process_memory_info(pid: u32) -> ProcessMemoryInfo {
     // SAFETY: GetProcessMemoryInfo is safe with a valid handle and initialized struct.
     let result = unsafe {
         GetProcessMemoryInfo(
-            handle,
+            process.as_raw(),
             &mut counters,
             mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
         )
     };
     
-    // Always close the handle
-    // SAFETY: We own this handle and it's valid.
-    unsafe {
-        let _ = CloseHandle(handle);
-    }
-    
     if result.is_ok() {
         ProcessMemoryInfo {
             working_set: counters.WorkingSetSize as u64,
             peak_working_set: counters.PeakWorkingSetSize as u64,
             private_bytes: counters.PagefileUsage as u64,
+            accessible: true,
         }
     } else {
         ProcessMemoryInfo::default()
@@ -209,14 +289,39 @@ mod tests {
     fn test_process_memory_info() {
         let pid = std::process::id();
         let info = get_process_memory_info(pid);
+        assert!(info.accessible, "Our own process should be readable");
         assert!(info.working_set > 0, "Our process should use some memory");
     }
+
+    #[test]
+    fn test_process_memory_info_with_shared_handle() {
+        // The handle opened once per refresh must carry PROCESS_VM_READ
+        let process = ProcessHandle::open_for_query(std::process::id()).unwrap();
+        assert!(get_process_memory_info_with(&process).accessible);
+    }
     
+    #[test]
+    fn test_commit_percent() {
+        let info = SystemMemoryInfo {
+            total_page_file: 200,
+            available_page_file: 6,
+            ..Default::default()
+        };
+        assert_eq!(info.commit_percent(), 97.0);
+        assert_eq!(SystemMemoryInfo::default().commit_percent(), 0.0);
+    }
+
     #[test]
     fn test_format_bytes() {
-        assert_eq!(format_bytes(500), "500 B");
-        assert_eq!(format_bytes(1536), "1.5 KB");
-        assert_eq!(format_bytes(1572864), "1.5 MB");
-        assert_eq!(format_bytes(1610612736), "1.5 GB");
+        assert_eq!(format_bytes(500, ByteUnits::Binary), "500 B");
+        assert_eq!(format_bytes(1536, ByteUnits::Binary), "1.5 KiB");
+        assert_eq!(format_bytes(1572864, ByteUnits::Binary), "1.5 MiB");
+        assert_eq!(format_bytes(1610612736, ByteUnits::Binary), "1.5 GiB");
+
+        // Decimal units change the divisor as well as the label
+        assert_eq!(format_bytes(1000, ByteUnits::Decimal), "1.0 KB");
+        assert_eq!(format_bytes(1536, ByteUnits::Decimal), "1.5 KB");
+        assert_eq!(format_bytes(1610612736, ByteUnits::Decimal), "1.6 GB");
+        assert_eq!(format_bytes(1020, ByteUnits::Binary), "1020 B");
     }
 }
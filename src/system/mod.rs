@@ -8,16 +8,32 @@
 
 mod admin;
 mod affinity;
+mod clipboard;
 pub mod cpu;
+mod debugger;
 mod details;
 mod disk;
+mod efficiency;
 mod error;
+mod io_priority;
 mod memory;
+mod network;
+mod ntdll;
+mod owner;
+mod package;
 mod path;
 mod priority;
 mod processes;
+mod services;
+mod session;
+mod shell;
+mod signature;
 mod suspend;
+mod sysinfo;
+mod terminate;
+mod threads;
 mod uptime;
+mod wow64;
 
 // ============================================================================
 // Re-exports for clean imports
@@ -26,9 +42,18 @@ mod uptime;
 // Admin/elevation
 pub use admin::{elevation_indicator, elevation_status_string, is_elevated};
 
+// CPU time
+pub use cpu::{format_cpu_time, CpuStats};
+
 // CPU affinity
 pub use affinity::{get_process_affinity, get_system_core_count, set_process_affinity, CpuAffinity};
 
+// Clipboard
+pub use clipboard::copy_to_clipboard;
+
+// Debugger detection
+pub use debugger::{is_being_debugged, is_being_debugged_with};
+
 // Process details
 pub use details::{
     get_process_command_line, get_process_modules, get_process_tcp_connections,
@@ -36,33 +61,85 @@ pub use details::{
 };
 
 // Disk I/O
-pub use disk::{get_process_disk_info, ProcessDiskInfo};
+pub use disk::{get_process_disk_info, get_process_disk_info_with, ProcessDiskInfo};
+
+// Efficiency mode and priority boost
+pub use efficiency::{
+    format_on_off, get_efficiency_mode, get_efficiency_mode_with, get_priority_boost,
+    set_efficiency_mode, set_priority_boost,
+};
+
+// I/O priority
+pub use io_priority::{
+    get_process_io_priority, query_process_io_priority, set_process_io_priority, IoPriority,
+};
 
 // Memory
 pub use memory::{
-    format_bytes, get_process_memory_info, get_system_memory_info, ProcessMemoryInfo,
-    SystemMemoryInfo,
+    format_bytes, format_rate, get_process_memory_info, get_process_memory_info_with,
+    get_system_memory_info, ByteUnits, ProcessMemoryInfo, SystemMemoryInfo,
 };
 
+// Network I/O
+pub use network::{get_connection_pids, NetworkIoReader, ProcessNetworkInfo};
+
+// Process owner
+pub use owner::{get_current_user_sid, get_process_user_sid, get_process_user_sid_with};
+
+// Packaged app identity
+pub use package::{get_app_user_model_id, get_package_family_name};
+
 // Path and handles
-pub use path::{get_process_handle_count, get_process_path, path_to_filename};
+pub use path::{
+    get_process_handle_count, get_process_handle_count_with, get_process_path,
+    get_process_path_with, path_to_filename,
+};
 
 // Priority
-pub use priority::{get_process_priority, set_process_priority, Priority};
+pub use priority::{
+    get_process_priority, get_process_priority_with, set_process_priority, Priority,
+};
 
 // Process enumeration
 pub use processes::{enumerate_processes, ProcessInfo};
 
+// Services
+pub use services::{get_process_services, get_services_by_pid};
+
+// Sessions
+pub use session::{format_session, get_process_session_id};
+
+// Shell integration
+pub use shell::reveal_in_explorer;
+
 // Suspend/resume
-pub use suspend::{
-    is_process_suspended, resume_process, suspend_process, toggle_suspend, untrack_process,
+pub use suspend::{resume_process, suspend_process};
+
+// Machine-wide information
+pub use sysinfo::{
+    get_computer_name, get_os_version, get_system_info, get_system_uptime_seconds, SystemInfo,
 };
 
+// Termination
+pub use terminate::{
+    get_process_protection, get_process_protection_name, terminate_process, ProcessProtection,
+};
+
+// Code signatures
+pub use signature::{get_signature_info, SignatureInfo, SignatureStatus};
+
+// Per-thread CPU time
+pub use threads::{get_thread_cpu_times, ThreadCpuTimes};
+
 // Uptime
 pub use uptime::{
     calculate_uptime_seconds, format_uptime, get_current_filetime, get_process_start_time,
+    get_process_start_time_with,
 };
 
+// WOW64 (32-bit) detection
+pub use wow64::{is_wow64_process, is_wow64_process_with};
+
 // Error types
 pub use error::{
     AffinityError, AffinityResult, PriorityError, PriorityResult, ProcessError, ProcessResult,
@@ -0,0 +1,356 @@
+//! Per-process network I/O using TCP extended statistics
+//!
+//! Windows has no per-process network byte counter, and the PDH
+//! `\Network Interface` counters are system-wide only. Instead, this
+//! module reads the per-connection data counters of extended statistics
+//! (EStats) with `GetPerTcpConnectionEStats` and sums them by owning PID.
+//!
+//! Collection is a system-wide setting on each connection, so it is only
+//! turned on when asked (`--net-stats`); `NetworkIoReader` then records
+//! the connections it enabled and turns them off again when dropped.
+//!
+//! Limitations:
+//! - Only IPv4 TCP connections with collection on report bytes; enabling
+//!   it requires elevation.
+//! - UDP traffic is not counted (no per-endpoint byte counters exist).
+//! - Bytes from connections that closed since the last sample are lost,
+//!   so rates are a lower bound.
+
+use std::collections::{HashMap, HashSet};
+use std::ffi::c_void;
+use std::mem;
+
+use windows::Win32::NetworkManagement::IpHelper::{
+    GetExtendedTcpTable, GetExtendedUdpTable, GetPerTcpConnectionEStats,
+    SetPerTcpConnectionEStats, TcpConnectionEstatsData, MIB_TCP6ROW_OWNER_PID, MIB_TCPROW_LH,
+    MIB_TCPROW_LH_0, MIB_TCPROW_OWNER_PID, MIB_UDP6ROW_OWNER_PID, MIB_UDPROW_OWNER_PID,
+    TCP_ESTATS_DATA_ROD_v0, TCP_ESTATS_DATA_RW_v0, TCP_TABLE_OWNER_PID_ALL,
+    TCP_TABLE_OWNER_PID_CONNECTIONS, UDP_TABLE_OWNER_PID,
+};
+use windows::Win32::Foundation::{BOOLEAN, ERROR_INSUFFICIENT_BUFFER};
+use windows::Win32::Networking::WinSock::{AF_INET, AF_INET6};
+
+use crate::constants::SOCKET_TABLE_READ_ATTEMPTS;
+
+/// Cumulative network bytes for a process (summed over its TCP connections)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProcessNetworkInfo {
+    /// Total bytes received
+    pub recv_bytes: u64,
+    /// Total bytes sent
+    pub send_bytes: u64,
+}
+
+/// Local and remote address and port, identifying a TCP connection
+type ConnectionKey = (u32, u32, u32, u32);
+
+/// Builds the row the EStats calls take for a TCP table row.
+fn estats_row(row: &MIB_TCPROW_OWNER_PID) -> MIB_TCPROW_LH {
+    MIB_TCPROW_LH {
+        Anonymous: MIB_TCPROW_LH_0 { dwState: row.dwState },
+        dwLocalAddr: row.dwLocalAddr,
+        dwLocalPort: row.dwLocalPort,
+        dwRemoteAddr: row.dwRemoteAddr,
+        dwRemotePort: row.dwRemotePort,
+    }
+}
+
+/// Gets the identity of a TCP table row.
+fn connection_key(row: &MIB_TCPROW_OWNER_PID) -> ConnectionKey {
+    (row.dwLocalAddr, row.dwLocalPort, row.dwRemoteAddr, row.dwRemotePort)
+}
+
+/// Turns data collection on or off for one TCP connection.
+///
+/// # Returns
+/// `true` if the setting was applied (fails without elevation).
+fn set_collection(tcp_row: &MIB_TCPROW_LH, enable: bool) -> bool {
+    let rw = TCP_ESTATS_DATA_RW_v0 { EnableCollection: BOOLEAN(enable as u8) };
+
+    // SAFETY: The struct is plain-old-data and the byte slice covers
+    // exactly its memory for the duration of the call.
+    let result = unsafe {
+        let rw_bytes = std::slice::from_raw_parts(
+            &rw as *const _ as *const u8,
+            mem::size_of::<TCP_ESTATS_DATA_RW_v0>(),
+        );
+        SetPerTcpConnectionEStats(tcp_row, TcpConnectionEstatsData, rw_bytes, 0, 0)
+    };
+    result == 0
+}
+
+/// Reads the data counters for one TCP connection.
+///
+/// # Returns
+/// * `Some((bool, ProcessNetworkInfo))` - Whether collection is on, and
+///   the counters (meaningless while it is off)
+/// * `None` - If the connection could not be queried
+fn read_connection_bytes(tcp_row: &MIB_TCPROW_LH) -> Option<(bool, ProcessNetworkInfo)> {
+    let mut rw = TCP_ESTATS_DATA_RW_v0::default();
+    let mut rod = TCP_ESTATS_DATA_ROD_v0::default();
+
+    // SAFETY: Both structs are plain-old-data and the byte slices cover
+    // exactly their memory for the duration of the call.
+    let result = unsafe {
+        let rw_bytes = std::slice::from_raw_parts_mut(
+            &mut rw as *mut _ as *mut u8,
+            mem::size_of::<TCP_ESTATS_DATA_RW_v0>(),
+        );
+        let rod_bytes = std::slice::from_raw_parts_mut(
+            &mut rod as *mut _ as *mut u8,
+            mem::size_of::<TCP_ESTATS_DATA_ROD_v0>(),
+        );
+        GetPerTcpConnectionEStats(
+            tcp_row,
+            TcpConnectionEstatsData,
+            Some(rw_bytes),
+            0,
+            None,
+            0,
+            Some(rod_bytes),
+            0,
+        )
+    };
+
+    if result != 0 {
+        return None;
+    }
+
+    Some((
+        rw.EnableCollection.as_bool(),
+        ProcessNetworkInfo {
+            recv_bytes: rod.DataBytesIn,
+            send_bytes: rod.DataBytesOut,
+        },
+    ))
+}
+
+/// Reads every row of an owner-PID socket table.
+///
+/// The table is sized by a first call; sockets opened before the second
+/// call can make it outgrow the buffer (ERROR_INSUFFICIENT_BUFFER), so
+/// the read is retried with the updated size a few times.
+///
+/// # Arguments
+/// * `read` - Calls `GetExtendedTcpTable` or `GetExtendedUdpTable` with a
+///   buffer (`None` to query the size) and the size in/out value
+///
+/// # Returns
+/// * `Some(Vec<T>)` - The rows, `T` being the table's row type
+/// * `None` - If the table could not be read
+fn read_owner_table<T>(read: impl Fn(Option<*mut c_void>, &mut u32) -> u32) -> Option<Vec<T>> {
+    let mut size: u32 = 0;
+    let _ = read(None, &mut size);
+
+    for _ in 0..SOCKET_TABLE_READ_ATTEMPTS {
+        if size == 0 {
+            return None;
+        }
+
+        let mut buffer = vec![0u8; size as usize];
+        let result = read(Some(buffer.as_mut_ptr() as *mut _), &mut size);
+        if result == ERROR_INSUFFICIENT_BUFFER.0 {
+            continue;
+        }
+        if result != 0 {
+            return None;
+        }
+
+        // Every owner table is a DWORD dwNumEntries followed by the rows
+        let capacity = buffer.len().saturating_sub(4) / mem::size_of::<T>();
+        // SAFETY: The count is read from the buffer the call filled, and
+        // rows are only read within that buffer.
+        let rows = unsafe {
+            let num_entries = std::ptr::read_unaligned(buffer.as_ptr() as *const u32) as usize;
+            let table_ptr = buffer.as_ptr().add(4) as *const T;
+            (0..num_entries.min(capacity))
+                .map(|i| std::ptr::read_unaligned(table_ptr.add(i)))
+                .collect()
+        };
+        return Some(rows);
+    }
+
+    None
+}
+
+/// Samples per-process TCP bytes, optionally turning collection on
+#[derive(Debug, Default)]
+pub struct NetworkIoReader {
+    /// Whether to turn collection on for connections that have it off
+    enable_collection: bool,
+    /// Open connections this reader turned collection on for
+    enabled: HashSet<ConnectionKey>,
+}
+
+impl NetworkIoReader {
+    /// Creates a reader.
+    ///
+    /// # Arguments
+    /// * `enable_collection` - Turn collection on for connections that
+    ///   have it off (it is turned off again when the reader is dropped);
+    ///   otherwise only connections already collecting are counted
+    pub fn new(enable_collection: bool) -> Self {
+        Self { enable_collection, enabled: HashSet::new() }
+    }
+
+    /// Gets cumulative TCP bytes for every process with open connections.
+    ///
+    /// Takes a single snapshot of the TCP table and aggregates
+    /// per-connection counters by owning PID, so it should be called once
+    /// per refresh rather than once per process.
+    ///
+    /// # Returns
+    /// * `HashMap<u32, ProcessNetworkInfo>` - Byte totals keyed by PID
+    ///   (processes without collecting connections are absent)
+    pub fn read(&mut self) -> HashMap<u32, ProcessNetworkInfo> {
+        let mut totals: HashMap<u32, ProcessNetworkInfo> = HashMap::new();
+
+        let rows: Vec<MIB_TCPROW_OWNER_PID> = match read_owner_table(|buffer, size| unsafe {
+            GetExtendedTcpTable(buffer, size, false, AF_INET.0 as u32, TCP_TABLE_OWNER_PID_CONNECTIONS, 0)
+        }) {
+            Some(rows) => rows,
+            None => return totals,
+        };
+
+        // Closed connections need no turning off
+        let open: HashSet<ConnectionKey> = rows.iter().map(connection_key).collect();
+        self.enabled.retain(|key| open.contains(key));
+
+        for row in &rows {
+            // TIME_WAIT connections are owned by the idle pseudo-process
+            if row.dwOwningPid == 0 {
+                continue;
+            }
+
+            let tcp_row = estats_row(row);
+            let (mut collecting, mut bytes) = match read_connection_bytes(&tcp_row) {
+                Some(read) => read,
+                None => continue,
+            };
+            if !collecting && self.enable_collection && set_collection(&tcp_row, true) {
+                self.enabled.insert(connection_key(row));
+                (collecting, bytes) = match read_connection_bytes(&tcp_row) {
+                    Some(read) => read,
+                    None => continue,
+                };
+            }
+            if !collecting {
+                continue;
+            }
+
+            let entry = totals.entry(row.dwOwningPid).or_default();
+            entry.recv_bytes += bytes.recv_bytes;
+            entry.send_bytes += bytes.send_bytes;
+        }
+
+        totals
+    }
+}
+
+impl Drop for NetworkIoReader {
+    /// Turns collection off again on the connections this reader enabled
+    /// that are still open, leaving connections others enabled alone.
+    fn drop(&mut self) {
+        if self.enabled.is_empty() {
+            return;
+        }
+        let rows: Vec<MIB_TCPROW_OWNER_PID> = match read_owner_table(|buffer, size| unsafe {
+            GetExtendedTcpTable(buffer, size, false, AF_INET.0 as u32, TCP_TABLE_OWNER_PID_CONNECTIONS, 0)
+        }) {
+            Some(rows) => rows,
+            None => return,
+        };
+        for row in rows.iter().filter(|row| self.enabled.contains(&connection_key(row))) {
+            set_collection(&estats_row(row), false);
+        }
+    }
+}
+
+/// Gets the PIDs that own at least one TCP connection or listener, or a
+/// UDP endpoint, over IPv4 or IPv6.
+///
+/// Takes one snapshot of each table instead of querying per process, so
+/// it is cheap enough to call on every refresh.
+///
+/// # Returns
+/// * `HashSet<u32>` - PIDs owning sockets (empty if every table fails)
+#[must_use]
+pub fn get_connection_pids() -> HashSet<u32> {
+    let mut pids = HashSet::new();
+
+    let tcp4: Option<Vec<MIB_TCPROW_OWNER_PID>> = read_owner_table(|buffer, size| unsafe {
+        GetExtendedTcpTable(buffer, size, false, AF_INET.0 as u32, TCP_TABLE_OWNER_PID_ALL, 0)
+    });
+    pids.extend(tcp4.unwrap_or_default().iter().map(|row| row.dwOwningPid));
+
+    let tcp6: Option<Vec<MIB_TCP6ROW_OWNER_PID>> = read_owner_table(|buffer, size| unsafe {
+        GetExtendedTcpTable(buffer, size, false, AF_INET6.0 as u32, TCP_TABLE_OWNER_PID_ALL, 0)
+    });
+    pids.extend(tcp6.unwrap_or_default().iter().map(|row| row.dwOwningPid));
+
+    let udp4: Option<Vec<MIB_UDPROW_OWNER_PID>> = read_owner_table(|buffer, size| unsafe {
+        GetExtendedUdpTable(buffer, size, false, AF_INET.0 as u32, UDP_TABLE_OWNER_PID, 0)
+    });
+    pids.extend(udp4.unwrap_or_default().iter().map(|row| row.dwOwningPid));
+
+    let udp6: Option<Vec<MIB_UDP6ROW_OWNER_PID>> = read_owner_table(|buffer, size| unsafe {
+        GetExtendedUdpTable(buffer, size, false, AF_INET6.0 as u32, UDP_TABLE_OWNER_PID, 0)
+    });
+    pids.extend(udp6.unwrap_or_default().iter().map(|row| row.dwOwningPid));
+
+    pids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_pids() {
+        // A listener and a UDP socket of our own must show up
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let pids = get_connection_pids();
+        assert!(pids.contains(&std::process::id()));
+        drop(listener);
+
+        let socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        assert!(get_connection_pids().contains(&std::process::id()));
+        drop(socket);
+
+        // IPv6-only sockets come from the separate IPv6 tables
+        if let Ok(listener) = std::net::TcpListener::bind("[::1]:0") {
+            assert!(get_connection_pids().contains(&std::process::id()));
+            drop(listener);
+        }
+        if let Ok(socket) = std::net::UdpSocket::bind("[::1]:0") {
+            assert!(get_connection_pids().contains(&std::process::id()));
+            drop(socket);
+        }
+    }
+
+    #[test]
+    fn test_network_io_by_pid() {
+        use std::io::Write;
+
+        // Open a loopback connection of our own and send over it
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (_server, _) = listener.accept().unwrap();
+        client.write_all(b"ping").unwrap();
+
+        let mut reader = NetworkIoReader::new(true);
+        let totals = reader.read();
+        // Closed connections held by the idle process are never counted
+        assert!(!totals.contains_key(&0));
+        // Collection can only be enabled when elevated; otherwise the
+        // connection only reports if something else already enabled it
+        if crate::system::is_elevated() {
+            assert!(totals.contains_key(&std::process::id()));
+            assert!(!reader.enabled.is_empty());
+
+            // Dropping the reader turns collection back off
+            drop(reader);
+            assert!(!NetworkIoReader::new(false).read().contains_key(&std::process::id()));
+        }
+    }
+}
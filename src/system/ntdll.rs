@@ -0,0 +1,83 @@
+//! Native process information calls from ntdll.dll
+//!
+//! `NtQueryInformationProcess` / `NtSetInformationProcess` expose process
+//! attributes with no Win32 wrapper (I/O priority, protection signer,
+//! command line), and
+//! `RtlGetVersion` reports the real OS version regardless of the manifest.
+//! They are resolved dynamically once and cached.
+
+use std::ffi::c_void;
+use std::sync::OnceLock;
+
+use windows::core::s;
+use windows::Win32::Foundation::{HANDLE, NTSTATUS};
+use windows::Win32::System::LibraryLoader::{GetModuleHandleA, GetProcAddress};
+use windows::Win32::System::SystemInformation::OSVERSIONINFOW;
+
+/// `PROCESSINFOCLASS` value for `ProcessIoPriority`
+pub const PROCESS_IO_PRIORITY_CLASS: u32 = 33;
+
+/// `PROCESSINFOCLASS` value for `ProcessBasicInformation` (PEB address)
+pub const PROCESS_BASIC_INFORMATION_CLASS: u32 = 0;
+
+/// `PROCESSINFOCLASS` value for `ProcessCommandLineInformation` (Windows 8.1+)
+pub const PROCESS_COMMAND_LINE_INFORMATION_CLASS: u32 = 60;
+
+/// `PROCESSINFOCLASS` value for `ProcessProtectionInformation`
+pub const PROCESS_PROTECTION_INFORMATION_CLASS: u32 = 61;
+
+/// NtQueryInformationProcess signature
+pub type NtQueryInformationProcess =
+    unsafe extern "system" fn(HANDLE, u32, *mut c_void, u32, *mut u32) -> NTSTATUS;
+
+/// NtSetInformationProcess signature
+pub type NtSetInformationProcess =
+    unsafe extern "system" fn(HANDLE, u32, *const c_void, u32) -> NTSTATUS;
+
+/// RtlGetVersion signature
+pub type RtlGetVersion = unsafe extern "system" fn(*mut OSVERSIONINFOW) -> NTSTATUS;
+
+/// NtQueryInformationProcess, resolved on first use
+static NT_QUERY_INFORMATION_PROCESS: OnceLock<Option<NtQueryInformationProcess>> = OnceLock::new();
+
+/// NtSetInformationProcess, resolved on first use
+static NT_SET_INFORMATION_PROCESS: OnceLock<Option<NtSetInformationProcess>> = OnceLock::new();
+
+/// RtlGetVersion, resolved on first use
+static RTL_GET_VERSION: OnceLock<Option<RtlGetVersion>> = OnceLock::new();
+
+/// Get the NtQueryInformationProcess function from ntdll (resolved once)
+pub fn get_nt_query_information_process() -> Option<NtQueryInformationProcess> {
+    *NT_QUERY_INFORMATION_PROCESS.get_or_init(|| unsafe {
+        let module = GetModuleHandleA(s!("ntdll.dll")).ok()?;
+        // None for a missing export, checked before the transmute
+        let proc = GetProcAddress(module, s!("NtQueryInformationProcess"))?;
+        Some(std::mem::transmute::<
+            unsafe extern "system" fn() -> isize,
+            NtQueryInformationProcess,
+        >(proc))
+    })
+}
+
+/// Get the NtSetInformationProcess function from ntdll (resolved once)
+pub fn get_nt_set_information_process() -> Option<NtSetInformationProcess> {
+    *NT_SET_INFORMATION_PROCESS.get_or_init(|| unsafe {
+        let module = GetModuleHandleA(s!("ntdll.dll")).ok()?;
+        // None for a missing export, checked before the transmute
+        let proc = GetProcAddress(module, s!("NtSetInformationProcess"))?;
+        Some(std::mem::transmute::<
+            unsafe extern "system" fn() -> isize,
+            NtSetInformationProcess,
+        >(proc))
+    })
+}
+
+/// Get the RtlGetVersion function from ntdll (resolved once)
+pub fn get_rtl_get_version() -> Option<RtlGetVersion> {
+    *RTL_GET_VERSION.get_or_init(|| unsafe {
+        let module = GetModuleHandleA(s!("ntdll.dll")).ok()?;
+        // None for a missing export, checked before the transmute
+        let proc = GetProcAddress(module, s!("RtlGetVersion"))?;
+        Some(std::mem::transmute::<unsafe extern "system" fn() -> isize, RtlGetVersion>(proc))
+    })
+}
@@ -0,0 +1,109 @@
+//! Process owner (user SID) lookup
+//!
+//! This module reads the user SID from a process token so processes can
+//! be compared against the account running this application.
+
+use windows::Win32::Foundation::{CloseHandle, HANDLE};
+use windows::Win32::Security::{
+    GetLengthSid, GetTokenInformation, TokenUser, TOKEN_QUERY, TOKEN_USER,
+};
+use windows::Win32::System::Threading::{
+    GetCurrentProcess, OpenProcessToken, PROCESS_QUERY_LIMITED_INFORMATION,
+};
+
+use crate::ffi::ProcessHandle;
+
+/// Reads the user SID from a process handle's token as raw bytes.
+fn token_user_sid(process: HANDLE) -> Option<Vec<u8>> {
+    // SAFETY: The token handle is closed before returning, and the SID
+    // pointer is only dereferenced while `buffer` is alive.
+    unsafe {
+        let mut token = HANDLE::default();
+        OpenProcessToken(process, TOKEN_QUERY, &mut token).ok()?;
+
+        // First call to get the required buffer size
+        let mut size: u32 = 0;
+        let _ = GetTokenInformation(token, TokenUser, None, 0, &mut size);
+
+        if size == 0 {
+            let _ = CloseHandle(token);
+            return None;
+        }
+
+        // Use a u64 buffer so the TOKEN_USER struct is properly aligned
+        let mut buffer = vec![0u64; size as usize / 8 + 1];
+        let result = GetTokenInformation(
+            token,
+            TokenUser,
+            Some(buffer.as_mut_ptr() as *mut _),
+            size,
+            &mut size,
+        );
+
+        let _ = CloseHandle(token);
+
+        if result.is_err() {
+            return None;
+        }
+
+        let token_user = &*(buffer.as_ptr() as *const TOKEN_USER);
+        let sid = token_user.User.Sid;
+        let len = GetLengthSid(sid) as usize;
+        if sid.0.is_null() || len == 0 {
+            return None;
+        }
+
+        Some(std::slice::from_raw_parts(sid.0 as *const u8, len).to_vec())
+    }
+}
+
+/// Gets the user SID of the account running this application.
+///
+/// # Returns
+/// * `Option<Vec<u8>>` - Raw SID bytes, or None if the token cannot be read
+#[must_use]
+pub fn get_current_user_sid() -> Option<Vec<u8>> {
+    // SAFETY: GetCurrentProcess returns a pseudo-handle that needs no closing.
+    token_user_sid(unsafe { GetCurrentProcess() })
+}
+
+/// Gets the user SID that owns a process.
+///
+/// SIDs are stored in a canonical binary form, so two SIDs are equal
+/// exactly when their bytes are equal.
+///
+/// # Arguments
+/// * `pid` - The process ID to query
+///
+/// # Returns
+/// * `Option<Vec<u8>>` - Raw SID bytes, or None if inaccessible
+#[must_use]
+pub fn get_process_user_sid(pid: u32) -> Option<Vec<u8>> {
+    ProcessHandle::open(pid, PROCESS_QUERY_LIMITED_INFORMATION)
+        .ok()
+        .and_then(|process| get_process_user_sid_with(&process))
+}
+
+/// Gets the user SID that owns a process through an open handle.
+///
+/// # Arguments
+/// * `process` - Handle with at least limited query rights
+///
+/// # Returns
+/// * `Option<Vec<u8>>` - Raw SID bytes, or None if the token cannot be read
+#[must_use]
+pub fn get_process_user_sid_with(process: &ProcessHandle) -> Option<Vec<u8>> {
+    token_user_sid(process.as_raw())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_own_process_matches_current_user() {
+        let current = get_current_user_sid();
+        assert!(current.is_some(), "Should read our own token");
+        assert_eq!(get_process_user_sid(std::process::id()), current);
+    }
+}
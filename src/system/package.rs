@@ -0,0 +1,98 @@
+//! Packaged (Store/UWP/MSIX) app identity
+//!
+//! Packaged apps often run under generic hosts such as
+//! `ApplicationFrameHost.exe` or `RuntimeBroker.exe`. Their package family
+//! name and Application User Model ID identify the actual app. Classic
+//! (unpackaged) processes have neither.
+
+use windows::core::PWSTR;
+use windows::Win32::Foundation::{
+    CloseHandle, ERROR_INSUFFICIENT_BUFFER, ERROR_SUCCESS, HANDLE, WIN32_ERROR,
+};
+use windows::Win32::Storage::Packaging::Appx::{GetApplicationUserModelId, GetPackageFamilyName};
+use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+/// Reads a package string with the usual "ask for the length, then fill"
+/// pattern shared by the app model APIs.
+///
+/// # Arguments
+/// * `pid` - The process ID to query
+/// * `query` - The API call (handle, length in/out, buffer)
+///
+/// # Returns
+/// * `Option<String>` - None for unpackaged or inaccessible processes
+fn query_package_string(
+    pid: u32,
+    query: unsafe fn(HANDLE, *mut u32, PWSTR) -> WIN32_ERROR,
+) -> Option<String> {
+    if pid == 0 || pid == 4 {
+        return None;
+    }
+
+    // SAFETY: OpenProcess is safe with valid parameters
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) }.ok()?;
+
+    // SAFETY: The first call only writes the required length; the second
+    // writes at most `length` characters into a buffer of that size.
+    let result = unsafe {
+        let mut length: u32 = 0;
+        if query(handle, &mut length, PWSTR::null()) == ERROR_INSUFFICIENT_BUFFER {
+            let mut buffer = vec![0u16; length as usize];
+            if query(handle, &mut length, PWSTR(buffer.as_mut_ptr())) == ERROR_SUCCESS {
+                // length includes the null terminator
+                buffer.truncate(length.saturating_sub(1) as usize);
+                Some(String::from_utf16_lossy(&buffer))
+            } else {
+                None
+            }
+        } else {
+            // APPMODEL_ERROR_NO_PACKAGE / NO_APPLICATION for classic processes
+            None
+        }
+    };
+
+    // Always close the handle
+    unsafe {
+        let _ = CloseHandle(handle);
+    }
+
+    result
+}
+
+/// Gets the package family name of a packaged app.
+///
+/// # Arguments
+/// * `pid` - The process ID to query
+///
+/// # Returns
+/// * `Option<String>` - e.g. "Microsoft.WindowsCalculator_8wekyb3d8bbwe",
+///   or None for classic processes
+#[must_use]
+pub fn get_package_family_name(pid: u32) -> Option<String> {
+    query_package_string(pid, GetPackageFamilyName::<HANDLE>)
+}
+
+/// Gets the Application User Model ID (AUMID) of a packaged app.
+///
+/// # Arguments
+/// * `pid` - The process ID to query
+///
+/// # Returns
+/// * `Option<String>` - e.g. "Microsoft.WindowsCalculator_8wekyb3d8bbwe!App",
+///   or None for classic processes
+#[must_use]
+pub fn get_app_user_model_id(pid: u32) -> Option<String> {
+    query_package_string(pid, GetApplicationUserModelId::<HANDLE>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classic_process_has_no_package() {
+        // Test binaries are unpackaged
+        assert_eq!(get_package_family_name(std::process::id()), None);
+        assert_eq!(get_app_user_model_id(std::process::id()), None);
+    }
+}
@@ -3,12 +3,14 @@
 //! This module provides functions to get the full executable path
 //! and handle count for a process.
 
-use windows::Win32::Foundation::{CloseHandle, MAX_PATH};
+use windows::Win32::Foundation::MAX_PATH;
 use windows::Win32::System::Threading::{
-    GetProcessHandleCount, OpenProcess, QueryFullProcessImageNameW,
-    PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+    GetProcessHandleCount, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+    PROCESS_QUERY_LIMITED_INFORMATION,
 };
 
+use crate::ffi::ProcessHandle;
+
 /// Gets the full executable path for a process.
 ///
 /// # Arguments
@@ -18,34 +20,33 @@ use windows::Win32::System::Threading::{
 /// * `Option<String>` - Full path or None if inaccessible
 #[must_use]
 pub fn get_process_path(pid: u32) -> Option<String> {
-    // SAFETY: OpenProcess is safe with valid parameters
-    let handle = unsafe {
-        OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid)
-    };
-
-    let handle = match handle {
-        Ok(h) => h,
-        Err(_) => return None,
-    };
+    ProcessHandle::open(pid, PROCESS_QUERY_LIMITED_INFORMATION)
+        .ok()
+        .and_then(|process| get_process_path_with(&process))
+}
 
+/// Gets the full executable path through an open process handle.
+///
+/// # Arguments
+/// * `process` - Handle with at least limited query rights
+///
+/// # Returns
+/// * `Option<String>` - Full path or None if the query fails
+#[must_use]
+pub fn get_process_path_with(process: &ProcessHandle) -> Option<String> {
     let mut buffer = [0u16; MAX_PATH as usize];
     let mut size = buffer.len() as u32;
 
     // SAFETY: QueryFullProcessImageNameW is safe with valid handle and buffer
     let result = unsafe {
         QueryFullProcessImageNameW(
-            handle,
+            process.as_raw(),
             PROCESS_NAME_WIN32,
             windows::core::PWSTR(buffer.as_mut_ptr()),
             &mut size,
         )
     };
 
-    // Always close the handle
-    unsafe {
-        let _ = CloseHandle(handle);
-    }
-
     if result.is_ok() && size > 0 {
         Some(String::from_utf16_lossy(&buffer[..size as usize]))
     } else {
@@ -59,39 +60,37 @@ pub fn get_process_path(pid: u32) -> Option<String> {
 /// * `pid` - The process ID to query
 ///
 /// # Returns
-/// * `u32` - Number of handles, or 0 if inaccessible
+/// * `Option<u32>` - Number of handles, or None if inaccessible
 #[must_use]
-pub fn get_process_handle_count(pid: u32) -> u32 {
-    // SAFETY: OpenProcess is safe with valid parameters
-    let handle = unsafe {
-        OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid)
-    };
-
-    let handle = match handle {
-        Ok(h) => h,
-        Err(_) => return 0,
-    };
+pub fn get_process_handle_count(pid: u32) -> Option<u32> {
+    ProcessHandle::open(pid, PROCESS_QUERY_LIMITED_INFORMATION)
+        .ok()
+        .and_then(|process| get_process_handle_count_with(&process))
+}
 
+/// Gets the handle count through an open process handle.
+///
+/// # Arguments
+/// * `process` - Handle with at least limited query rights
+///
+/// # Returns
+/// * `Option<u32>` - Number of handles, or None if the query fails
+#[must_use]
+pub fn get_process_handle_count_with(process: &ProcessHandle) -> Option<u32> {
     let mut count: u32 = 0;
 
     // SAFETY: GetProcessHandleCount is safe with valid handle and pointer
-    let result = unsafe { GetProcessHandleCount(handle, &mut count) };
-
-    // Always close the handle
-    unsafe {
-        let _ = CloseHandle(handle);
-    }
+    let result = unsafe { GetProcessHandleCount(process.as_raw(), &mut count) };
 
     if result.is_ok() {
-        count
+        Some(count)
     } else {
-        0
+        None
     }
 }
 
 /// Extracts just the filename from a full path
 #[must_use]
-#[allow(dead_code)]
 pub fn path_to_filename(path: &str) -> &str {
     path.rsplit('\\').next().unwrap_or(path)
 }
@@ -108,10 +107,16 @@ mod tests {
         println!("Current process path: {:?}", path);
     }
 
+    #[test]
+    fn test_path_to_filename() {
+        assert_eq!(path_to_filename(r"C:\Windows\System32\svchost.exe"), "svchost.exe");
+        assert_eq!(path_to_filename("notepad.exe"), "notepad.exe");
+    }
+
     #[test]
     fn test_current_process_handles() {
         let pid = std::process::id();
-        let count = get_process_handle_count(pid);
+        let count = get_process_handle_count(pid).expect("Should query our own process");
         assert!(count > 0, "Should have some handles");
         println!("Current process handles: {}", count);
     }
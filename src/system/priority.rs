@@ -13,6 +13,7 @@ use windows::Win32::System::Threading::{
 };
 
 use super::error::{PriorityError, PriorityResult};
+use crate::ffi::ProcessHandle;
 
 /// Windows process priority levels.
 ///
@@ -133,25 +134,22 @@ impl Default for Priority {
 /// # Returns
 /// * `Priority` - The process priority (Unknown if access denied)
 pub fn get_process_priority(pid: u32) -> Priority {
-    // Try to open the process with query rights
-    // SAFETY: OpenProcess is safe to call with valid parameters.
-    let handle = unsafe {
-        OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid)
-    };
-
-    let handle = match handle {
-        Ok(h) => h,
-        Err(_) => return Priority::Unknown,
-    };
+    match ProcessHandle::open(pid, PROCESS_QUERY_LIMITED_INFORMATION) {
+        Ok(process) => get_process_priority_with(&process),
+        Err(_) => Priority::Unknown,
+    }
+}
 
+/// Gets the priority class through an open process handle.
+///
+/// # Arguments
+/// * `process` - Handle with at least limited query rights
+///
+/// # Returns
+/// * `Priority` - The process priority (Unknown if the query fails)
+pub fn get_process_priority_with(process: &ProcessHandle) -> Priority {
     // SAFETY: GetPriorityClass is safe with a valid handle.
-    let priority_class = unsafe { GetPriorityClass(handle) };
-
-    // Always close the handle
-    // SAFETY: We own this handle.
-    unsafe {
-        let _ = CloseHandle(handle);
-    }
+    let priority_class = unsafe { GetPriorityClass(process.as_raw()) };
 
     if priority_class == 0 {
         Priority::Unknown
@@ -4,12 +4,24 @@
 //! on the system using CreateToolhelp32Snapshot and Process32First/Next.
 
 use std::mem;
+use std::thread;
+use std::time::Duration;
 use windows::Win32::System::Diagnostics::ToolHelp::{
     Process32FirstW, Process32NextW, PROCESSENTRY32W,
 };
 
 use crate::ffi::SnapshotHandle;
 
+/// Kernel-managed pseudo-processes that have no executable image
+const PSEUDO_PROCESS_NAMES: &[&str] = &["Registry", "Memory Compression", "Secure System"];
+
+/// Snapshot attempts before giving up (creation can fail transiently
+/// under memory pressure)
+const SNAPSHOT_ATTEMPTS: u32 = 3;
+
+/// Pause between snapshot attempts
+const SNAPSHOT_RETRY_DELAY: Duration = Duration::from_millis(50);
+
 /// Information about a single process
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -40,6 +52,16 @@ impl ProcessInfo {
             name,
         }
     }
+
+    /// Checks whether this is the Idle/System process or another
+    /// kernel pseudo-process that cannot be acted on like a normal one.
+    ///
+    /// # Returns
+    /// `true` for PIDs 0 and 4 and image-less kernel processes.
+    #[must_use]
+    pub fn is_system_pseudo_process(&self) -> bool {
+        self.pid == 0 || self.pid == 4 || PSEUDO_PROCESS_NAMES.contains(&self.name.as_str())
+    }
 }
 
 /// Converts a null-terminated wide string (u16 slice) to a Rust String
@@ -52,11 +74,12 @@ fn wide_to_string(wide: &[u16]) -> String {
 /// Enumerates all running processes on the system.
 ///
 /// Uses the ToolHelp32 API to create a snapshot of all processes
-/// and iterates through them.
+/// and iterates through them. Snapshot creation is retried a few
+/// times with a short pause, since it can fail transiently.
 ///
 /// # Returns
 /// * `Ok(Vec<ProcessInfo>)` - A vector of all running processes
-/// * `Err` - If the snapshot cannot be created
+/// * `Err` - If every snapshot attempt failed
 ///
 /// # Example
 /// ```no_run
@@ -67,7 +90,11 @@ fn wide_to_string(wide: &[u16]) -> String {
 /// ```
 #[must_use]
 pub fn enumerate_processes() -> windows::core::Result<Vec<ProcessInfo>> {
-    let snapshot = SnapshotHandle::create_process_snapshot()?;
+    let snapshot = retry(
+        SNAPSHOT_ATTEMPTS,
+        SNAPSHOT_RETRY_DELAY,
+        SnapshotHandle::create_process_snapshot,
+    )?;
     let mut processes = Vec::new();
     
     // Initialize the entry structure - CRITICAL: dwSize must be set!
@@ -91,10 +118,66 @@ pub fn enumerate_processes() -> windows::core::Result<Vec<ProcessInfo>> {
     Ok(processes)
 }
 
+/// Calls `op` up to `attempts` times, sleeping `delay` between failures.
+///
+/// # Returns
+/// The first success, or the last error.
+fn retry<T, E>(attempts: u32, delay: Duration, mut op: impl FnMut() -> Result<T, E>) -> Result<T, E> {
+    let mut remaining = attempts.max(1);
+    loop {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                remaining -= 1;
+                if remaining == 0 {
+                    return Err(e);
+                }
+                thread::sleep(delay);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     
+    fn info(pid: u32, name: &str) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            parent_pid: 4,
+            thread_count: 1,
+            base_priority: 8,
+            name: name.to_string(),
+        }
+    }
+    
+    #[test]
+    fn test_is_system_pseudo_process() {
+        assert!(info(0, "[System Process]").is_system_pseudo_process());
+        assert!(info(4, "System").is_system_pseudo_process());
+        assert!(info(132, "Registry").is_system_pseudo_process());
+        assert!(info(2480, "Memory Compression").is_system_pseudo_process());
+        assert!(!info(1234, "notepad.exe").is_system_pseudo_process());
+    }
+    
+    #[test]
+    fn test_retry() {
+        let mut calls = 0;
+        let result: Result<u32, &str> = retry(3, Duration::ZERO, || {
+            calls += 1;
+            if calls < 3 { Err("transient") } else { Ok(calls) }
+        });
+        assert_eq!(result, Ok(3));
+
+        let mut calls = 0;
+        let result: Result<(), &str> = retry(2, Duration::ZERO, || {
+            calls += 1;
+            Err("down")
+        });
+        assert_eq!((result, calls), (Err("down"), 2));
+    }
+    
     #[test]
     fn test_enumerate_processes() {
         let processes = enumerate_processes().expect("Should enumerate processes");
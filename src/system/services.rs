@@ -0,0 +1,141 @@
+//! Service-to-process mapping via the Service Control Manager
+//!
+//! This module enumerates running Win32 services and groups them by
+//! hosting PID, so shared hosts like `svchost.exe` can be identified.
+
+use std::collections::HashMap;
+
+use windows::core::PCWSTR;
+use windows::Win32::System::Services::{
+    CloseServiceHandle, EnumServicesStatusExW, OpenSCManagerW, ENUM_SERVICE_STATUS_PROCESSW,
+    SC_ENUM_PROCESS_INFO, SC_MANAGER_ENUMERATE_SERVICE, SERVICE_ACTIVE, SERVICE_WIN32,
+};
+
+/// Gets the names of running services, grouped by hosting process ID.
+///
+/// Each call opens the SCM and enumerates all active services, so it
+/// should be used for on-demand views rather than every refresh.
+///
+/// # Returns
+/// * `HashMap<u32, Vec<String>>` - Service names keyed by PID, sorted
+///   alphabetically (empty if the SCM cannot be opened)
+#[must_use]
+pub fn get_services_by_pid() -> HashMap<u32, Vec<String>> {
+    let mut services: HashMap<u32, Vec<String>> = HashMap::new();
+
+    // SAFETY: The SCM handle is closed before returning, and service name
+    // pointers are only dereferenced while `buffer` is alive.
+    unsafe {
+        let scm = match OpenSCManagerW(
+            PCWSTR::null(),
+            PCWSTR::null(),
+            SC_MANAGER_ENUMERATE_SERVICE,
+        ) {
+            Ok(h) => h,
+            Err(_) => return services,
+        };
+
+        let mut resume_handle: u32 = 0;
+        loop {
+            // First call to get the required buffer size
+            let mut bytes_needed: u32 = 0;
+            let mut returned: u32 = 0;
+            let _ = EnumServicesStatusExW(
+                scm,
+                SC_ENUM_PROCESS_INFO,
+                SERVICE_WIN32,
+                SERVICE_ACTIVE,
+                None,
+                &mut bytes_needed,
+                &mut returned,
+                Some(&mut resume_handle),
+                PCWSTR::null(),
+            );
+
+            if bytes_needed == 0 {
+                break;
+            }
+
+            // Use a u64 buffer so the entries are properly aligned
+            let mut buffer = vec![0u64; bytes_needed as usize / 8 + 1];
+            let bytes = std::slice::from_raw_parts_mut(
+                buffer.as_mut_ptr() as *mut u8,
+                buffer.len() * 8,
+            );
+
+            // Fails with ERROR_MORE_DATA if services appeared in between;
+            // the resume handle picks up where this call stopped.
+            let result = EnumServicesStatusExW(
+                scm,
+                SC_ENUM_PROCESS_INFO,
+                SERVICE_WIN32,
+                SERVICE_ACTIVE,
+                Some(bytes),
+                &mut bytes_needed,
+                &mut returned,
+                Some(&mut resume_handle),
+                PCWSTR::null(),
+            );
+
+            let entries = std::slice::from_raw_parts(
+                buffer.as_ptr() as *const ENUM_SERVICE_STATUS_PROCESSW,
+                returned as usize,
+            );
+
+            for entry in entries {
+                let pid = entry.ServiceStatusProcess.dwProcessId;
+                if pid == 0 || entry.lpServiceName.is_null() {
+                    continue;
+                }
+                if let Ok(name) = entry.lpServiceName.to_string() {
+                    services.entry(pid).or_default().push(name);
+                }
+            }
+
+            if result.is_ok() || returned == 0 {
+                break;
+            }
+        }
+
+        let _ = CloseServiceHandle(scm);
+    }
+
+    for names in services.values_mut() {
+        names.sort_by_key(|n| n.to_lowercase());
+    }
+
+    services
+}
+
+/// Gets the names of running services hosted by a single process.
+///
+/// # Arguments
+/// * `pid` - The process ID to look up
+///
+/// # Returns
+/// * `Vec<String>` - Hosted service names (empty if none or inaccessible)
+#[must_use]
+pub fn get_process_services(pid: u32) -> Vec<String> {
+    get_services_by_pid().remove(&pid).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_services_by_pid() {
+        let services = get_services_by_pid();
+        // Any running Windows system has at least one active service
+        assert!(!services.is_empty(), "Should find running services");
+
+        // RpcSs (Remote Procedure Call) always runs, in a live svchost
+        let (pid, _) = services
+            .iter()
+            .find(|(_, names)| names.iter().any(|n| n.eq_ignore_ascii_case("RpcSs")))
+            .expect("RpcSs should be running");
+        let processes = crate::system::enumerate_processes().unwrap();
+        let host = processes.iter().find(|p| p.pid == *pid).expect("RpcSs host should be running");
+        assert!(host.name.eq_ignore_ascii_case("svchost.exe"));
+    }
+}
@@ -0,0 +1,57 @@
+//! Terminal Services session lookup
+//!
+//! Every process belongs to a session: session 0 hosts services, and each
+//! interactive or RDP logon gets its own session ID from 1 upward.
+
+use windows::Win32::System::RemoteDesktop::ProcessIdToSessionId;
+
+/// Gets the Terminal Services session a process runs in.
+///
+/// Needs no process handle, so it works even where `OpenProcess` is denied.
+///
+/// # Arguments
+/// * `pid` - The process ID to query
+///
+/// # Returns
+/// * `Option<u32>` - Session ID, or None if the process has exited
+#[must_use]
+pub fn get_process_session_id(pid: u32) -> Option<u32> {
+    let mut session_id: u32 = 0;
+
+    // SAFETY: session_id is a valid, writable u32
+    unsafe { ProcessIdToSessionId(pid, &mut session_id) }.ok()?;
+
+    Some(session_id)
+}
+
+/// Describes a session ID for display, e.g. "0 (services)".
+///
+/// # Arguments
+/// * `session_id` - Session ID, or None if unknown
+#[must_use]
+pub fn format_session(session_id: Option<u32>) -> String {
+    match session_id {
+        Some(0) => "0 (services)".to_string(),
+        Some(id) => id.to_string(),
+        None => "Unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_own_session() {
+        // Usually an interactive session, but a service or scheduled-task
+        // test runner runs in session 0, so only check it can be read
+        assert!(get_process_session_id(std::process::id()).is_some());
+    }
+
+    #[test]
+    fn test_format_session() {
+        assert_eq!(format_session(Some(0)), "0 (services)");
+        assert_eq!(format_session(Some(2)), "2");
+        assert_eq!(format_session(None), "Unknown");
+    }
+}
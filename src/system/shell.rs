@@ -0,0 +1,46 @@
+//! Windows shell integration
+//!
+//! This module hands paths off to Explorer so a process's executable
+//! can be located on disk.
+
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::HWND;
+use windows::Win32::UI::Shell::ShellExecuteW;
+use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+use super::error::{ProcessError, ProcessResult};
+
+/// Opens an Explorer window with the given file selected.
+///
+/// # Arguments
+/// * `path` - Full path of the file to reveal
+///
+/// # Returns
+/// * `Ok(())` - Explorer was launched
+/// * `Err(ProcessError)` - Error if ShellExecuteW failed
+pub fn reveal_in_explorer(path: &str) -> ProcessResult<()> {
+    let params: Vec<u16> = format!("/select,\"{}\"", path)
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+
+    // SAFETY: All strings are null-terminated and outlive the call.
+    let result = unsafe {
+        ShellExecuteW(
+            HWND::default(),
+            w!("open"),
+            w!("explorer.exe"),
+            PCWSTR(params.as_ptr()),
+            PCWSTR::null(),
+            SW_SHOWNORMAL,
+        )
+    };
+
+    // Values greater than 32 indicate success
+    let code = result.0 as isize;
+    if code > 32 {
+        Ok(())
+    } else {
+        Err(ProcessError::WinApiError { api: "ShellExecuteW", code: code as i32 })
+    }
+}
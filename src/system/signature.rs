@@ -0,0 +1,316 @@
+//! Code signature (Authenticode) verification
+//!
+//! `get_signature_info` checks an executable with WinVerifyTrust and reads
+//! the signer's display name from the verified certificate chain. Many
+//! Windows binaries carry no embedded signature but are listed in a signed
+//! system catalog, so when the file itself is unsigned the catalog database
+//! is searched by the file's hash and the catalog's signature is verified
+//! instead.
+//!
+//! Revocation is not checked online, so a lookup never stalls on the
+//! network. Hashing the file still takes tens of milliseconds for large
+//! executables, so callers look it up once per path and cache it.
+
+use std::ffi::{c_void, OsStr};
+use std::fs::File;
+use std::iter::once;
+use std::mem;
+use std::os::windows::ffi::OsStrExt;
+use std::os::windows::io::AsRawHandle;
+
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::{
+    CERT_E_CHAINING, CERT_E_EXPIRED, CERT_E_REVOKED, CERT_E_UNTRUSTEDROOT, HANDLE, HWND,
+    TRUST_E_BAD_DIGEST, TRUST_E_EXPLICIT_DISTRUST, TRUST_E_NOSIGNATURE, TRUST_E_PROVIDER_UNKNOWN,
+    TRUST_E_SUBJECT_FORM_UNKNOWN,
+};
+use windows::Win32::Security::Cryptography::Catalog::{
+    CryptCATAdminAcquireContext2, CryptCATAdminCalcHashFromFileHandle2,
+    CryptCATAdminEnumCatalogFromHash, CryptCATAdminReleaseCatalogContext,
+    CryptCATAdminReleaseContext, CryptCATCatalogInfoFromContext, CATALOG_INFO,
+};
+use windows::Win32::Security::Cryptography::{CertGetNameStringW, CERT_NAME_SIMPLE_DISPLAY_TYPE};
+use windows::Win32::Security::WinTrust::{
+    WTHelperGetProvCertFromChain, WTHelperGetProvSignerFromChain, WTHelperProvDataFromStateData,
+    WinVerifyTrust, WINTRUST_ACTION_GENERIC_VERIFY_V2, WINTRUST_CATALOG_INFO, WINTRUST_DATA,
+    WINTRUST_DATA_0, WINTRUST_DATA_UNION_CHOICE, WINTRUST_FILE_INFO, WTD_CACHE_ONLY_URL_RETRIEVAL,
+    WTD_CHOICE_CATALOG, WTD_CHOICE_FILE, WTD_REVOKE_NONE, WTD_STATEACTION_CLOSE,
+    WTD_STATEACTION_VERIFY, WTD_UI_NONE,
+};
+
+/// Largest file hash a catalog lookup produces (SHA-512)
+const MAX_HASH_BYTES: usize = 64;
+
+/// Whether a signature was found and verified
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignatureStatus {
+    /// Signed, and the signature and chain verified
+    Valid,
+    /// Signed, but verification failed (the reason)
+    Invalid(String),
+    /// Neither an embedded signature nor a catalog entry was found
+    Unsigned,
+}
+
+/// Signature of an executable image
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureInfo {
+    /// Verification result
+    pub status: SignatureStatus,
+    /// Signer's display name, e.g. "Microsoft Corporation" (None if unsigned or unreadable)
+    pub signer: Option<String>,
+    /// Whether the signature came from a system catalog rather than the file
+    pub catalog: bool,
+}
+
+impl SignatureInfo {
+    /// Formats the signature for display.
+    ///
+    /// # Returns
+    /// e.g. "Signed by Microsoft Corporation (Valid)",
+    /// "Signed by Microsoft Windows (Valid, catalog)" or "Unsigned"
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let validity = match &self.status {
+            SignatureStatus::Unsigned => return "Unsigned".to_string(),
+            SignatureStatus::Valid => "Valid".to_string(),
+            SignatureStatus::Invalid(reason) => format!("Invalid: {}", reason),
+        };
+        format!(
+            "Signed by {} ({}{})",
+            self.signer.as_deref().unwrap_or("unknown signer"),
+            validity,
+            if self.catalog { ", catalog" } else { "" }
+        )
+    }
+}
+
+/// Checks whether a WinVerifyTrust result means there is no signature to
+/// verify (as opposed to a signature that failed).
+fn is_unsigned(code: i32) -> bool {
+    [TRUST_E_NOSIGNATURE, TRUST_E_SUBJECT_FORM_UNKNOWN, TRUST_E_PROVIDER_UNKNOWN]
+        .iter()
+        .any(|e| e.0 == code)
+}
+
+/// Describes why a signature failed to verify.
+///
+/// # Arguments
+/// * `code` - The HRESULT returned by WinVerifyTrust
+fn trust_error_reason(code: i32) -> String {
+    let reason = match code {
+        c if c == CERT_E_EXPIRED.0 => "certificate expired",
+        c if c == CERT_E_REVOKED.0 => "certificate revoked",
+        c if c == CERT_E_UNTRUSTEDROOT.0 || c == CERT_E_CHAINING.0 => "untrusted root",
+        c if c == TRUST_E_BAD_DIGEST.0 => "file modified after signing",
+        c if c == TRUST_E_EXPLICIT_DISTRUST.0 => "explicitly distrusted",
+        c => return format!("error 0x{:08X}", c as u32),
+    };
+    reason.to_string()
+}
+
+/// Builds WinVerifyTrust input that never shows UI or goes online.
+fn trust_data(choice: WINTRUST_DATA_UNION_CHOICE, subject: WINTRUST_DATA_0) -> WINTRUST_DATA {
+    WINTRUST_DATA {
+        cbStruct: mem::size_of::<WINTRUST_DATA>() as u32,
+        dwUIChoice: WTD_UI_NONE,
+        fdwRevocationChecks: WTD_REVOKE_NONE,
+        dwUnionChoice: choice,
+        Anonymous: subject,
+        dwProvFlags: WTD_CACHE_ONLY_URL_RETRIEVAL,
+        ..Default::default()
+    }
+}
+
+/// Runs WinVerifyTrust, reads the signer while the state data is open,
+/// then releases it.
+///
+/// # Returns
+/// The raw result (0 when valid) and the signer's name.
+///
+/// # Safety
+/// `data` must point at a subject that stays alive for the call.
+unsafe fn verify(data: &mut WINTRUST_DATA) -> (i32, Option<String>) {
+    let mut action = WINTRUST_ACTION_GENERIC_VERIFY_V2;
+    data.dwStateAction = WTD_STATEACTION_VERIFY;
+    let code = WinVerifyTrust(HWND::default(), &mut action, data as *mut _ as *mut c_void);
+    let signer = signer_name(data.hWVTStateData);
+
+    data.dwStateAction = WTD_STATEACTION_CLOSE;
+    WinVerifyTrust(HWND::default(), &mut action, data as *mut _ as *mut c_void);
+    (code, signer)
+}
+
+/// Reads the display name of the first signer's certificate.
+///
+/// # Safety
+/// `state` must be open WinVerifyTrust state data.
+unsafe fn signer_name(state: HANDLE) -> Option<String> {
+    let provider = WTHelperProvDataFromStateData(state);
+    if provider.is_null() {
+        return None;
+    }
+    let signer = WTHelperGetProvSignerFromChain(provider, 0, false, 0);
+    if signer.is_null() {
+        return None;
+    }
+    let cert = WTHelperGetProvCertFromChain(signer, 0);
+    if cert.is_null() || (*cert).pCert.is_null() {
+        return None;
+    }
+
+    let mut name = [0u16; 256];
+    let len = CertGetNameStringW((*cert).pCert, CERT_NAME_SIMPLE_DISPLAY_TYPE, 0, None, Some(&mut name));
+    // The length includes the terminating null; 1 means an empty name
+    if len <= 1 {
+        return None;
+    }
+    Some(String::from_utf16_lossy(&name[..len as usize - 1]))
+}
+
+/// Verifies the signature embedded in the file.
+fn verify_embedded(wide_path: &[u16]) -> (i32, Option<String>) {
+    let mut file_info = WINTRUST_FILE_INFO {
+        cbStruct: mem::size_of::<WINTRUST_FILE_INFO>() as u32,
+        pcwszFilePath: PCWSTR(wide_path.as_ptr()),
+        ..Default::default()
+    };
+    let mut data = trust_data(WTD_CHOICE_FILE, WINTRUST_DATA_0 { pFile: &mut file_info });
+    // SAFETY: file_info and the path outlive the call.
+    unsafe { verify(&mut data) }
+}
+
+/// Looks the file up in the system catalogs and verifies the catalog
+/// that lists it.
+///
+/// # Returns
+/// * `Option<(i32, Option<String>)>` - The result and signer, or None if
+///   no catalog lists the file
+fn verify_catalog(wide_path: &[u16], file: &File) -> Option<(i32, Option<String>)> {
+    let mut admin = 0isize;
+    // SAFETY: admin receives a context that is released below.
+    unsafe { CryptCATAdminAcquireContext2(&mut admin, None, w!("SHA256"), None, 0) }.ok()?;
+    let result = verify_catalog_with(admin, wide_path, file);
+    // SAFETY: admin was acquired above and is released once.
+    unsafe {
+        let _ = CryptCATAdminReleaseContext(admin, 0);
+    }
+    result
+}
+
+/// Hashes the file, finds a catalog listing the hash and verifies it.
+fn verify_catalog_with(admin: isize, wide_path: &[u16], file: &File) -> Option<(i32, Option<String>)> {
+    let handle = HANDLE(file.as_raw_handle());
+    let mut hash = [0u8; MAX_HASH_BYTES];
+    let mut hash_len = hash.len() as u32;
+    // SAFETY: hash has room for hash_len bytes and the handle is open.
+    unsafe {
+        CryptCATAdminCalcHashFromFileHandle2(admin, handle, &mut hash_len, Some(hash.as_mut_ptr()), 0)
+    }
+    .ok()?;
+    let hash_len = (hash_len as usize).min(MAX_HASH_BYTES);
+
+    // SAFETY: admin is a valid context and the hash slice is initialized.
+    let catalog = unsafe { CryptCATAdminEnumCatalogFromHash(admin, &hash[..hash_len], 0, None) };
+    if catalog == 0 {
+        return None;
+    }
+
+    let mut info = CATALOG_INFO {
+        cbStruct: mem::size_of::<CATALOG_INFO>() as u32,
+        ..Default::default()
+    };
+    // SAFETY: catalog is a valid catalog context from the enumeration above.
+    let result = unsafe { CryptCATCatalogInfoFromContext(catalog, &mut info, 0) }.ok().map(|()| {
+        // Catalog members are tagged with the hash as uppercase hex
+        let tag: Vec<u16> = hash[..hash_len]
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect::<String>()
+            .encode_utf16()
+            .chain(once(0))
+            .collect();
+        let mut catalog_info = WINTRUST_CATALOG_INFO {
+            cbStruct: mem::size_of::<WINTRUST_CATALOG_INFO>() as u32,
+            pcwszCatalogFilePath: PCWSTR(info.wszCatalogFile.as_ptr()),
+            pcwszMemberTag: PCWSTR(tag.as_ptr()),
+            pcwszMemberFilePath: PCWSTR(wide_path.as_ptr()),
+            hMemberFile: handle,
+            pbCalculatedFileHash: hash.as_mut_ptr(),
+            cbCalculatedFileHash: hash_len as u32,
+            hCatAdmin: admin,
+            ..Default::default()
+        };
+        let mut data = trust_data(WTD_CHOICE_CATALOG, WINTRUST_DATA_0 { pCatalog: &mut catalog_info });
+        // SAFETY: catalog_info, the tag, hash and paths outlive the call.
+        unsafe { verify(&mut data) }
+    });
+
+    // SAFETY: catalog came from this admin context and is released once.
+    unsafe {
+        let _ = CryptCATAdminReleaseCatalogContext(admin, catalog, 0);
+    }
+    result
+}
+
+/// Gets the code signature of an executable.
+///
+/// Tries the file's embedded signature first, then the system catalogs.
+///
+/// # Arguments
+/// * `path` - Full path of the executable
+///
+/// # Returns
+/// * `Option<SignatureInfo>` - The signature, or None if the file cannot be opened
+#[must_use]
+pub fn get_signature_info(path: &str) -> Option<SignatureInfo> {
+    let file = File::open(path).ok()?;
+    let wide_path: Vec<u16> = OsStr::new(path).encode_wide().chain(once(0)).collect();
+
+    let (code, signer) = verify_embedded(&wide_path);
+    if code == 0 {
+        return Some(SignatureInfo { status: SignatureStatus::Valid, signer, catalog: false });
+    }
+    if !is_unsigned(code) {
+        let status = SignatureStatus::Invalid(trust_error_reason(code));
+        return Some(SignatureInfo { status, signer, catalog: false });
+    }
+
+    Some(match verify_catalog(&wide_path, &file) {
+        Some((0, signer)) => SignatureInfo { status: SignatureStatus::Valid, signer, catalog: true },
+        Some((code, signer)) if !is_unsigned(code) => SignatureInfo {
+            status: SignatureStatus::Invalid(trust_error_reason(code)),
+            signer,
+            catalog: true,
+        },
+        _ => SignatureInfo { status: SignatureStatus::Unsigned, signer: None, catalog: false },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_signature() {
+        let signed = |status, catalog| SignatureInfo {
+            status,
+            signer: Some("Microsoft Corporation".to_string()),
+            catalog,
+        };
+        assert_eq!(
+            signed(SignatureStatus::Valid, false).describe(),
+            "Signed by Microsoft Corporation (Valid)"
+        );
+        assert_eq!(
+            signed(SignatureStatus::Invalid(trust_error_reason(CERT_E_EXPIRED.0)), true).describe(),
+            "Signed by Microsoft Corporation (Invalid: certificate expired, catalog)"
+        );
+        let unsigned = SignatureInfo { status: SignatureStatus::Unsigned, signer: None, catalog: false };
+        assert_eq!(unsigned.describe(), "Unsigned");
+
+        assert!(is_unsigned(TRUST_E_NOSIGNATURE.0));
+        assert!(!is_unsigned(TRUST_E_BAD_DIGEST.0));
+        assert_eq!(trust_error_reason(0x800B0004_u32 as i32), "error 0x800B0004");
+    }
+}
@@ -0,0 +1,112 @@
+//! Machine-wide information for the system info panel
+//!
+//! OS version comes from `RtlGetVersion` (see `ntdll`), since
+//! `GetVersionEx` reports Windows 8 to unmanifested programs.
+
+use windows::core::PWSTR;
+use windows::Win32::Foundation::STATUS_SUCCESS;
+use windows::Win32::System::SystemInformation::{
+    ComputerNamePhysicalDnsHostname, GetComputerNameExW, GetTickCount64, OSVERSIONINFOW,
+};
+
+use super::affinity::get_system_core_count;
+use super::memory::get_system_memory_info;
+use super::ntdll::get_rtl_get_version;
+
+/// Snapshot of machine-wide information
+#[derive(Debug, Clone)]
+pub struct SystemInfo {
+    /// OS version, e.g. "Windows 10.0 (build 22631)"
+    pub os_version: Option<String>,
+    /// Computer (DNS host) name
+    pub computer_name: Option<String>,
+    /// Number of logical processors
+    pub logical_cpus: u32,
+    /// Total physical memory in bytes
+    pub total_memory: u64,
+    /// Seconds since the system booted
+    pub uptime_seconds: u64,
+}
+
+/// Gets the OS version via `RtlGetVersion`.
+///
+/// # Returns
+/// * `Option<String>` - e.g. "Windows 10.0 (build 22631)", or None if the
+///   call is unavailable
+#[must_use]
+pub fn get_os_version() -> Option<String> {
+    let rtl_get_version = get_rtl_get_version()?;
+
+    let mut info = OSVERSIONINFOW {
+        dwOSVersionInfoSize: std::mem::size_of::<OSVERSIONINFOW>() as u32,
+        ..Default::default()
+    };
+    // SAFETY: info is sized and initialized as RtlGetVersion requires
+    let status = unsafe { rtl_get_version(&mut info) };
+    if status != STATUS_SUCCESS {
+        return None;
+    }
+
+    Some(format!(
+        "Windows {}.{} (build {})",
+        info.dwMajorVersion, info.dwMinorVersion, info.dwBuildNumber
+    ))
+}
+
+/// Gets the computer's DNS host name.
+///
+/// # Returns
+/// * `Option<String>` - None if the name cannot be read
+#[must_use]
+pub fn get_computer_name() -> Option<String> {
+    let mut buffer = [0u16; 256];
+    let mut size = buffer.len() as u32;
+
+    // SAFETY: size holds the buffer length in characters
+    unsafe {
+        GetComputerNameExW(
+            ComputerNamePhysicalDnsHostname,
+            PWSTR(buffer.as_mut_ptr()),
+            &mut size,
+        )
+        .ok()?;
+    }
+
+    Some(String::from_utf16_lossy(&buffer[..size as usize]))
+}
+
+/// Gets the time since the system booted.
+///
+/// # Returns
+/// * `u64` - Uptime in seconds
+#[must_use]
+pub fn get_system_uptime_seconds() -> u64 {
+    // SAFETY: GetTickCount64 has no preconditions
+    unsafe { GetTickCount64() / 1000 }
+}
+
+/// Gathers all machine-wide information for the system info panel.
+#[must_use]
+pub fn get_system_info() -> SystemInfo {
+    SystemInfo {
+        os_version: get_os_version(),
+        computer_name: get_computer_name(),
+        logical_cpus: get_system_core_count(),
+        total_memory: get_system_memory_info().map(|m| m.total_physical).unwrap_or(0),
+        uptime_seconds: get_system_uptime_seconds(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_info() {
+        let info = get_system_info();
+        assert!(info.os_version.is_some_and(|v| v.starts_with("Windows ")));
+        assert!(info.computer_name.is_some_and(|n| !n.is_empty()));
+        assert!(info.logical_cpus > 0);
+        assert!(info.total_memory > 0);
+    }
+}
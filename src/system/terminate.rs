@@ -0,0 +1,250 @@
+//! Process termination with protection checks
+//!
+//! Protected processes (PPL, e.g. antimalware services) reject termination
+//! even from an elevated caller, and terminating a critical process such as
+//! `csrss.exe` or `wininit.exe` bug-checks the system. Both are detected
+//! before attempting, so failures carry a specific reason instead of a bare
+//! error code.
+
+use windows::core::HRESULT;
+use windows::Win32::Foundation::{
+    CloseHandle, BOOL, ERROR_ACCESS_DENIED, ERROR_INVALID_PARAMETER, HANDLE, STATUS_SUCCESS,
+};
+use windows::Win32::System::Threading::{
+    GetProcessInformation, IsProcessCritical, OpenProcess, ProcessProtectionLevelInfo,
+    TerminateProcess, PROCESS_PROTECTION_LEVEL_INFORMATION, PROCESS_QUERY_LIMITED_INFORMATION,
+    PROCESS_TERMINATE, PROTECTION_LEVEL_NONE,
+};
+
+use super::error::{ProcessError, ProcessResult};
+use super::ntdll::{get_nt_query_information_process, PROCESS_PROTECTION_INFORMATION_CLASS};
+
+/// Protection attributes that affect whether a process can be terminated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProcessProtection {
+    /// Process runs as a protected process (PPL)
+    pub protected: bool,
+    /// Process is marked critical; terminating it crashes Windows
+    pub critical: bool,
+}
+
+/// Reads protection attributes from an open process handle.
+fn query_protection(handle: HANDLE) -> ProcessProtection {
+    let mut protection = ProcessProtection::default();
+
+    // SAFETY: Both output structs are plain-old-data and sized correctly.
+    unsafe {
+        let mut critical = BOOL(0);
+        if IsProcessCritical(handle, &mut critical).is_ok() {
+            protection.critical = critical.as_bool();
+        }
+
+        let mut info = PROCESS_PROTECTION_LEVEL_INFORMATION::default();
+        if GetProcessInformation(
+            handle,
+            ProcessProtectionLevelInfo,
+            &mut info as *mut _ as *mut _,
+            std::mem::size_of::<PROCESS_PROTECTION_LEVEL_INFORMATION>() as u32,
+        )
+        .is_ok()
+        {
+            protection.protected = info.ProtectionLevel != PROTECTION_LEVEL_NONE;
+        }
+    }
+
+    protection
+}
+
+/// Gets the protection attributes of a process.
+///
+/// # Arguments
+/// * `pid` - The process ID to query
+///
+/// # Returns
+/// * `ProcessProtection` - Both flags false if the process cannot be queried
+#[must_use]
+pub fn get_process_protection(pid: u32) -> ProcessProtection {
+    // SAFETY: OpenProcess is safe with valid parameters
+    let handle = match unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) } {
+        Ok(h) => h,
+        Err(_) => return ProcessProtection::default(),
+    };
+
+    let protection = query_protection(handle);
+
+    // Always close the handle
+    unsafe {
+        let _ = CloseHandle(handle);
+    }
+
+    protection
+}
+
+/// Decodes a `PS_PROTECTION` byte into the kernel's signer name.
+///
+/// Bits 0-2 hold the protection type (1 = light, 2 = full) and bits 4-7
+/// the signer, e.g. 0x31 is "PsProtectedSignerAntimalware-Light".
+///
+/// # Returns
+/// * `Option<String>` - None for unprotected processes
+fn format_ps_protection(level: u8) -> Option<String> {
+    let suffix = match level & 0x7 {
+        1 => "-Light",
+        2 => "",
+        _ => return None,
+    };
+    let signer = match level >> 4 {
+        0 => "None",
+        1 => "Authenticode",
+        2 => "CodeGen",
+        3 => "Antimalware",
+        4 => "Lsa",
+        5 => "Windows",
+        6 => "WinTcb",
+        7 => "WinSystem",
+        8 => "App",
+        _ => "Unknown",
+    };
+    Some(format!("PsProtectedSigner{}{}", signer, suffix))
+}
+
+/// Gets the protection signer of a protected process (PP/PPL).
+///
+/// Uses `NtQueryInformationProcess(ProcessProtectionInformation)`, which
+/// only needs limited query access and so works on protected processes.
+///
+/// # Arguments
+/// * `pid` - The process ID to query
+///
+/// # Returns
+/// * `Option<String>` - e.g. "PsProtectedSignerAntimalware-Light", or None
+///   if the process is not protected or cannot be queried
+#[must_use]
+pub fn get_process_protection_name(pid: u32) -> Option<String> {
+    let nt_query = get_nt_query_information_process()?;
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+
+        let mut level: u8 = 0;
+        let mut returned: u32 = 0;
+        let status = nt_query(
+            handle,
+            PROCESS_PROTECTION_INFORMATION_CLASS,
+            &mut level as *mut u8 as *mut _,
+            std::mem::size_of::<u8>() as u32,
+            &mut returned,
+        );
+        let _ = CloseHandle(handle);
+
+        if status == STATUS_SUCCESS {
+            format_ps_protection(level)
+        } else {
+            None
+        }
+    }
+}
+
+/// Maps a failed Win32 call during termination to a descriptive error.
+///
+/// # Arguments
+/// * `api` - Name of the failing API function
+/// * `code` - The HRESULT it failed with
+/// * `protection` - Protection attributes read before the attempt
+fn classify_error(api: &'static str, code: HRESULT, protection: ProcessProtection) -> ProcessError {
+    if code == ERROR_ACCESS_DENIED.to_hresult() {
+        if protection.protected {
+            ProcessError::Protected
+        } else {
+            ProcessError::AccessDenied
+        }
+    } else if code == ERROR_INVALID_PARAMETER.to_hresult() {
+        // OpenProcess reports an exited PID as an invalid parameter
+        ProcessError::NotFound
+    } else {
+        ProcessError::WinApiError { api, code: code.0 }
+    }
+}
+
+/// Terminate a process by PID
+///
+/// Refuses system and critical processes up front, and explains access
+/// failures caused by process protection.
+///
+/// # Arguments
+/// * `pid` - The process ID to terminate
+/// * `exit_code` - Exit code the process will report
+///
+/// # Returns
+/// * `Ok(())` - The process was terminated
+/// * `Err(ProcessError)` - Why it could not be terminated
+pub fn terminate_process(pid: u32, exit_code: u32) -> ProcessResult<()> {
+    if pid == 0 || pid == 4 {
+        return Err(ProcessError::SystemProcess);
+    }
+
+    let protection = get_process_protection(pid);
+    if protection.critical {
+        return Err(ProcessError::Critical);
+    }
+
+    // SAFETY: OpenProcess and TerminateProcess are safe to call with valid params.
+    unsafe {
+        let handle = OpenProcess(PROCESS_TERMINATE, false, pid)
+            .map_err(|e| classify_error("OpenProcess", e.code(), protection))?;
+
+        let result = TerminateProcess(handle, exit_code);
+        let _ = CloseHandle(handle);
+
+        result.map_err(|e| classify_error("TerminateProcess", e.code(), protection))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_error() {
+        let none = ProcessProtection::default();
+        let ppl = ProcessProtection { protected: true, critical: false };
+        let denied = ERROR_ACCESS_DENIED.to_hresult();
+
+        assert_eq!(classify_error("OpenProcess", denied, none), ProcessError::AccessDenied);
+        assert_eq!(classify_error("OpenProcess", denied, ppl), ProcessError::Protected);
+        assert_eq!(
+            classify_error("OpenProcess", ERROR_INVALID_PARAMETER.to_hresult(), none),
+            ProcessError::NotFound
+        );
+
+        // Anything else keeps the failing API and raw code
+        let other = windows::Win32::Foundation::ERROR_GEN_FAILURE.to_hresult();
+        assert_eq!(
+            classify_error("TerminateProcess", other, none),
+            ProcessError::WinApiError { api: "TerminateProcess", code: other.0 }
+        );
+    }
+
+    #[test]
+    fn test_own_process_not_protected() {
+        let protection = get_process_protection(std::process::id());
+        assert!(!protection.protected && !protection.critical);
+        assert_eq!(get_process_protection_name(std::process::id()), None);
+    }
+
+    #[test]
+    fn test_format_ps_protection() {
+        assert_eq!(format_ps_protection(0), None);
+        assert_eq!(
+            format_ps_protection(0x31).as_deref(),
+            Some("PsProtectedSignerAntimalware-Light")
+        );
+        assert_eq!(format_ps_protection(0x62).as_deref(), Some("PsProtectedSignerWinTcb"));
+    }
+
+    #[test]
+    fn test_terminate_system_process_rejected() {
+        assert_eq!(terminate_process(0, 1), Err(ProcessError::SystemProcess));
+        assert_eq!(terminate_process(4, 1), Err(ProcessError::SystemProcess));
+    }
+}
@@ -0,0 +1,174 @@
+//! Per-thread CPU time, summed per process
+//!
+//! The CPU time GetProcessTimes reports for a process also counts threads
+//! that have already exited, so the sum over its live threads (from a
+//! ToolHelp32 thread snapshot and GetThreadTimes) is normally a little
+//! lower. The detail view shows both and flags a notable gap, which
+//! explains a busy process with few live threads, or readings that
+//! disagree outright.
+
+use std::mem;
+use windows::Win32::Foundation::FILETIME;
+use windows::Win32::System::Diagnostics::ToolHelp::{Thread32First, Thread32Next, THREADENTRY32};
+use windows::Win32::System::Threading::{
+    GetProcessTimes, GetThreadTimes, PROCESS_QUERY_LIMITED_INFORMATION,
+    THREAD_QUERY_LIMITED_INFORMATION,
+};
+
+use crate::constants::{THREAD_CPU_DIVERGENCE_MIN_SECONDS, THREAD_CPU_DIVERGENCE_RATIO};
+use crate::ffi::{ProcessHandle, SnapshotHandle, ThreadHandle};
+
+use super::cpu::{filetime_to_u64, format_cpu_time, FILETIME_TICKS_PER_SECOND};
+
+/// Summed thread CPU time of one process, next to its process-level total
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ThreadCpuTimes {
+    /// Live threads found in the snapshot
+    pub thread_count: u32,
+    /// Threads whose times could not be read (exited or access denied)
+    pub unreadable: u32,
+    /// Kernel plus user time of the readable threads, in seconds
+    pub threads_seconds: f64,
+    /// Kernel plus user time from GetProcessTimes, in seconds
+    pub process_seconds: f64,
+}
+
+impl ThreadCpuTimes {
+    /// Formats the thread sum next to the process total, with the
+    /// divergence note if there is one.
+    ///
+    /// # Returns
+    /// e.g. "00:01:12 over 9 threads (process 00:01:40; process total
+    /// includes threads that have exited)"
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let mut text = format!(
+            "{} over {} thread{} (process {}",
+            format_cpu_time(self.threads_seconds as u64),
+            self.thread_count,
+            if self.thread_count == 1 { "" } else { "s" },
+            format_cpu_time(self.process_seconds as u64)
+        );
+        if let Some(note) = self.divergence_note() {
+            text.push_str("; ");
+            text.push_str(note);
+        }
+        text.push(')');
+        text
+    }
+
+    /// Explains a notable gap between the thread sum and the process total.
+    ///
+    /// # Returns
+    /// `None` while the two agree within `THREAD_CPU_DIVERGENCE_RATIO` of
+    /// the process total or `THREAD_CPU_DIVERGENCE_MIN_SECONDS`, whichever
+    /// is larger; otherwise a short reason.
+    #[must_use]
+    pub fn divergence_note(&self) -> Option<&'static str> {
+        let gap = self.threads_seconds - self.process_seconds;
+        let allowed = (self.process_seconds * THREAD_CPU_DIVERGENCE_RATIO)
+            .max(THREAD_CPU_DIVERGENCE_MIN_SECONDS);
+        if gap.abs() < allowed {
+            None
+        } else if gap > 0.0 {
+            Some("threads exceed the process total; readings disagree")
+        } else if self.unreadable > 0 {
+            Some("some threads could not be read")
+        } else {
+            Some("process total includes threads that have exited")
+        }
+    }
+}
+
+/// Converts kernel plus user FILETIMEs to seconds
+fn cpu_seconds(kernel: &FILETIME, user: &FILETIME) -> f64 {
+    (filetime_to_u64(kernel) + filetime_to_u64(user)) as f64 / FILETIME_TICKS_PER_SECOND as f64
+}
+
+/// Sums the CPU time of a process's live threads and reads the process
+/// total alongside for comparison.
+///
+/// # Arguments
+/// * `pid` - The process ID to query
+///
+/// # Returns
+/// * `Option<ThreadCpuTimes>` - The times, or None if the process times
+///   or the thread snapshot cannot be read
+#[must_use]
+pub fn get_thread_cpu_times(pid: u32) -> Option<ThreadCpuTimes> {
+    let snapshot = SnapshotHandle::create_thread_snapshot().ok()?;
+    let mut times = ThreadCpuTimes::default();
+
+    // CRITICAL: dwSize must be set before the first call
+    let mut entry = THREADENTRY32 {
+        dwSize: mem::size_of::<THREADENTRY32>() as u32,
+        ..Default::default()
+    };
+
+    // SAFETY: We have a valid snapshot handle and properly initialized entry.
+    let mut success = unsafe { Thread32First(snapshot.as_raw(), &mut entry) };
+    while success.is_ok() {
+        if entry.th32OwnerProcessID == pid {
+            times.thread_count += 1;
+            match read_thread_seconds(entry.th32ThreadID) {
+                Some(seconds) => times.threads_seconds += seconds,
+                None => times.unreadable += 1,
+            }
+        }
+        // SAFETY: Same as above - valid handles and initialized struct.
+        success = unsafe { Thread32Next(snapshot.as_raw(), &mut entry) };
+    }
+
+    // Read the process total last, so it has had no less time to grow
+    let process = ProcessHandle::open(pid, PROCESS_QUERY_LIMITED_INFORMATION).ok()?;
+    let (mut creation, mut exit, mut kernel, mut user) = Default::default();
+    // SAFETY: GetProcessTimes is safe with a valid handle and pointers.
+    unsafe { GetProcessTimes(process.as_raw(), &mut creation, &mut exit, &mut kernel, &mut user) }
+        .ok()?;
+    times.process_seconds = cpu_seconds(&kernel, &user);
+
+    Some(times)
+}
+
+/// Reads one thread's kernel plus user time in seconds.
+fn read_thread_seconds(tid: u32) -> Option<f64> {
+    let thread = ThreadHandle::open(tid, THREAD_QUERY_LIMITED_INFORMATION).ok()?;
+    let (mut creation, mut exit, mut kernel, mut user) = Default::default();
+    // SAFETY: GetThreadTimes is safe with a valid handle and pointers.
+    unsafe { GetThreadTimes(thread.as_raw(), &mut creation, &mut exit, &mut kernel, &mut user) }
+        .ok()?;
+    Some(cpu_seconds(&kernel, &user))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_divergence_note() {
+        let times = |threads_seconds, process_seconds, unreadable| ThreadCpuTimes {
+            thread_count: 4,
+            unreadable,
+            threads_seconds,
+            process_seconds,
+        };
+        // Within 10% of the process total, or under a second apart
+        assert_eq!(times(95.0, 100.0, 0).divergence_note(), None);
+        assert_eq!(times(0.2, 0.9, 0).divergence_note(), None);
+
+        assert_eq!(
+            times(60.0, 100.0, 0).divergence_note(),
+            Some("process total includes threads that have exited")
+        );
+        assert_eq!(times(60.0, 100.0, 1).divergence_note(), Some("some threads could not be read"));
+        assert_eq!(
+            times(120.0, 100.0, 0).divergence_note(),
+            Some("threads exceed the process total; readings disagree")
+        );
+        assert_eq!(
+            times(72.0, 100.0, 0).summary(),
+            "00:01:12 over 4 threads (process 00:01:40; process total includes threads that have exited)"
+        );
+        assert_eq!(times(99.5, 100.0, 0).summary(), "00:01:39 over 4 threads (process 00:01:40)");
+    }
+}
@@ -3,10 +3,10 @@
 //! This module provides functions to get process creation time
 //! and calculate uptime.
 
-use windows::Win32::Foundation::{CloseHandle, FILETIME};
-use windows::Win32::System::Threading::{
-    GetProcessTimes, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
-};
+use windows::Win32::Foundation::FILETIME;
+use windows::Win32::System::Threading::{GetProcessTimes, PROCESS_QUERY_LIMITED_INFORMATION};
+
+use crate::ffi::ProcessHandle;
 
 /// Converts a FILETIME to a u64 (100-nanosecond intervals since 1601)
 fn filetime_to_u64(ft: &FILETIME) -> u64 {
@@ -39,16 +39,19 @@ pub fn get_current_filetime() -> u64 {
 /// # Returns
 /// * `Option<u64>` - Creation time as FILETIME, or None if inaccessible
 pub fn get_process_start_time(pid: u32) -> Option<u64> {
-    // SAFETY: OpenProcess is safe with valid parameters
-    let handle = unsafe {
-        OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid)
-    };
-
-    let handle = match handle {
-        Ok(h) => h,
-        Err(_) => return None,
-    };
+    ProcessHandle::open(pid, PROCESS_QUERY_LIMITED_INFORMATION)
+        .ok()
+        .and_then(|process| get_process_start_time_with(&process))
+}
 
+/// Gets the creation time of a process through an open handle.
+///
+/// # Arguments
+/// * `process` - Handle with at least limited query rights
+///
+/// # Returns
+/// * `Option<u64>` - Creation time as FILETIME, or None if the query fails
+pub fn get_process_start_time_with(process: &ProcessHandle) -> Option<u64> {
     let mut creation_time = FILETIME::default();
     let mut exit_time = FILETIME::default();
     let mut kernel_time = FILETIME::default();
@@ -57,7 +60,7 @@ pub fn get_process_start_time(pid: u32) -> Option<u64> {
     // SAFETY: GetProcessTimes is safe with valid handle and pointers
     let result = unsafe {
         GetProcessTimes(
-            handle,
+            process.as_raw(),
             &mut creation_time,
             &mut exit_time,
             &mut kernel_time,
@@ -65,11 +68,6 @@ pub fn get_process_start_time(pid: u32) -> Option<u64> {
         )
     };
 
-    // Always close the handle
-    unsafe {
-        let _ = CloseHandle(handle);
-    }
-
     if result.is_ok() {
         Some(filetime_to_u64(&creation_time))
     } else {
@@ -0,0 +1,90 @@
+//! 32-bit (WOW64) process detection
+//!
+//! Uses IsWow64Process2 where available (Windows 10 1511+) and falls back
+//! to IsWow64Process on older systems.
+
+use windows::core::s;
+use windows::Win32::Foundation::{BOOL, HANDLE};
+use windows::Win32::System::LibraryLoader::{GetModuleHandleA, GetProcAddress};
+use windows::Win32::System::SystemInformation::{IMAGE_FILE_MACHINE, IMAGE_FILE_MACHINE_UNKNOWN};
+use windows::Win32::System::Threading::{IsWow64Process, PROCESS_QUERY_LIMITED_INFORMATION};
+
+use crate::ffi::ProcessHandle;
+
+/// Type alias for the IsWow64Process2 function signature
+type IsWow64Process2Fn =
+    unsafe extern "system" fn(HANDLE, *mut IMAGE_FILE_MACHINE, *mut IMAGE_FILE_MACHINE) -> BOOL;
+
+/// Get the IsWow64Process2 function from kernel32, if this Windows has it
+fn get_is_wow64_process2() -> Option<IsWow64Process2Fn> {
+    unsafe {
+        let module = GetModuleHandleA(s!("kernel32.dll")).ok()?;
+        let proc = GetProcAddress(module, s!("IsWow64Process2"))?;
+        Some(std::mem::transmute::<unsafe extern "system" fn() -> isize, IsWow64Process2Fn>(proc))
+    }
+}
+
+/// Checks whether a process is running under WOW64 (a 32-bit process
+/// on a 64-bit OS).
+///
+/// # Arguments
+/// * `pid` - The process ID to query
+///
+/// # Returns
+/// * `Option<bool>` - `Some(true)` for 32-bit processes, `Some(false)` for
+///   native ones, or None if inaccessible
+#[must_use]
+pub fn is_wow64_process(pid: u32) -> Option<bool> {
+    ProcessHandle::open(pid, PROCESS_QUERY_LIMITED_INFORMATION)
+        .ok()
+        .and_then(|process| is_wow64_process_with(&process))
+}
+
+/// Checks for WOW64 through an open process handle.
+///
+/// # Arguments
+/// * `process` - Handle with at least limited query rights
+///
+/// # Returns
+/// * `Option<bool>` - `Some(true)` for 32-bit processes, `Some(false)` for
+///   native ones, or None if the query fails
+#[must_use]
+pub fn is_wow64_process_with(process: &ProcessHandle) -> Option<bool> {
+    let handle = process.as_raw();
+
+    // SAFETY: Both calls only write to the local out-parameters.
+    unsafe {
+        match get_is_wow64_process2() {
+            Some(is_wow64_process2) => {
+                let mut process_machine = IMAGE_FILE_MACHINE_UNKNOWN;
+                let mut native_machine = IMAGE_FILE_MACHINE_UNKNOWN;
+                is_wow64_process2(handle, &mut process_machine, &mut native_machine)
+                    .as_bool()
+                    // UNKNOWN means the process is not running under WOW64
+                    .then_some(process_machine != IMAGE_FILE_MACHINE_UNKNOWN)
+            }
+            None => {
+                let mut is_wow64 = BOOL::default();
+                IsWow64Process(handle, &mut is_wow64)
+                    .ok()
+                    .map(|_| is_wow64.as_bool())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_process_arch() {
+        // Our own process is always queryable, and is WOW64 exactly when
+        // a 32-bit build runs on a 64-bit OS.
+        let is_wow64 = is_wow64_process(std::process::id());
+        assert!(is_wow64.is_some());
+        if cfg!(target_pointer_width = "64") {
+            assert_eq!(is_wow64, Some(false));
+        }
+    }
+}
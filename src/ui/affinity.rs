@@ -8,14 +8,19 @@ use crossterm::{
     style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
 };
 
-use crate::app::App;
-use crate::constants::{AFFINITY_DIALOG_WIDTH, DIALOG_MARGIN};
+use crate::app::{affinity_grid_columns, App};
+use crate::constants::{AFFINITY_CORE_CELL_WIDTH, AFFINITY_DIALOG_WIDTH, DIALOG_MARGIN};
+
+use super::utils::{display_width, truncate_string};
 
 /// Renders the CPU affinity dialog overlay.
 ///
 /// Displays a centered dialog allowing users to select which CPU cores
 /// a process can run on. Shows checkboxes for each core, with the current
 /// affinity highlighted. Supports keyboard navigation and toggle.
+/// The grid gets as many columns as the terminal width allows (widening
+/// the dialog if needed); when it is still too tall, it is split into
+/// pages and the page holding the cursor is shown.
 pub fn render_affinity_dialog(
     stdout: &mut io::Stdout,
     app: &App,
@@ -27,13 +32,22 @@ pub fn render_affinity_dialog(
     let total_cores = app.affinity_total_cores as usize;
 
     // Calculate dialog dimensions
-    // Each core takes about 12 chars: "[X] Core N  "
-    // Display 4 cores per row
-    let cores_per_row = 4.min(total_cores);
-    let num_rows = (total_cores + cores_per_row - 1) / cores_per_row;
-    
-    let box_width = AFFINITY_DIALOG_WIDTH.min(width.saturating_sub(DIALOG_MARGIN));
+    let cores_per_row = affinity_grid_columns(total_cores, width);
+    let num_rows = total_cores.div_ceil(cores_per_row);
+    // Borders plus a space either side of the grid
+    let grid_box_width = cores_per_row * AFFINITY_CORE_CELL_WIDTH + 4;
+
+    let box_width = AFFINITY_DIALOG_WIDTH
+        .max(grid_box_width)
+        .min(width.saturating_sub(DIALOG_MARGIN));
     let box_height = (num_rows + 8).min(height.saturating_sub(DIALOG_MARGIN)); // +8 for header, footer, padding
+
+    // Page through the grid when it is taller than the dialog
+    let rows_per_page = box_height.saturating_sub(8).max(1);
+    let page_count = num_rows.saturating_sub(1) / rows_per_page + 1;
+    let page = (app.affinity_selected_core / cores_per_row) / rows_per_page;
+    let first_row = page * rows_per_page;
+    let last_row = (first_row + rows_per_page).min(num_rows);
     let start_x = (width.saturating_sub(box_width)) / 2;
     let start_y = (height.saturating_sub(box_height)) / 2;
 
@@ -54,12 +68,9 @@ pub fn render_affinity_dialog(
     // Helper to draw a bordered line
     let draw_line = |stdout: &mut io::Stdout, y: usize, content: &str, fg: Color, bg: Color| -> io::Result<()> {
         execute!(stdout, MoveTo(start_x as u16, y as u16))?;
-        let truncated = if content.len() > inner_width {
-            &content[..inner_width]
-        } else {
-            content
-        };
-        let padded = format!("{:<w$}", truncated, w = inner_width);
+        let truncated = truncate_string(content, inner_width);
+        let padding = inner_width.saturating_sub(display_width(&truncated));
+        let padded = format!("{}{:w$}", truncated, "", w = padding);
         execute!(
             stdout,
             SetBackgroundColor(bg),
@@ -89,7 +100,16 @@ pub fn render_affinity_dialog(
     y += 1;
 
     // Title
-    let title = format!(" Set CPU Affinity: {} (PID: {}) ", name, pid);
+    let title = if !app.affinity_batch.is_empty() {
+        format!(" Set CPU Affinity: {} selected processes ", app.affinity_batch.len())
+    } else if app.affinity_group_count > 1 {
+        format!(
+            " Set CPU Affinity: {} (PID: {}, Group {}/{}) ",
+            name, pid, app.affinity_group, app.affinity_group_count
+        )
+    } else {
+        format!(" Set CPU Affinity: {} (PID: {}) ", name, pid)
+    };
     draw_line(stdout, y, &title, Color::Yellow, Color::DarkBlue)?;
     y += 1;
 
@@ -108,46 +128,34 @@ pub fn render_affinity_dialog(
 
     // Current selection info
     let selected_count = app.affinity_mask.count_ones();
-    let info = format!(" Selected: {}/{} cores", selected_count, total_cores);
+    let info = if page_count > 1 {
+        format!(
+            " Selected: {}/{} cores   Page {}/{}",
+            selected_count, total_cores, page + 1, page_count
+        )
+    } else {
+        format!(" Selected: {}/{} cores", selected_count, total_cores)
+    };
     draw_line(stdout, y, &info, Color::Cyan, Color::DarkBlue)?;
     y += 1;
 
-    // Empty line
-    draw_line(stdout, y, "", Color::White, Color::DarkBlue)?;
+    // Batch target count, or an empty line
+    let batch_info = if app.affinity_batch.is_empty() {
+        String::new()
+    } else {
+        format!(" Applying to {} processes (starting from {})", app.affinity_batch.len(), name)
+    };
+    draw_line(stdout, y, &batch_info, Color::Yellow, Color::DarkBlue)?;
     y += 1;
 
     // Render cores in a grid
-    for row in 0..num_rows {
-        let mut line = String::from(" ");
-        
-        for col in 0..cores_per_row {
-            let core_idx = row * cores_per_row + col;
-            if core_idx >= total_cores {
-                break;
-            }
-
-            let is_selected = app.is_core_selected(core_idx);
-            let is_cursor = core_idx == app.affinity_selected_core;
-
-            let checkbox = if is_selected { "[X]" } else { "[ ]" };
-            
-            // Build the core label
-            let core_label = if is_cursor {
-                format!(">{}Core {:<2}", checkbox, core_idx)
-            } else {
-                format!(" {}Core {:<2}", checkbox, core_idx)
-            };
-
-            line.push_str(&format!("{:<14}", core_label));
-        }
-
-        // Render the line with proper coloring
+    for row in first_row..last_row {
         execute!(stdout, MoveTo(start_x as u16, y as u16))?;
         execute!(
             stdout,
             SetBackgroundColor(Color::DarkBlue),
             SetForegroundColor(Color::White),
-            Print("│"),
+            Print("│ "),
         )?;
 
         // Render each core with appropriate color
@@ -184,12 +192,11 @@ pub fn render_affinity_dialog(
                 Print(format!("Core {:<2}  ", core_idx)),
             )?;
             
-            char_pos += 14;
+            char_pos += AFFINITY_CORE_CELL_WIDTH;
         }
 
-        // Fill remaining space to align right border
-        // char_pos is total chars rendered, add 5 for leading space, subtract from inner_width
-        let remaining = inner_width.saturating_sub(char_pos + 1) + 5;
+        // Fill remaining space (after the leading space) to align right border
+        let remaining = inner_width.saturating_sub(char_pos + 1);
         execute!(
             stdout,
             SetBackgroundColor(Color::DarkBlue),
@@ -219,7 +226,7 @@ pub fn render_affinity_dialog(
     y += 1;
 
     // Help line 1
-    let help1 = " ←/→: Select   Space: Toggle   A: All   N: None";
+    let help1 = " Arrows: Select   Space: Toggle   A: All   N: None";
     draw_line(stdout, y, help1, Color::DarkGrey, Color::DarkBlue)?;
     y += 1;
 
@@ -7,17 +7,44 @@ use crossterm::{
     style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
 };
 
-use crate::app::App;
-use crate::constants::DISPLAY_NAME;
-use crate::system::{format_bytes, is_elevated, SystemMemoryInfo};
+use crate::app::{App, SortColumn};
+use crate::constants::{DISPLAY_NAME, USAGE_HISTORY_SAMPLES};
+use crate::system::{format_bytes, format_rate, is_elevated, SystemMemoryInfo};
 
-use super::utils::truncate_string;
+use super::layout::column_headers;
+use super::utils::{display_width, sparkline, truncate_string};
+
+/// Renders a red banner warning that system commit is near its limit.
+///
+/// # Arguments
+/// * `commit_percent` - Commit charge as a percent of the commit limit
+pub fn render_commit_warning(stdout: &mut io::Stdout, commit_percent: f64, width: usize) -> io::Result<()> {
+    let line = format!(
+        " ⚠ System commit at {:.0}% — allocations may fail",
+        commit_percent
+    );
+    let line = truncate_string(&line, width);
+    execute!(
+        stdout,
+        SetBackgroundColor(Color::DarkRed),
+        SetForegroundColor(Color::White),
+        Print(format!(
+            "{}{:pad$}",
+            line,
+            "",
+            pad = width.saturating_sub(display_width(&line))
+        )),
+        ResetColor,
+        Print("\r\n")
+    )
+}
 
 /// Renders the application header with admin status indicator.
 ///
-/// Displays the application title and whether it's running with
-/// elevated (Administrator) privileges.
-pub fn render_header(stdout: &mut io::Stdout, width: usize) -> io::Result<()> {
+/// Displays the application title, CPU and memory history sparklines
+/// sized to the free space, and whether it's running with elevated
+/// (Administrator) privileges.
+pub fn render_header(stdout: &mut io::Stdout, app: &App, width: usize) -> io::Result<()> {
     let admin_indicator = if is_elevated() {
         ("[Administrator]", Color::Green)
     } else {
@@ -25,7 +52,20 @@ pub fn render_header(stdout: &mut io::Stdout, width: usize) -> io::Result<()> {
     };
 
     let title = format!(" {}", DISPLAY_NAME);
-    let spacing = width.saturating_sub(title.len() + admin_indicator.0.len() + 2);
+    let free = width.saturating_sub(title.len() + admin_indicator.0.len() + 2);
+
+    // Two labelled graphs ("  CPU " / "  MEM " plus a trailing gap) share the free space
+    let graph_width = (free.saturating_sub(14) / 2).min(USAGE_HISTORY_SAMPLES);
+    let graphs = if graph_width >= 8 {
+        format!(
+            "  CPU {}  MEM {}  ",
+            sparkline(&app.cpu_history, graph_width),
+            sparkline(&app.memory_history, graph_width)
+        )
+    } else {
+        String::new()
+    };
+    let spacing = free.saturating_sub(display_width(&graphs));
 
     execute!(
         stdout,
@@ -33,6 +73,8 @@ pub fn render_header(stdout: &mut io::Stdout, width: usize) -> io::Result<()> {
         SetForegroundColor(Color::White),
         Print(title),
         Print(format!("{:spacing$}", "", spacing = spacing)),
+        SetForegroundColor(Color::Cyan),
+        Print(graphs),
         SetForegroundColor(admin_indicator.1),
         Print(admin_indicator.0),
         Print(" "),
@@ -43,8 +85,8 @@ pub fn render_header(stdout: &mut io::Stdout, width: usize) -> io::Result<()> {
 
 /// Renders the system statistics line.
 ///
-/// Shows CPU usage, memory usage, process count, current sort column,
-/// and refresh interval.
+/// Shows CPU usage, memory usage, process count, total threads and
+/// handles, current sort column, and refresh interval.
 pub fn render_system_stats(
     stdout: &mut io::Stdout,
     app: &App,
@@ -55,32 +97,45 @@ pub fn render_system_stats(
     let mem_str = if let Some(ref info) = mem_info {
         format!(
             "Memory: {} / {} ({:.0}%)",
-            format_bytes(info.used_physical()),
-            format_bytes(info.total_physical),
+            format_bytes(info.used_physical(), app.units),
+            format_bytes(info.total_physical, app.units),
             info.memory_load_percent
         )
     } else {
         "Memory: N/A".to_string()
     };
-    let proc_count = if app.filter.is_empty() {
+    let proc_count = if !app.is_filtered() {
         format!("Processes: {}", app.processes.len())
     } else {
         format!("Showing: {}/{}", app.filtered_processes.len(), app.processes.len())
     };
+    let totals_str = format!("Threads: {}  Handles: {}", app.total_threads, app.total_handles);
     let sort_arrow = if app.sort_ascending { "↑" } else { "↓" };
     let sort_str = if app.tree_view_mode {
-        "View: Tree".to_string()
+        format!("View: Tree, Sort: {} {}", app.sort_column.name(), sort_arrow)
     } else {
         format!("Sort: {} {}", app.sort_column.name(), sort_arrow)
     };
     let refresh_str = format!("Refresh: {}", app.format_refresh_interval());
+    let tags = app.active_mode_tags();
+    let modes_str = if tags.is_empty() {
+        String::new()
+    } else {
+        let joined: String = tags.iter().map(|t| format!("[{}]", t)).collect();
+        format!("  |  {}", joined)
+    };
+    let selected_str = if app.selected_pids.is_empty() {
+        String::new()
+    } else {
+        format!("  |  Selected: {}", app.selected_pids.len())
+    };
 
     execute!(
         stdout,
         SetForegroundColor(Color::Cyan),
         Print(format!(
-            " {}  |  {}  |  {}  |  {}  |  {}",
-            cpu_str, mem_str, proc_count, sort_str, refresh_str
+            " {}  |  {}  |  {}  |  {}  |  {}  |  {}{}{}",
+            cpu_str, mem_str, proc_count, totals_str, sort_str, refresh_str, selected_str, modes_str
         )),
         ResetColor,
         Print(format!("{:width$}\r\n", "", width = width.saturating_sub(100)))
@@ -89,8 +144,8 @@ pub fn render_system_stats(
 
 /// Renders the filter bar when active or showing current filter.
 ///
-/// In filter mode, displays an input field with cursor.
-/// Otherwise, shows the current filter value if set.
+/// In filter or find mode, displays an input field with cursor.
+/// Otherwise, shows the current filter and find text if set.
 pub fn render_filter_bar(stdout: &mut io::Stdout, app: &App, width: usize) -> io::Result<()> {
     if app.view_mode.is_filter_input() {
         execute!(
@@ -98,44 +153,178 @@ pub fn render_filter_bar(stdout: &mut io::Stdout, app: &App, width: usize) -> io
             SetBackgroundColor(Color::DarkYellow),
             SetForegroundColor(Color::Black),
             Print(format!(
-                " Filter: {}█{:width$}",
+                " Filter [{}]: {}█{:width$}",
+                app.filter_scope.label(),
                 app.filter,
                 "",
-                width = width.saturating_sub(app.filter.len() + 10)
+                width = width.saturating_sub(app.filter.len() + app.filter_scope.label().len() + 13)
             )),
             ResetColor,
             Print("\r\n")
         )
-    } else if !app.filter.is_empty() {
+    } else if app.view_mode.is_find_input() {
         execute!(
             stdout,
-            SetForegroundColor(Color::Yellow),
+            SetBackgroundColor(Color::DarkMagenta),
+            SetForegroundColor(Color::White),
             Print(format!(
-                " Filter: \"{}\" (press / to edit, Esc to clear)",
-                app.filter
+                " Find: {}█{:width$}",
+                app.find,
+                "",
+                width = width.saturating_sub(display_width(&app.find) + 8)
             )),
             ResetColor,
             Print("\r\n")
         )
     } else {
-        execute!(stdout, Print("\r\n"))
+        let mut parts = Vec::new();
+        if !app.filter.is_empty() {
+            parts.push(format!(
+                " Filter [{}]: \"{}\" (press / to edit, Esc to clear)",
+                app.filter_scope.label(),
+                app.filter
+            ));
+        }
+        if !app.find.is_empty() {
+            parts.push(format!(" Find: \"{}\" (F3/Shift+F3: next/prev, Esc to clear)", app.find));
+        }
+        if app.is_filtered() {
+            let totals = app.filtered_totals();
+            parts.push(format!(
+                " Filtered total: {:.1}% CPU, {}",
+                totals.cpu_percent,
+                format_bytes(totals.memory_bytes, app.units)
+            ));
+        }
+        execute!(
+            stdout,
+            SetForegroundColor(Color::Yellow),
+            Print(truncate_string(&parts.join("  |"), width)),
+            ResetColor,
+            Print("\r\n")
+        )
     }
 }
 
+/// Lays out the column headers, marking the sort column with its arrow.
+///
+/// Each segment includes the separator before it, so the arrow can take a
+/// separator space and the columns stay aligned with the rows. With
+/// `disk_totals`, the disk headers drop their "/s".
+///
+/// # Arguments
+/// * `widths` - Fixed column widths from `column_widths`, shared with the rows
+///
+/// # Returns
+/// The header text split into segments, each flagged if it is the sort column.
+fn column_header_segments(
+    compact: bool,
+    widths: &[usize],
+    sort_column: SortColumn,
+    ascending: bool,
+    disk_totals: bool,
+) -> Vec<(String, bool)> {
+    let (headers, gap) = column_headers(compact);
+    let arrow = if ascending { "↑" } else { "↓" };
+
+    headers
+        .iter()
+        .zip(widths.iter().chain(std::iter::once(&0)))
+        .enumerate()
+        .map(|(i, (&(label, _, sorts), &width))| {
+            let sep = if i == 0 { 1 } else { gap };
+            let active = sorts.contains(&sort_column);
+            let is_disk = sorts.contains(&SortColumn::DiskReadRate) || sorts.contains(&SortColumn::DiskWriteRate);
+            let label = if disk_totals && is_disk { label.trim_end_matches("/s") } else { label };
+            let text = if width == 0 {
+                // Name is left-aligned, so its arrow goes after it
+                let suffix = if active { format!(" {}", arrow) } else { String::new() };
+                format!("{:sep$}{}{}", "", label, suffix, sep = sep)
+            } else {
+                let label = if active { format!("{}{}", arrow, label) } else { label.to_string() };
+                format!("{:>w$}", label, w = sep + width)
+            };
+            (text, active)
+        })
+        .collect()
+}
+
 /// Renders the column headers for the process list.
 ///
-/// Displays headers for: PID, Priority, Threads, Handles, Uptime,
-/// Memory, CPU%, Read/s, Write/s, and Name.
-pub fn render_column_headers(stdout: &mut io::Stdout, width: usize) -> io::Result<()> {
-    let header = format!(
-        " {:>7}  {:>8}  {:>5}  {:>6}  {:>9}  {:>10}  {:>6}  {:>9}  {:>9}  {}",
-        "PID", "Priority", "Thrd", "Hndls", "Uptime", "Memory", "CPU%", "Read/s", "Write/s", "Name"
+/// Displays headers for: PID, Session, Priority, Base priority, Threads, Handles,
+/// Uptime, Memory, Mem%, Mem/s, CPU%, CPU Time, Read/s, Write/s, Net/s, Power, and Name.
+/// The compact layout matches `render_process_list`'s compact rows (no Mem% or Power).
+/// The sort column's header is highlighted and carries the sort direction arrow.
+/// The disk headers lose their "/s" while `d` shows lifetime totals.
+/// Columns are as wide as `widths`, the same widths the rows are padded to.
+pub fn render_column_headers(
+    stdout: &mut io::Stdout,
+    app: &App,
+    widths: &[usize],
+    width: usize,
+) -> io::Result<()> {
+    let segments = column_header_segments(
+        app.compact_rows,
+        widths,
+        app.sort_column,
+        app.sort_ascending,
+        app.disk_totals,
     );
+
+    execute!(stdout, SetBackgroundColor(Color::DarkGrey))?;
+    let mut used = 0;
+    for (text, active) in &segments {
+        let color = if *active { Color::Yellow } else { Color::White };
+        execute!(stdout, SetForegroundColor(color), Print(text))?;
+        used += display_width(text);
+    }
     execute!(
         stdout,
-        SetBackgroundColor(Color::DarkGrey),
-        SetForegroundColor(Color::White),
-        Print(format!("{:width$}", header, width = width)),
+        Print(format!("{:pad$}", "", pad = width.saturating_sub(used))),
+        ResetColor,
+        Print("\r\n")
+    )
+}
+
+/// Renders the `--debug` line: how long the last refresh and frame took,
+/// summed per-process CPU% against the system CPU, and total disk
+/// read/write rates.
+pub fn render_debug_line(stdout: &mut io::Stdout, app: &App, width: usize) -> io::Result<()> {
+    let totals = app.metric_totals();
+    let line = format!(
+        " Debug: refresh: {}ms render: {}ms  |  Σ process CPU {:.1}% vs system {:.1}% (Δ {:+.1})  |  Σ disk read {}  write {}",
+        app.refresh_duration.as_millis(),
+        app.render_duration.as_millis(),
+        totals.cpu_percent,
+        app.system_cpu,
+        totals.cpu_percent - app.system_cpu,
+        format_rate(totals.disk_read_rate, app.units),
+        format_rate(totals.disk_write_rate, app.units)
+    );
+    execute!(
+        stdout,
+        SetForegroundColor(Color::Magenta),
+        Print(format!("{:width$}", truncate_string(&line, width), width = width)),
+        ResetColor,
+        Print("\r\n")
+    )
+}
+
+/// Renders the selected process's executable path as a dimmed line.
+pub fn render_path_line(stdout: &mut io::Stdout, app: &App, width: usize) -> io::Result<()> {
+    let path_display = app
+        .selected_process()
+        .and_then(|p| p.path.as_ref())
+        .map(|p| {
+            let space = width.saturating_sub(10);
+            let space = app.path_width.map_or(space, |limit| space.min(limit));
+            format!(" Path: {}", truncate_string(p, space))
+        })
+        .unwrap_or_else(|| " Path: <access denied>".to_string());
+    execute!(
+        stdout,
+        SetForegroundColor(Color::DarkGrey),
+        Print(format!("{:width$}", path_display, width = width)),
         ResetColor,
         Print("\r\n")
     )
@@ -145,20 +334,60 @@ pub fn render_column_headers(stdout: &mut io::Stdout, width: usize) -> io::Resul
 ///
 /// Shows kill confirmation dialog when in confirm mode,
 /// error messages when present, or keyboard shortcuts otherwise.
-pub fn render_footer(stdout: &mut io::Stdout, app: &App, width: usize) -> io::Result<()> {
+/// With no message, the status line shows the selected process's path
+/// unless `path_shown` says it already has its own line.
+pub fn render_footer(
+    stdout: &mut io::Stdout,
+    app: &App,
+    width: usize,
+    path_shown: bool,
+) -> io::Result<()> {
     // Error/status message or confirmation dialog
     if app.view_mode.is_confirm_kill() {
-        if let (Some(pid), Some(ref name)) = (app.pending_kill_pid, &app.pending_kill_name) {
+        if !app.pending_kill_batch.is_empty() {
             execute!(
                 stdout,
                 SetBackgroundColor(Color::DarkRed),
                 SetForegroundColor(Color::White),
                 Print(format!(
-                    " Kill process '{}' (PID {})? [Y/N] {:width$}",
-                    truncate_string(name, 30),
-                    pid,
+                    " Kill {} selected processes? [Y/N] {:width$}",
+                    app.pending_kill_batch.len(),
                     "",
-                    width = width.saturating_sub(60)
+                    width = width.saturating_sub(40)
+                )),
+                ResetColor,
+                Print("\r\n")
+            )?;
+        } else if let (Some(pid), Some(ref name)) = (app.pending_kill_pid, &app.pending_kill_name) {
+            let action = if app.pending_kill_hold.is_some() {
+                "Kill and hold"
+            } else {
+                "Kill process"
+            };
+            // What the kill takes down, from the latest refresh
+            let impact = app
+                .processes
+                .iter()
+                .find(|p| p.info.pid == pid)
+                .map(|entry| format!(" — {}", entry.kill_impact(app.units)))
+                .unwrap_or_default();
+            let prompt = format!(
+                " {} '{}' (PID {}){}? [Y/N]",
+                action,
+                truncate_string(name, 30),
+                pid,
+                impact
+            );
+            let prompt = truncate_string(&prompt, width.saturating_sub(1));
+            execute!(
+                stdout,
+                SetBackgroundColor(Color::DarkRed),
+                SetForegroundColor(Color::White),
+                Print(format!(
+                    "{} {:pad$}",
+                    prompt,
+                    "",
+                    pad = width.saturating_sub(display_width(&prompt) + 1)
                 )),
                 ResetColor,
                 Print("\r\n")
@@ -166,29 +395,52 @@ pub fn render_footer(stdout: &mut io::Stdout, app: &App, width: usize) -> io::Re
         } else {
             execute!(stdout, Print("\r\n"))?;
         }
-    } else if let Some(ref msg) = app.error_message {
+    } else if app.view_mode.is_confirm_realtime() {
+        let target = match app.pending_realtime.as_slice() {
+            [(pid, name, _)] => format!("'{}' (PID {})", truncate_string(name, 30), pid),
+            targets => format!("{} selected processes", targets.len()),
+        };
+        let prompt = truncate_string(
+            &format!(
+                " Set {} to Realtime? It can starve input and system threads and freeze Windows. [Y/N]",
+                target
+            ),
+            width,
+        );
         execute!(
             stdout,
-            SetForegroundColor(Color::Yellow),
-            Print(format!(" {}", truncate_string(msg, width - 2))),
+            SetBackgroundColor(Color::DarkRed),
+            SetForegroundColor(Color::White),
+            Print(format!(
+                "{}{:pad$}",
+                prompt,
+                "",
+                pad = width.saturating_sub(display_width(&prompt))
+            )),
             ResetColor,
             Print("\r\n")
         )?;
-    } else {
-        // Show selected process path
-        let path_display = app
-            .filtered_processes
-            .get(app.selected_index)
-            .and_then(|p| p.path.as_ref())
-            .map(|p| format!(" Path: {}", truncate_string(p, width.saturating_sub(10))))
-            .unwrap_or_else(|| " Path: <access denied>".to_string());
+    } else if app.view_mode.is_confirm_quit() {
         execute!(
             stdout,
-            SetForegroundColor(Color::DarkGrey),
-            Print(format!("{:width$}", path_display, width = width)),
+            SetBackgroundColor(Color::DarkRed),
+            SetForegroundColor(Color::White),
+            Print(format!(" Quit? [Y/N] {:width$}", "", width = width.saturating_sub(13))),
             ResetColor,
             Print("\r\n")
         )?;
+    } else if let Some(ref msg) = app.error_message {
+        execute!(
+            stdout,
+            SetForegroundColor(Color::Yellow),
+            Print(format!(" {}", truncate_string(msg, width - 2))),
+            ResetColor,
+            Print("\r\n")
+        )?;
+    } else if path_shown {
+        execute!(stdout, Print(format!("{:width$}\r\n", "", width = width)))?;
+    } else {
+        render_path_line(stdout, app, width)?;
     }
 
     // Help line
@@ -200,24 +452,126 @@ pub fn render_footer(stdout: &mut io::Stdout, app: &App, width: usize) -> io::Re
             Print(format!("{:width$}", " Kill process? Y:Confirm | N/Esc:Cancel", width = width)),
             ResetColor,
         )?;
+    } else if app.view_mode.is_confirm_realtime() {
+        execute!(
+            stdout,
+            SetBackgroundColor(Color::DarkRed),
+            SetForegroundColor(Color::White),
+            Print(format!("{:width$}", " Realtime priority? Y:Confirm | N/Esc:Cancel", width = width)),
+            ResetColor,
+        )?;
+    } else if app.view_mode.is_confirm_quit() {
+        execute!(
+            stdout,
+            SetBackgroundColor(Color::DarkRed),
+            SetForegroundColor(Color::White),
+            Print(format!("{:width$}", " Quit? Y/Ctrl+C:Quit | N/Esc:Cancel", width = width)),
+            ResetColor,
+        )?;
     } else if app.view_mode.is_filter_input() {
         execute!(
             stdout,
             SetBackgroundColor(Color::DarkYellow),
             SetForegroundColor(Color::Black),
-            Print(format!("{:width$}", " Type to filter | Enter:Apply | Esc:Cancel", width = width)),
+            Print(format!("{:width$}", " Type to filter | Tab:Fields | Enter:Apply | Esc:Cancel", width = width)),
+            ResetColor,
+        )?;
+    } else if app.view_mode.is_find_input() {
+        execute!(
+            stdout,
+            SetBackgroundColor(Color::DarkMagenta),
+            SetForegroundColor(Color::White),
+            Print(format!("{:width$}", " Type to highlight | Enter:Jump to match | Esc:Clear", width = width)),
             ResetColor,
         )?;
     } else {
         let help_line = " ?:Help | q:Quit | Enter:Details | k:Kill | p:Suspend | t:Tree | s:Sort | /:Filter | +/-:Priority";
+        let mut help_width = width;
+        if app.no_confirm_kill {
+            // Persistent reminder that kills happen without confirmation
+            let indicator = " [NO-CONFIRM]";
+            help_width = width.saturating_sub(indicator.len());
+            execute!(
+                stdout,
+                SetBackgroundColor(Color::DarkRed),
+                SetForegroundColor(Color::White),
+                Print(indicator),
+            )?;
+        }
         execute!(
             stdout,
             SetBackgroundColor(Color::DarkBlue),
             SetForegroundColor(Color::White),
-            Print(format!("{:width$}", truncate_string(help_line, width), width = width)),
+            Print(format!("{:width$}", truncate_string(help_line, help_width), width = help_width)),
             ResetColor,
         )?;
     }
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::layout::{column_widths, row_cells};
+
+    fn joined(segments: &[(String, bool)]) -> String {
+        segments.iter().map(|(text, _)| text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_column_headers_keep_row_layout() {
+        // With nothing on screen the columns keep their table widths
+        let full_widths = column_widths(false, &[]);
+        let compact_widths = column_widths(true, &[]);
+
+        // Children has no column of its own, so nothing is marked
+        let full = column_header_segments(false, &full_widths, SortColumn::Children, false, false);
+        assert_eq!(
+            joined(&full),
+            format!(
+                " {:>7}  {:>4}  {:>8}  {:>4}  {:>5}  {:>6}  {:>9}  {:>10}  {:>6}  {:>12}  {:>6}  {:>9}  {:>11}  {:>11}  {:>11}  {:>6}  {}",
+                "PID", "Sess", "Priority", "Base", "Thrd", "Hndls", "Uptime", "Memory", "Mem%", "Mem/s",
+                "CPU%", "CPU Time", "Read/s", "Write/s", "Net/s", "Power", "Name"
+            )
+        );
+        assert!(full.iter().all(|(_, active)| !active));
+
+        // The arrow takes a separator space; the Name column starts where it did
+        let marked = column_header_segments(false, &full_widths, SortColumn::Priority, false, false);
+        assert_eq!(display_width(&joined(&marked)), display_width(&joined(&full)));
+        assert_eq!(marked[2], (" ↓Priority".to_string(), true));
+
+        let compact = column_header_segments(true, &compact_widths, SortColumn::DiskWriteRate, true, false);
+        assert!(compact.iter().any(|(text, active)| *active && text.ends_with("↑Disk/s")));
+        let name = column_header_segments(true, &compact_widths, SortColumn::Name, true, false);
+        assert_eq!(name.last().unwrap(), &(" Name ↑".to_string(), true));
+
+        // Lifetime totals keep the column widths but drop "/s"
+        let totals = column_header_segments(false, &full_widths, SortColumn::DiskReadRate, false, true);
+        assert_eq!(display_width(&joined(&totals)), display_width(&joined(&full)));
+        assert!(totals.iter().any(|(text, active)| *active && text.ends_with("↓Read")));
+        assert!(totals.iter().any(|(text, _)| text.ends_with(" Write")));
+        assert!(totals.iter().any(|(text, _)| text.ends_with(" Net/s")));
+    }
+
+    #[test]
+    fn test_column_headers_align_with_row_cells() {
+        for compact in [false, true] {
+            let mut values = vec!["1".to_string(); column_widths(compact, &[]).len()];
+            // A value wider than its table width widens the header too
+            values[0] = "123456789".to_string();
+            let widths = column_widths(compact, std::slice::from_ref(&values));
+            let headers = column_header_segments(compact, &widths, SortColumn::Cpu, false, false);
+            let cells = row_cells(compact, &widths, ' ', &values);
+            assert_eq!(cells.len(), headers.len());
+            // Every fixed column is exactly as wide as its header, and the
+            // name starts in the same place
+            for ((header, _), cell) in headers.iter().zip(&cells).take(headers.len() - 1) {
+                assert_eq!(display_width(header), display_width(cell), "{:?}", header);
+            }
+            let (name, _) = headers.last().unwrap();
+            assert!(name.starts_with(cells.last().unwrap().as_str()));
+        }
+    }
+}
@@ -7,15 +7,18 @@ use crossterm::{
     style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
 };
 
-use crate::app::{App, ViewMode};
-use crate::system::{format_bytes, format_uptime};
+use crate::app::{App, ModuleView, ViewMode};
+use crate::system::{
+    format_bytes, format_cpu_time, format_on_off, format_rate, format_session, format_uptime,
+    ByteUnits, ProcessDetails, SignatureStatus,
+};
 
-use super::utils::{format_rate, truncate_string};
+use super::utils::truncate_string;
 
 /// Renders the detailed process view.
 ///
 /// Shows comprehensive information about a selected process including:
-/// - Basic info (PID, name, path, command line)
+/// - Basic info (PID, name, path, command line, hosted services)
 /// - Resource usage (CPU, memory, threads, handles)
 /// - Disk I/O rates
 /// - CPU affinity
@@ -27,15 +30,108 @@ pub fn render_detail_view(
     width: usize,
     height: usize,
 ) -> io::Result<()> {
-    let details = match &app.detail_view_data {
-        Some(d) => d,
+    let lines = match &app.detail_view_data {
+        Some(details) => detail_lines(details, &app.module_view, width, app.units),
         None => {
             app.view_mode = ViewMode::ProcessList;
             return Ok(());
         }
     };
 
-    // Build content lines
+    // Calculate visible area
+    let header_lines_count = 2;
+    let footer_lines_count = 2;
+    let visible_rows = height.saturating_sub(header_lines_count + footer_lines_count);
+    
+    // Clamp scroll offset
+    let max_scroll = lines.len().saturating_sub(visible_rows);
+    if app.detail_scroll_offset > max_scroll {
+        app.detail_scroll_offset = max_scroll;
+    }
+    
+    // Render header, tagged when the data is frozen
+    let (title, title_color) = if app.detail_view_exited {
+        (" Process Details View (process exited; last-known data)", Color::DarkRed)
+    } else {
+        (" Process Details View", Color::DarkMagenta)
+    };
+    execute!(
+        stdout,
+        SetBackgroundColor(title_color),
+        SetForegroundColor(Color::White),
+        Print(format!("{:width$}", title, width = width)),
+        ResetColor,
+        Print("\r\n"),
+        SetForegroundColor(Color::DarkGrey),
+        Print(format!(" Scroll: {}/{} lines  |  ↑↓/PgUp/PgDn: Scroll  |  Esc/Enter: Close",
+            app.detail_scroll_offset + 1, lines.len())),
+        ResetColor,
+        Print(format!("{:width$}\r\n", "", width = width.saturating_sub(70))),
+    )?;
+
+    // Render content lines
+    for (color, line) in lines.iter().skip(app.detail_scroll_offset).take(visible_rows) {
+        let display_line = truncate_string(line, width.saturating_sub(1));
+        execute!(
+            stdout,
+            SetForegroundColor(*color),
+            Print(format!("{:width$}", display_line, width = width)),
+            ResetColor,
+            Print("\r\n")
+        )?;
+    }
+    
+    // Fill remaining space
+    let lines_rendered = lines.len().saturating_sub(app.detail_scroll_offset).min(visible_rows);
+    for _ in lines_rendered..visible_rows {
+        execute!(stdout, Print(format!("{:width$}\r\n", "", width = width)))?;
+    }
+    
+    // Status message (e.g. after saving), then footer
+    let status = app.error_message.as_deref().unwrap_or("");
+    execute!(
+        stdout,
+        SetForegroundColor(Color::Yellow),
+        Print(format!(" {}", truncate_string(status, width.saturating_sub(2)))),
+        ResetColor,
+        Print("\r\n"),
+        SetBackgroundColor(Color::DarkMagenta),
+        SetForegroundColor(Color::White),
+        Print(format!("{:width$}", " Esc/Enter: Back to process list  |  k: Kill process  |  a: CPU affinity  |  i: I/O priority  |  S: Save to file  |  c: Copy  |  m: Sort modules  |  /: Find module", width = width)),
+        ResetColor,
+    )?;
+    
+    stdout.flush()
+}
+
+/// Gets the detail view's content as plain text (no colors), one line per
+/// row, for copying to the clipboard.
+///
+/// # Arguments
+/// * `details` - The process details shown in the view
+/// * `modules` - Sort order and search applied to the module list
+/// * `units` - Binary (KiB) or decimal (KB) units for sizes and rates
+pub fn detail_view_text(details: &ProcessDetails, modules: &ModuleView, units: ByteUnits) -> String {
+    detail_lines(details, modules, usize::MAX, units)
+        .into_iter()
+        .map(|(_, line)| line)
+        .collect::<Vec<_>>()
+        .join("\r\n")
+}
+
+/// Builds the detail view's content lines with their colors.
+///
+/// # Arguments
+/// * `details` - The process details to show
+/// * `module_view` - Sort order and search applied to the module list
+/// * `width` - Terminal width, used to shorten module paths
+/// * `units` - Binary (KiB) or decimal (KB) units for sizes and rates
+fn detail_lines(
+    details: &ProcessDetails,
+    module_view: &ModuleView,
+    width: usize,
+    units: ByteUnits,
+) -> Vec<(Color, String)> {
     let mut lines: Vec<(Color, String)> = Vec::new();
     
     // Header section
@@ -48,20 +144,86 @@ pub fn render_detail_view(
     lines.push((Color::White, format!("  PID:         {}", details.pid)));
     lines.push((Color::White, format!("  Path:        {}", details.path.as_deref().unwrap_or("<access denied>"))));
     lines.push((Color::White, format!("  Command:     {}", details.command_line.as_deref().unwrap_or("<access denied>"))));
+    if !details.services.is_empty() {
+        lines.push((Color::White, format!("  Services:    {}", details.services.join(", "))));
+    }
+    lines.push((Color::White, format!("  Arch:        {}", match details.is_32bit {
+        Some(true) => "32-bit (WOW64)",
+        Some(false) => "Native",
+        None => "Unknown",
+    })));
+    lines.push((Color::White, format!("  Session:     {}", format_session(details.session_id))));
+    match details.package_family {
+        Some(ref family) => lines.push((Color::White, format!("  Package:     {}", family))),
+        None => lines.push((Color::White, "  Package:     None (classic app)".to_string())),
+    }
+    if let Some(ref aumid) = details.app_user_model_id {
+        lines.push((Color::White, format!("  App ID:      {}", aumid)));
+    }
+    match details.being_debugged {
+        Some(true) => lines.push((Color::Yellow, "  Debugger:    Attached".to_string())),
+        Some(false) => lines.push((Color::White, "  Debugger:    None".to_string())),
+        None => lines.push((Color::White, "  Debugger:    Unknown".to_string())),
+    }
+    lines.push((Color::White, format!("  Efficiency:  {}", format_on_off(details.efficiency_mode))));
+    lines.push((Color::White, format!("  Prio Boost:  {}", format_on_off(details.priority_boost))));
+    match details.protection {
+        Some(ref signer) => lines.push((Color::Yellow, format!("  Protection:  {} (cannot be killed, suspended or reprioritized)", signer))),
+        None => lines.push((Color::White, "  Protection:  None".to_string())),
+    }
+    match details.signature {
+        Some(ref signature) => {
+            let color = match signature.status {
+                SignatureStatus::Valid => Color::White,
+                SignatureStatus::Invalid(_) => Color::Red,
+                SignatureStatus::Unsigned => Color::Yellow,
+            };
+            lines.push((color, format!("  Signature:   {}", signature.describe())));
+        }
+        None => lines.push((Color::White, "  Signature:   Unknown".to_string())),
+    }
     lines.push((Color::White, format!("  Priority:    {}", details.priority)));
+    lines.push((Color::White, format!("  Base Priority: {}", details.base_priority)));
     lines.push((Color::White, format!("  Uptime:      {}", format_uptime(details.uptime_seconds))));
     lines.push((Color::Reset, String::new()));
     
     // Resource stats
     lines.push((Color::Cyan, "── Resource Usage ──".to_string()));
-    lines.push((Color::White, format!("  CPU:         {:.1}%", details.cpu_percent)));
-    lines.push((Color::White, format!("  Memory:      {}", format_bytes(details.memory_bytes))));
+    if details.query_access_denied {
+        lines.push((Color::DarkGrey, "  CPU:         <access denied>".to_string()));
+        lines.push((Color::DarkGrey, "  CPU Time:    <access denied>".to_string()));
+    } else {
+        lines.push((Color::White, format!(
+            "  CPU:         {:.1}% (avg {:.1}%, peak {:.1}%)",
+            details.cpu_percent, details.cpu_avg_percent, details.cpu_peak_percent
+        )));
+        lines.push((Color::White, format!("  CPU Time:    {}", format_cpu_time(details.cpu_time_seconds))));
+    }
+    if details.memory_access_denied {
+        lines.push((Color::DarkGrey, "  Memory:      <access denied>".to_string()));
+    } else {
+        lines.push((Color::White, format!("  Memory:      {} ({:.1}% of RAM)",
+            format_bytes(details.memory_bytes, units), details.mem_percent)));
+    }
     lines.push((Color::White, format!("  Threads:     {}", details.thread_count)));
-    lines.push((Color::White, format!("  Handles:     {}", details.handle_count)));
+    if let Some(times) = &details.thread_cpu {
+        // Yellow when the thread sum and process total disagree notably
+        let color = if times.divergence_note().is_some() { Color::Yellow } else { Color::White };
+        lines.push((color, format!("  Thread CPU:  {}", times.summary())));
+    }
+    if details.query_access_denied {
+        lines.push((Color::DarkGrey, "  Handles:     <access denied>".to_string()));
+    } else {
+        lines.push((Color::White, format!("  Handles:     {}", details.handle_count)));
+    }
     lines.push((Color::White, format!("  CPU Affinity: {}", 
         details.cpu_affinity.as_deref().unwrap_or("Unknown"))));
-    lines.push((Color::White, format!("  Disk Read:   {}", format_rate(details.disk_read_rate))));
-    lines.push((Color::White, format!("  Disk Write:  {}", format_rate(details.disk_write_rate))));
+    lines.push((Color::White, format!("  I/O Priority: {}",
+        details.io_priority.as_deref().unwrap_or("Unknown"))));
+    lines.push((Color::White, format!("  Disk Read:   {}", format_rate(details.disk_read_rate, units))));
+    lines.push((Color::White, format!("  Disk Write:  {}", format_rate(details.disk_write_rate, units))));
+    lines.push((Color::White, format!("  Net Recv:    {}", format_rate(details.net_recv_rate, units))));
+    lines.push((Color::White, format!("  Net Send:    {}", format_rate(details.net_send_rate, units))));
     lines.push((Color::Reset, String::new()));
     
     // Network connections
@@ -94,11 +256,29 @@ pub fn render_detail_view(
     lines.push((Color::Reset, String::new()));
     
     // Loaded modules
-    lines.push((Color::Cyan, format!("── Loaded Modules ({}) ──", details.modules.len())));
+    let modules = module_view.apply(&details.modules);
+    let count = if module_view.filter.is_empty() {
+        details.modules.len().to_string()
+    } else {
+        format!("{}/{}", modules.len(), details.modules.len())
+    };
+    let search = if module_view.filter_input {
+        format!("  Find: {}█", module_view.filter)
+    } else if !module_view.filter.is_empty() {
+        format!("  Find: \"{}\" (/ to edit)", module_view.filter)
+    } else {
+        String::new()
+    };
+    lines.push((Color::Cyan, format!(
+        "── Loaded Modules ({}, by {}) ──{}",
+        count, module_view.sort.label(), search
+    )));
     if details.modules.is_empty() {
         lines.push((Color::DarkGrey, "  No modules (access denied or system process)".to_string()));
+    } else if modules.is_empty() {
+        lines.push((Color::DarkGrey, "  No modules match the search".to_string()));
     } else {
-        for module in &details.modules {
+        for module in modules {
             lines.push((Color::White, format!("  {:40} @ 0x{:016X}", 
                 truncate_string(&module.name, 40), 
                 module.base_address)));
@@ -107,64 +287,6 @@ pub fn render_detail_view(
             }
         }
     }
-    
-    // Calculate visible area
-    let header_lines_count = 2;
-    let footer_lines_count = 2;
-    let visible_rows = height.saturating_sub(header_lines_count + footer_lines_count);
-    
-    // Clamp scroll offset
-    let max_scroll = lines.len().saturating_sub(visible_rows);
-    if app.detail_scroll_offset > max_scroll {
-        app.detail_scroll_offset = max_scroll;
-    }
-    
-    // Render header
-    execute!(
-        stdout,
-        SetBackgroundColor(Color::DarkMagenta),
-        SetForegroundColor(Color::White),
-        Print(format!("{:width$}", " Process Details View", width = width)),
-        ResetColor,
-        Print("\r\n"),
-        SetForegroundColor(Color::DarkGrey),
-        Print(format!(" Scroll: {}/{} lines  |  ↑↓/PgUp/PgDn: Scroll  |  Esc/Enter: Close",
-            app.detail_scroll_offset + 1, lines.len())),
-        ResetColor,
-        Print(format!("{:width$}\r\n", "", width = width.saturating_sub(70))),
-    )?;
 
-    // Render content lines
-    for (_i, (color, line)) in lines.iter()
-        .skip(app.detail_scroll_offset)
-        .take(visible_rows)
-        .enumerate()
-    {
-        let display_line = truncate_string(line, width.saturating_sub(1));
-        execute!(
-            stdout,
-            SetForegroundColor(*color),
-            Print(format!("{:width$}", display_line, width = width)),
-            ResetColor,
-            Print("\r\n")
-        )?;
-    }
-    
-    // Fill remaining space
-    let lines_rendered = lines.len().saturating_sub(app.detail_scroll_offset).min(visible_rows);
-    for _ in lines_rendered..visible_rows {
-        execute!(stdout, Print(format!("{:width$}\r\n", "", width = width)))?;
-    }
-    
-    // Footer
-    execute!(
-        stdout,
-        Print("\r\n"),
-        SetBackgroundColor(Color::DarkMagenta),
-        SetForegroundColor(Color::White),
-        Print(format!("{:width$}", " Esc/Enter: Back to process list  |  k: Kill process  |  a: CPU affinity", width = width)),
-        ResetColor,
-    )?;
-    
-    stdout.flush()
+    lines
 }
@@ -0,0 +1,156 @@
+//! Centered dialog box drawing shared by the overlay panels
+
+use std::io;
+
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
+};
+
+use super::utils::{display_width, truncate_string};
+
+/// Dims the whole screen behind a dialog.
+///
+/// # Arguments
+/// * `width` - Terminal width
+/// * `height` - Terminal height
+pub(super) fn draw_backdrop(stdout: &mut io::Stdout, width: usize, height: usize) -> io::Result<()> {
+    for y in 0..height {
+        execute!(stdout, MoveTo(0, y as u16))?;
+        execute!(
+            stdout,
+            SetBackgroundColor(Color::Black),
+            SetForegroundColor(Color::DarkGrey),
+            Print(format!("{:w$}", "", w = width)),
+            ResetColor
+        )?;
+    }
+    Ok(())
+}
+
+/// A bordered box centered on the screen.
+///
+/// Rows below the bottom of a short terminal are dropped instead of being
+/// drawn past it.
+pub(super) struct Dialog {
+    /// Column of the left border
+    start_x: usize,
+    /// Row of the top border
+    start_y: usize,
+    /// Width between the borders
+    inner_width: usize,
+    /// Terminal height
+    screen_height: usize,
+}
+
+impl Dialog {
+    /// Centers a box on the screen.
+    ///
+    /// # Arguments
+    /// * `width` - Terminal width
+    /// * `height` - Terminal height
+    /// * `box_width` - Width of the box, borders included
+    /// * `box_height` - Height of the box, borders included
+    pub(super) fn centered(width: usize, height: usize, box_width: usize, box_height: usize) -> Self {
+        Self {
+            start_x: width.saturating_sub(box_width) / 2,
+            start_y: height.saturating_sub(box_height) / 2,
+            inner_width: box_width.saturating_sub(2),
+            screen_height: height,
+        }
+    }
+
+    /// Row of the top border
+    pub(super) fn top(&self) -> usize {
+        self.start_y
+    }
+
+    /// Width between the borders
+    pub(super) fn inner_width(&self) -> usize {
+        self.inner_width
+    }
+
+    /// Draws one line of content between the side borders, cut or padded
+    /// to the inner width.
+    ///
+    /// # Arguments
+    /// * `y` - Screen row
+    /// * `content` - Text of the line
+    /// * `fg` - Text color
+    pub(super) fn line(&self, stdout: &mut io::Stdout, y: usize, content: &str, fg: Color) -> io::Result<()> {
+        self.line_on(stdout, y, content, fg, Color::DarkBlue)
+    }
+
+    /// Draws one line of content on its own background (e.g. a
+    /// highlighted row), keeping the borders on the dialog's.
+    ///
+    /// # Arguments
+    /// * `y` - Screen row
+    /// * `content` - Text of the line
+    /// * `fg` - Text color
+    /// * `bg` - Background of the content between the borders
+    pub(super) fn line_on(
+        &self,
+        stdout: &mut io::Stdout,
+        y: usize,
+        content: &str,
+        fg: Color,
+        bg: Color,
+    ) -> io::Result<()> {
+        if y >= self.screen_height {
+            return Ok(());
+        }
+        execute!(stdout, MoveTo(self.start_x as u16, y as u16))?;
+        let truncated = truncate_string(content, self.inner_width);
+        let padding = self.inner_width.saturating_sub(display_width(&truncated));
+        execute!(
+            stdout,
+            SetBackgroundColor(Color::DarkBlue),
+            SetForegroundColor(Color::White),
+            Print("│"),
+            SetBackgroundColor(bg),
+            SetForegroundColor(fg),
+            Print(format!("{}{:w$}", truncated, "", w = padding)),
+            SetBackgroundColor(Color::DarkBlue),
+            SetForegroundColor(Color::White),
+            Print("│"),
+            ResetColor
+        )
+    }
+
+    /// Draws a horizontal border row.
+    ///
+    /// # Arguments
+    /// * `y` - Screen row
+    /// * `left` - Left corner or junction, e.g. "┌" or "├"
+    /// * `right` - Right corner or junction, e.g. "┐" or "┤"
+    pub(super) fn border(&self, stdout: &mut io::Stdout, y: usize, left: &str, right: &str) -> io::Result<()> {
+        if y >= self.screen_height {
+            return Ok(());
+        }
+        execute!(stdout, MoveTo(self.start_x as u16, y as u16))?;
+        execute!(
+            stdout,
+            SetBackgroundColor(Color::DarkBlue),
+            SetForegroundColor(Color::White),
+            Print(left),
+            Print("─".repeat(self.inner_width)),
+            Print(right),
+            ResetColor
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_centered() {
+        let dialog = Dialog::centered(100, 40, 60, 10);
+        assert_eq!((dialog.start_x, dialog.top(), dialog.inner_width()), (20, 15, 58));
+        // A box taller than the screen starts at the top
+        assert_eq!(Dialog::centered(100, 5, 60, 10).top(), 0);
+    }
+}
@@ -19,36 +19,101 @@ const HELP_LINES: &[(&str, &str)] = &[
     ("  PgUp/PgDn", "Scroll by page"),
     ("  Home/End", "Jump to first/last process"),
     ("  Enter", "View process details"),
+    ("  *", "Pin/unpin process at top"),
+    ("  Space", "Mark process for batch actions"),
     ("", ""),
     ("PROCESS ACTIONS", ""),
     ("  k", "Kill selected process"),
+    ("  h", "Kill and hold (kill respawns for 5s)"),
+    ("  !", "Toggle kill confirmation"),
     ("  p", "Suspend/Resume process"),
     ("  +/-", "Raise/Lower priority"),
-    ("  a", "Set CPU affinity (in detail view)"),
+    ("  a", "Set CPU affinity"),
+    ("  f", "Toggle efficiency mode (EcoQoS)"),
+    ("  b", "Toggle dynamic priority boost"),
+    ("  S", "Save details to file (in detail view)"),
+    ("  c", "Copy details as text (in detail view)"),
+    ("  m", "Sort modules by load order/name/address (in detail view)"),
+    ("  /", "Search modules by name or path (in detail view)"),
+    ("  i", "Cycle I/O priority (in detail view)"),
+    ("  o", "Open containing folder"),
+    ("  c", "Copy executable path"),
     ("", ""),
     ("VIEW OPTIONS", ""),
     ("  s", "Cycle sort column"),
+    ("  S", "Pick sort column from a list"),
     ("  r", "Reverse sort order"),
+    ("  x", "Freeze row order (metrics keep updating)"),
     ("  t", "Toggle tree view"),
-    ("  /", "Filter by process name"),
-    ("  Esc", "Clear filter"),
+    ("  w", "Toggle compact columns"),
+    ("  v", "Toggle row stripes"),
+    ("  z", "Reset CPU average/peak"),
+    ("  /", "Filter by process name (digits: also PID)"),
+    ("  pid:N-M,K", "Filter term matching PIDs"),
+    ("  priority:X", "Filter term matching priority (e.g. high,rt)"),
+    ("  !term", "Filter term hiding matches"),
+    ("  ↑/↓", "Recall previous filters (while filtering)"),
+    ("  Tab", "Cycle searched fields (while filtering)"),
+    ("  Ctrl+F", "Find: highlight matches without hiding rows"),
+    ("  F3/Shift+F3", "Jump to next/previous find match"),
+    ("  u", "Show only my processes"),
+    ("  n", "Show only processes with network connections"),
+    ("  i", "Show/hide system processes"),
+    ("  l", "Show, dim or hide idle processes"),
+    ("  Esc", "Clear marks, then find, then filter"),
     ("", ""),
     ("SETTINGS", ""),
     ("  [", "Slow down refresh"),
     ("  ]", "Speed up refresh"),
+    ("  F5/Ctrl+R", "Refresh now"),
     ("", ""),
     ("OTHER", ""),
     ("  e", "Export to CSV file"),
-    ("  ?", "Show/hide this help"),
+    ("  ?", "Show/hide this help (type to search)"),
+    ("  d", "Disk columns: rate per second / lifetime total"),
+    ("  g", "Histogram of processes by CPU and memory band"),
+    ("  F1", "System information"),
+    ("  F12", "Debug line (timings, totals)"),
     ("  q", "Quit application"),
     ("  Ctrl+C", "Quit application"),
 ];
 
+/// Selects the help lines matching a search string.
+///
+/// A shortcut matches when its key or description contains the search
+/// (case-insensitive). Matches are grouped under their section headers;
+/// sections without matches are dropped. An empty search keeps every line.
+fn filter_help_lines(search: &str) -> Vec<(&'static str, &'static str)> {
+    if search.is_empty() {
+        return HELP_LINES.to_vec();
+    }
+    let search = search.to_lowercase();
+    let mut lines = Vec::new();
+    let mut section = None;
+    for &(key, desc) in HELP_LINES {
+        if desc.is_empty() {
+            // Blank line or section header
+            section = (!key.is_empty()).then_some(key);
+        } else if key.to_lowercase().contains(&search) || desc.to_lowercase().contains(&search) {
+            if let Some(header) = section.take() {
+                if !lines.is_empty() {
+                    lines.push(("", ""));
+                }
+                lines.push((header, ""));
+            }
+            lines.push((key, desc));
+        }
+    }
+    lines
+}
+
 /// Renders the help overlay showing all keyboard shortcuts.
 ///
 /// Displays a centered dialog with available keybindings organized
 /// by category: Navigation, Process Actions, View Options, Settings,
-/// and Other. Supports scrolling when content exceeds window height.
+/// and Other. Typing narrows the list to matching shortcuts (shown in a
+/// search line at the bottom). Supports scrolling when content exceeds
+/// window height.
 pub fn render_help_overlay(
     stdout: &mut io::Stdout,
     app: &App,
@@ -62,13 +127,14 @@ pub fn render_help_overlay(
     // Calculate available content height (minus borders, title, separator, footer hint)
     let max_content_lines = height.saturating_sub(8); // 8 = top border + title + separator + footer hint + margins
     let content_height = HELP_LINES.len().min(max_content_lines);
-    let box_height = content_height + 5; // +5 for borders and header/footer
+    let box_height = content_height + 5; // +5 for borders, header and search line
+    let lines = filter_help_lines(&app.help_filter);
     
     let start_x = (width.saturating_sub(box_width)) / 2;
     let start_y = (height.saturating_sub(box_height)) / 2;
     
     // Clamp scroll offset to valid range
-    let max_scroll = HELP_LINES.len().saturating_sub(content_height);
+    let max_scroll = lines.len().saturating_sub(content_height);
     let scroll_offset = app.help_scroll_offset.min(max_scroll);
 
     // Draw background fill for the whole screen (dimmed)
@@ -138,7 +204,7 @@ pub fn render_help_overlay(
     )?;
 
     // Draw help content with scroll offset
-    let visible_lines: Vec<_> = HELP_LINES.iter()
+    let visible_lines: Vec<_> = lines.iter()
         .skip(scroll_offset)
         .take(content_height)
         .collect();
@@ -173,10 +239,21 @@ pub fn render_help_overlay(
     }
 
     // Fill remaining space in box
-    for y in (start_y + 3 + visible_lines.len())..(start_y + box_height - 1) {
+    for y in (start_y + 3 + visible_lines.len())..(start_y + box_height - 2) {
         draw_bordered_line(stdout, y, "", Color::White)?;
     }
 
+    // Search line
+    let search_line = if app.help_filter.is_empty() {
+        " Type to search".to_string()
+    } else if lines.is_empty() {
+        format!(" Search: {}_  (no matches)", app.help_filter)
+    } else {
+        format!(" Search: {}_", app.help_filter)
+    };
+    let search_color = if app.help_filter.is_empty() { Color::DarkGrey } else { Color::Yellow };
+    draw_bordered_line(stdout, start_y + box_height - 2, &search_line, search_color)?;
+
     // Draw bottom border with scroll hint
     execute!(stdout, MoveTo(start_x as u16, (start_y + box_height - 1) as u16))?;
     let hint = if max_scroll > 0 {
@@ -205,3 +282,23 @@ pub fn render_help_overlay(
 
     stdout.flush()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_help_lines() {
+        assert_eq!(filter_help_lines("").len(), HELP_LINES.len());
+
+        let lines = filter_help_lines("AFFINITY");
+        assert_eq!(lines, vec![("PROCESS ACTIONS", ""), ("  a", "Set CPU affinity")]);
+
+        // Matches from two sections are separated by a blank line
+        let lines = filter_help_lines("detail");
+        assert_eq!(lines[..3], [("NAVIGATION", ""), ("  Enter", "View process details"), ("", "")]);
+        assert_eq!(lines[3], ("PROCESS ACTIONS", ""));
+
+        assert!(filter_help_lines("no such command").is_empty());
+    }
+}
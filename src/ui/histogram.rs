@@ -0,0 +1,84 @@
+//! CPU/memory histogram panel rendering
+
+use std::io::{self, Write};
+
+use crossterm::style::Color;
+
+use crate::app::{cpu_bands, memory_bands, App};
+use crate::constants::{DIALOG_MARGIN, HISTOGRAM_DIALOG_WIDTH, HISTOGRAM_LABEL_WIDTH};
+
+use super::dialog::{draw_backdrop, Dialog};
+
+/// Formats one histogram row: label, bar scaled to `max`, and count.
+///
+/// # Arguments
+/// * `label` - Band label
+/// * `count` - Processes in the band
+/// * `max` - Largest count in the group (the full-width bar)
+/// * `bar_width` - Columns available for the bar
+fn band_line(label: &str, count: usize, max: usize, bar_width: usize) -> String {
+    let filled = if max == 0 {
+        0
+    } else {
+        // Round up so a non-empty band always shows at least one block
+        (count * bar_width).div_ceil(max)
+    };
+    format!(
+        " {:<lw$}{:<bw$} {:>4}",
+        label,
+        "█".repeat(filled),
+        count,
+        lw = HISTOGRAM_LABEL_WIDTH,
+        bw = bar_width
+    )
+}
+
+/// Renders the histogram panel.
+///
+/// Shows how many processes fall into each CPU% band and each working set
+/// band as horizontal bars, updated on every refresh. Each group's bars are
+/// scaled to its largest band.
+pub fn render_histogram(
+    stdout: &mut io::Stdout,
+    app: &App,
+    width: usize,
+    height: usize,
+) -> io::Result<()> {
+    let cpu = cpu_bands(&app.processes);
+    let memory = memory_bands(&app.processes);
+
+    let box_width = HISTOGRAM_DIALOG_WIDTH.min(width.saturating_sub(DIALOG_MARGIN));
+    // Borders, title, separator, two group headings, spacer and key hint
+    let box_height = cpu.len() + memory.len() + 8;
+    let dialog = Dialog::centered(width, height, box_width, box_height);
+    // Leading space, label column, space and 4-digit count
+    let bar_width = dialog.inner_width().saturating_sub(HISTOGRAM_LABEL_WIDTH + 6);
+
+    draw_backdrop(stdout, width, height)?;
+
+    let mut y = dialog.top();
+    dialog.border(stdout, y, "┌", "┐")?;
+    y += 1;
+    dialog.line(stdout, y, " Process Distribution", Color::Yellow)?;
+    y += 1;
+    dialog.border(stdout, y, "├", "┤")?;
+    y += 1;
+
+    for (heading, bands, color) in [("CPU", &cpu, Color::Green), ("Memory", &memory, Color::Cyan)] {
+        dialog.line(stdout, y, &format!(" {}", heading), Color::White)?;
+        y += 1;
+        let max = bands.iter().map(|(_, count)| *count).max().unwrap_or(0);
+        for (label, count) in bands {
+            dialog.line(stdout, y, &band_line(label, *count, max, bar_width), color)?;
+            y += 1;
+        }
+    }
+
+    dialog.line(stdout, y, "", Color::White)?;
+    y += 1;
+    dialog.line(stdout, y, " Esc/Enter/g: Close", Color::DarkGrey)?;
+    y += 1;
+    dialog.border(stdout, y, "└", "┘")?;
+
+    stdout.flush()
+}
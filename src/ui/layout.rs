@@ -0,0 +1,208 @@
+//! Process list column layout
+//!
+//! The column tables here are the one description of the list's fixed
+//! columns. Each frame widens the table widths to fit the rows on screen
+//! (`column_widths`), and both the headers and every row are padded to
+//! the result, so a value wider than its column moves the whole column
+//! rather than one row. The name column gets whatever the rest of the row
+//! leaves of the terminal width.
+
+use crate::app::SortColumn;
+
+use super::utils::display_width;
+
+/// A list column header: label, minimum width (0 for the left-aligned
+/// Name column at the end) and the sort columns that order by it
+pub(super) type ColumnHeader = (&'static str, usize, &'static [SortColumn]);
+
+/// Headers of the full layout, separated by two spaces
+const FULL_COLUMN_HEADERS: &[ColumnHeader] = &[
+    ("PID", 7, &[SortColumn::Pid]),
+    ("Sess", 4, &[SortColumn::Session]),
+    ("Priority", 8, &[SortColumn::Priority]),
+    ("Base", 4, &[SortColumn::BasePriority]),
+    ("Thrd", 5, &[SortColumn::Threads]),
+    ("Hndls", 6, &[SortColumn::Handles]),
+    ("Uptime", 9, &[SortColumn::Uptime]),
+    ("Memory", 10, &[SortColumn::Memory]),
+    ("Mem%", 6, &[SortColumn::MemPercent]),
+    ("Mem/s", 12, &[SortColumn::MemGrowth]),
+    ("CPU%", 6, &[SortColumn::Cpu]),
+    ("CPU Time", 9, &[SortColumn::CpuTime]),
+    ("Read/s", 11, &[SortColumn::DiskReadRate]),
+    ("Write/s", 11, &[SortColumn::DiskWriteRate]),
+    ("Net/s", 11, &[SortColumn::Network]),
+    ("Power", 6, &[SortColumn::Power]),
+    ("Name", 0, &[SortColumn::Name]),
+];
+
+/// Headers of the compact layout, separated by one space
+const COMPACT_COLUMN_HEADERS: &[ColumnHeader] = &[
+    ("PID", 7, &[SortColumn::Pid]),
+    ("Sess", 4, &[SortColumn::Session]),
+    ("Priority", 8, &[SortColumn::Priority]),
+    ("Thrd", 5, &[SortColumn::Threads]),
+    ("Uptime", 9, &[SortColumn::Uptime]),
+    ("Memory", 10, &[SortColumn::Memory]),
+    ("Mem/s", 12, &[SortColumn::MemGrowth]),
+    ("CPU%", 6, &[SortColumn::Cpu]),
+    ("CPU Time", 9, &[SortColumn::CpuTime]),
+    ("Disk/s", 11, &[SortColumn::DiskReadRate, SortColumn::DiskWriteRate]),
+    ("Net/s", 11, &[SortColumn::Network]),
+    ("Name", 0, &[SortColumn::Name]),
+];
+
+/// Returns the column headers of a layout and the gap between columns.
+///
+/// # Arguments
+/// * `compact` - The compact layout (single-space gaps, fewer columns)
+pub(super) fn column_headers(compact: bool) -> (&'static [ColumnHeader], usize) {
+    if compact {
+        (COMPACT_COLUMN_HEADERS, 1)
+    } else {
+        (FULL_COLUMN_HEADERS, 2)
+    }
+}
+
+/// Finds a column's position in a layout.
+///
+/// # Arguments
+/// * `compact` - The compact layout (single-space gaps, fewer columns)
+/// * `sort` - A sort column the wanted column orders by
+///
+/// # Panics
+/// If no column of the layout sorts by `sort`.
+pub(super) fn column_index(compact: bool, sort: SortColumn) -> usize {
+    let (headers, _) = column_headers(compact);
+    headers
+        .iter()
+        .position(|(_, _, sorts)| sorts.contains(&sort))
+        .expect("column missing from the layout")
+}
+
+/// Works out this frame's width of each fixed column: the widest of its
+/// table width, its label and the values of the rows on screen.
+///
+/// # Arguments
+/// * `compact` - The compact layout (single-space gaps, fewer columns)
+/// * `rows` - Values of the visible rows, one per fixed column in table order
+///
+/// # Returns
+/// One width per fixed column (Name excluded).
+pub(super) fn column_widths(compact: bool, rows: &[Vec<String>]) -> Vec<usize> {
+    let (headers, _) = column_headers(compact);
+    let fixed = &headers[..headers.len() - 1];
+    fixed
+        .iter()
+        .enumerate()
+        .map(|(i, &(label, width, _))| {
+            rows.iter()
+                .filter_map(|values| values.get(i))
+                .map(|value| display_width(value))
+                .fold(width.max(display_width(label)), usize::max)
+        })
+        .collect()
+}
+
+/// Pads a row's values to the frame's column widths.
+///
+/// Each cell includes the separator before it, matching the header
+/// segments; the first cell starts with the selection marker instead. A
+/// final cell holds the separator before the name, which the caller adds.
+///
+/// # Arguments
+/// * `compact` - The compact layout (single-space gaps, fewer columns)
+/// * `widths` - Fixed column widths from `column_widths`
+/// * `marker` - Character in the selection marker column
+/// * `values` - One value per fixed column, in table order
+///
+/// # Returns
+/// One cell per column of the table, Name included.
+pub(super) fn row_cells(compact: bool, widths: &[usize], marker: char, values: &[String]) -> Vec<String> {
+    let (_, gap) = column_headers(compact);
+    debug_assert_eq!(values.len(), widths.len(), "one value per fixed column");
+    widths
+        .iter()
+        .chain(std::iter::once(&0))
+        .zip(values.iter().map(String::as_str).chain(std::iter::once("")))
+        .enumerate()
+        .map(|(i, (&width, value))| {
+            if i == 0 {
+                format!("{}{:>width$}", marker, value, width = width)
+            } else {
+                format!("{:gap$}{:>width$}", "", value, gap = gap, width = width)
+            }
+        })
+        .collect()
+}
+
+/// Gives the name column the space the rest of the row leaves over.
+///
+/// `used` is measured from the row as formatted, so columns widened for
+/// this frame (or an added column) shrink the name instead of pushing the
+/// row past the edge.
+///
+/// # Arguments
+/// * `width` - Row width available, excluding the scrollbar
+/// * `used` - Display width of the row without the name
+/// * `limit` - The `--name-width` cap, if set
+///
+/// # Returns
+/// The name width, 0 when the other columns already fill the row.
+pub(super) fn name_column_width(width: usize, used: usize, limit: Option<usize>) -> usize {
+    let space = width.saturating_sub(used);
+    limit.map_or(space, |limit| space.min(limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_cells() {
+        let values: Vec<String> = [
+            "4242", "1", "Normal", "8", "1:02:03", "12.5 MB", "+1.0 KB/s", "3.2%", "00:00:05", "0 B/s",
+            "4.0 KB/s",
+        ]
+        .iter()
+        .map(|v| v.to_string())
+        .collect();
+        let widths = column_widths(true, std::slice::from_ref(&values));
+        let cells = row_cells(true, &widths, '+', &values);
+        assert_eq!(cells.len(), COMPACT_COLUMN_HEADERS.len());
+        assert_eq!(cells[0], "+   4242");
+        assert_eq!(cells[column_index(true, SortColumn::Cpu)], "   3.2%");
+        assert_eq!(cells.last().unwrap(), " ");
+    }
+
+    #[test]
+    fn test_column_widths() {
+        let row = |pid: &str| -> Vec<String> {
+            let mut values = vec!["1".to_string(); COMPACT_COLUMN_HEADERS.len() - 1];
+            values[0] = pid.to_string();
+            values
+        };
+        // Nothing on screen keeps the table widths
+        let table: Vec<usize> = COMPACT_COLUMN_HEADERS[..COMPACT_COLUMN_HEADERS.len() - 1]
+            .iter()
+            .map(|&(_, width, _)| width)
+            .collect();
+        assert_eq!(column_widths(true, &[]), table);
+
+        // Too wide a value widens its column on every row of the frame
+        let rows = vec![row("4"), row("123456789")];
+        let widths = column_widths(true, &rows);
+        assert_eq!(widths[0], 9);
+        assert_eq!(widths[1..], table[1..]);
+        assert_eq!(row_cells(true, &widths, ' ', &rows[0])[0], "         4");
+        assert_eq!(row_cells(true, &widths, ' ', &rows[1])[0], " 123456789");
+    }
+
+    #[test]
+    fn test_name_column_width() {
+        assert_eq!(name_column_width(200, 158, None), 42);
+        assert_eq!(name_column_width(200, 158, Some(20)), 20);
+        // Narrow terminals leave no room instead of underflowing
+        assert_eq!(name_column_width(120, 158, None), 0);
+    }
+}
@@ -4,17 +4,27 @@
 //! - `render` - Main rendering entry point
 //! - `components` - Header, stats bar, filter bar, column headers, footer
 //! - `process_list` - Process list rendering
+//! - `layout` - Process list column table (header and row widths)
 //! - `detail_view` - Detailed process information view
+//! - `dialog` - Centered dialog box drawing shared by the panels
 //! - `help` - Help overlay
+//! - `histogram` - Process counts by CPU and memory band
 //! - `affinity` - CPU affinity dialog
+//! - `sort_picker` - Sort column picker
+//! - `sysinfo` - System information panel
 //! - `utils` - Shared utilities
 
 mod affinity;
 mod components;
 mod detail_view;
+mod dialog;
 mod help;
+mod histogram;
+mod layout;
 mod process_list;
 mod render;
+mod sort_picker;
+mod sysinfo;
 mod utils;
 
 // ============================================================================
@@ -24,3 +34,6 @@ mod utils;
 // Main rendering entry point
 pub use render::render;
 
+// Plain-text detail view for the clipboard
+pub use detail_view::detail_view_text;
+
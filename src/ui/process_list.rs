@@ -7,44 +7,187 @@ use crossterm::{
     style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
 };
 
-use crate::app::App;
-use crate::constants::MAX_TREE_INDENT_DEPTH;
-use crate::system::{format_bytes, format_uptime};
+use crate::app::{App, ProcessEntry, SortColumn};
+use crate::constants::{MAX_TREE_INDENT_DEPTH, ROW_STRIPE_ANSI_COLOR};
+use crate::system::{format_bytes, format_cpu_time, format_rate, format_uptime};
 
-use super::utils::{cpu_color, format_rate, truncate_string};
+use super::layout::{column_index, name_column_width, row_cells};
+use super::utils::{
+    cpu_color, display_width, format_growth_rate, scrollbar_thumb, truncate_string,
+    UNAVAILABLE,
+};
+
+/// Scrolls the list so the selected row is within the visible rows.
+///
+/// # Arguments
+/// * `visible_rows` - Number of list rows on screen
+pub fn scroll_into_view(app: &mut App, visible_rows: usize) {
+    if app.selected_index < app.scroll_offset {
+        app.scroll_offset = app.selected_index;
+    } else if app.selected_index >= app.scroll_offset + visible_rows {
+        app.scroll_offset = app.selected_index - visible_rows + 1;
+    }
+}
+
+/// Formats the fixed column values of the rows on screen, so the column
+/// widths can be fitted to them before anything is drawn.
+///
+/// # Arguments
+/// * `visible_rows` - Number of list rows on screen
+///
+/// # Returns
+/// One value per fixed column for each visible row, in table order.
+pub fn visible_row_values(app: &App, visible_rows: usize) -> Vec<Vec<String>> {
+    app.filtered_processes
+        .iter()
+        .skip(app.scroll_offset)
+        .take(visible_rows)
+        .map(|entry| row_values(app, entry))
+        .collect()
+}
+
+/// Formats one process's fixed column values, in the order of the
+/// layout's column table.
+fn row_values(app: &App, entry: &ProcessEntry) -> Vec<String> {
+    let session = entry
+        .session_id
+        .map_or_else(|| UNAVAILABLE.to_string(), |id| id.to_string());
+    let (memory, mem_percent, growth) = if entry.memory_access_denied {
+        (UNAVAILABLE.to_string(), UNAVAILABLE.to_string(), UNAVAILABLE.to_string())
+    } else {
+        (
+            format_bytes(entry.memory_bytes, app.units),
+            format!("{:.1}%", entry.mem_percent),
+            format_growth_rate(entry.memory_growth_rate, app.units),
+        )
+    };
+    let (handles, cpu, cpu_time) = if entry.query_access_denied {
+        (UNAVAILABLE.to_string(), UNAVAILABLE.to_string(), UNAVAILABLE.to_string())
+    } else {
+        (
+            entry.handle_count.to_string(),
+            format!("{:.1}%", entry.cpu_percent),
+            format_cpu_time(entry.cpu_time_seconds),
+        )
+    };
+    // Disk columns show per-second rates, or lifetime bytes after `d`
+    let (disk_read, disk_write, disk_total) = if app.disk_totals {
+        (
+            format_bytes(entry.disk_read, app.units),
+            format_bytes(entry.disk_write, app.units),
+            format_bytes(entry.disk_read + entry.disk_write, app.units),
+        )
+    } else {
+        (
+            format_rate(entry.disk_read_rate, app.units),
+            format_rate(entry.disk_write_rate, app.units),
+            format_rate(entry.disk_read_rate + entry.disk_write_rate, app.units),
+        )
+    };
+    let network = format_rate(entry.net_recv_rate + entry.net_send_rate, app.units);
+
+    // Compact rows drop base priority, handles, Mem% and power, and
+    // combine disk reads and writes into a single rate
+    if app.compact_rows {
+        vec![
+            entry.info.pid.to_string(),
+            session,
+            entry.priority.short_name().to_string(),
+            entry.thread_count.to_string(),
+            format_uptime(entry.uptime_seconds),
+            memory,
+            growth,
+            cpu,
+            cpu_time,
+            disk_total,
+            network,
+        ]
+    } else {
+        vec![
+            entry.info.pid.to_string(),
+            session,
+            entry.priority.short_name().to_string(),
+            entry.info.base_priority.to_string(),
+            entry.thread_count.to_string(),
+            handles,
+            format_uptime(entry.uptime_seconds),
+            memory,
+            mem_percent,
+            growth,
+            cpu,
+            cpu_time,
+            disk_read,
+            disk_write,
+            network,
+            entry.power_usage().short_name().to_string(),
+        ]
+    }
+}
 
 /// Renders the scrollable process list.
 ///
-/// Displays process information including PID, priority, threads, handles,
-/// uptime, memory, CPU usage, disk I/O rates, and process name.
+/// Displays process information including PID, priority, base priority, threads, handles,
+/// uptime, memory, CPU usage and time, disk and network I/O rates, estimated power
+/// usage, and process name.
 /// Highlights the currently selected process and shows tree indentation
-/// when tree view mode is enabled.
+/// when tree view mode is enabled. In compact mode, base priority, handles
+/// and power are dropped, disk reads and writes share one column, and columns are
+/// separated by single spaces to leave more room for the name.
+/// Every row is padded to `widths`, the same widths as the headers; the
+/// name column takes whatever width the rest of the row leaves over, so it
+/// never pushes the row past the edge.
+/// When the list is longer than the window, the rightmost column shows a
+/// scrollbar marking the visible part.
+///
+/// # Arguments
+/// * `rows` - Values of the visible rows, from `visible_row_values`
+/// * `widths` - Fixed column widths fitted to `rows`
+/// * `visible_rows` - Number of list rows on screen
+/// * `width` - Terminal width
 pub fn render_process_list(
     stdout: &mut io::Stdout,
-    app: &mut App,
+    app: &App,
+    rows: &[Vec<String>],
+    widths: &[usize],
     visible_rows: usize,
     width: usize,
 ) -> io::Result<()> {
-    // Adjust scroll offset to keep selection visible
-    if app.selected_index < app.scroll_offset {
-        app.scroll_offset = app.selected_index;
-    } else if app.selected_index >= app.scroll_offset + visible_rows {
-        app.scroll_offset = app.selected_index - visible_rows + 1;
-    }
+    // Reserve the last column for the scrollbar when the list overflows
+    let thumb = scrollbar_thumb(app.filtered_processes.len(), visible_rows, app.scroll_offset);
+    let scrollbar_cell = |row: usize| match thumb {
+        Some((start, len)) if (start..start + len).contains(&row) => "█",
+        Some(_) => "│",
+        None => "",
+    };
+    let width = if thumb.is_some() { width.saturating_sub(1) } else { width };
 
     // Display processes
-    for (i, entry) in app
+    for (i, (entry, values)) in app
         .filtered_processes
         .iter()
         .skip(app.scroll_offset)
-        .take(visible_rows)
+        .zip(rows)
         .enumerate()
     {
         let actual_index = app.scroll_offset + i;
         let is_selected = actual_index == app.selected_index;
 
-        // Color-code CPU usage
-        let cpu_col = cpu_color(entry.cpu_percent);
+        // Idle processes are drawn dim when the idle mode asks for it
+        let is_dimmed = app.is_dimmed_idle(entry);
+
+        // Color-code CPU usage (dimmed when it could not be read)
+        let cpu_col = if entry.query_access_denied || is_dimmed {
+            Color::DarkGrey
+        } else {
+            cpu_color(entry.cpu_percent)
+        };
+
+        // Pinned and newly started processes use a distinct color for the rest of the row
+        let is_pinned = app.pinned.contains(&entry.info.pid);
+        let is_new = app.is_new_process(entry);
+
+        // Rows matching the find text get a background of their own
+        let is_find_match = app.is_find_match(entry);
 
         // Add tree indentation if in tree view mode
         let tree_prefix = if app.tree_view_mode && entry.tree_depth > 0 {
@@ -57,62 +200,117 @@ pub fn render_process_list(
         // Check if process is suspended
         let is_suspended = app.is_process_suspended(entry.info.pid);
         let suspend_indicator = if is_suspended { "[S] " } else { "" };
+
+        // Tag 32-bit (WOW64) processes like Task Manager's "*32"
+        let arch_tag = if entry.is_32bit == Some(true) { " *32" } else { "" };
+
+        // Flag processes with a debugger attached
+        let debug_tag = if entry.being_debugged == Some(true) { " [DBG]" } else { "" };
+
+        // Flag processes in efficiency mode, like Task Manager's leaf icon
+        let eff_tag = if entry.efficiency_mode == Some(true) { " [Eff]" } else { "" };
+
+        // The flat list shows how many children each process has;
+        // the tree already shows them as indented rows
+        let child_tag = if !app.tree_view_mode && entry.child_count > 0 {
+            format!(" (+{})", entry.child_count)
+        } else {
+            String::new()
+        };
         
-        let prefix = format!(
-            " {:>7}  {:>8}  {:>5}  {:>6}  {:>9}  {:>10}  ",
-            entry.info.pid,
-            entry.priority.short_name(),
-            entry.thread_count,
-            entry.handle_count,
-            format_uptime(entry.uptime_seconds),
-            format_bytes(entry.memory_bytes),
-        );
-        let cpu_str = format!("{:>5.1}%", entry.cpu_percent);
-        
-        // Calculate available space for name with tree prefix and suspend indicator
-        let name_space = width.saturating_sub(90 + tree_prefix.len() + suspend_indicator.len());
+        // Multi-selected rows are marked in the leftmost column
+        let marker = if app.selected_pids.contains(&entry.info.pid) { '+' } else { ' ' };
+
+        let cells = row_cells(app.compact_rows, widths, marker, values);
+        // CPU% is drawn in its own color, so the row is split around it
+        let cpu_index = column_index(app.compact_rows, SortColumn::Cpu);
+        let prefix = cells[..cpu_index].concat();
+        let cpu_str = &cells[cpu_index];
+        let columns = cells[cpu_index + 1..].concat();
+
+        // The name gets whatever the row as formatted leaves over, after
+        // its tree prefix and tags
+        let used = [&prefix, cpu_str, &columns, &tree_prefix, &child_tag]
+            .iter()
+            .map(|text| display_width(text))
+            .sum::<usize>()
+            + suspend_indicator.len()
+            + arch_tag.len()
+            + debug_tag.len()
+            + eff_tag.len();
+        let name = truncate_string(&entry.info.name, name_column_width(width, used, app.name_width));
         let suffix = format!(
-            "  {:>9}  {:>9}  {}{}{}",
-            format_rate(entry.disk_read_rate),
-            format_rate(entry.disk_write_rate),
-            tree_prefix,
-            suspend_indicator,
-            truncate_string(&entry.info.name, name_space)
+            "{}{}{}{}{}{}{}{}",
+            columns, tree_prefix, suspend_indicator, name, arch_tag, debug_tag, eff_tag, child_tag
         );
 
         if is_selected {
             // Selected row - use background color, CPU still colored
+            let row_color = if is_pinned {
+                Color::Yellow
+            } else if is_new {
+                Color::Green
+            } else if is_dimmed {
+                Color::Grey
+            } else {
+                Color::White
+            };
             execute!(
                 stdout,
                 SetBackgroundColor(Color::DarkCyan),
-                SetForegroundColor(Color::White),
+                SetForegroundColor(row_color),
                 Print(&prefix),
                 SetForegroundColor(cpu_col),
-                Print(&cpu_str),
-                SetForegroundColor(Color::White),
+                Print(cpu_str),
+                SetForegroundColor(row_color),
                 Print(format!(
                     "{:width$}",
                     suffix,
-                    width = width.saturating_sub(prefix.len() + cpu_str.len())
+                    width = width.saturating_sub(display_width(&prefix) + display_width(cpu_str))
                 )),
                 ResetColor,
             )?;
         } else {
-            // Normal row - color only CPU
+            // Normal row - color only CPU (and pinned rows)
+            let row_color = if is_pinned {
+                Color::Yellow
+            } else if is_new {
+                Color::Green
+            } else if is_dimmed {
+                Color::DarkGrey
+            } else {
+                Color::Reset
+            };
+            let row_background = if is_find_match {
+                Color::DarkMagenta
+            } else if app.row_stripes && actual_index % 2 == 1 {
+                Color::AnsiValue(ROW_STRIPE_ANSI_COLOR)
+            } else {
+                Color::Reset
+            };
             execute!(
                 stdout,
+                SetBackgroundColor(row_background),
+                SetForegroundColor(row_color),
                 Print(&prefix),
                 SetForegroundColor(cpu_col),
-                Print(&cpu_str),
-                ResetColor,
+                Print(cpu_str),
+                SetForegroundColor(row_color),
                 Print(format!(
                     "{:width$}",
                     suffix,
-                    width = width.saturating_sub(prefix.len() + cpu_str.len())
+                    width = width.saturating_sub(display_width(&prefix) + display_width(cpu_str))
                 )),
+                ResetColor,
             )?;
         }
-        execute!(stdout, Print("\r\n"))?;
+        execute!(
+            stdout,
+            SetForegroundColor(Color::DarkGrey),
+            Print(scrollbar_cell(i)),
+            ResetColor,
+            Print("\r\n")
+        )?;
     }
 
     // Fill remaining space
@@ -5,26 +5,36 @@
 //! - `process_list` - Process list rendering
 //! - `detail_view` - Detailed process information view
 //! - `help` - Help overlay
-//! - `utils` - Shared utilities (truncate, format_rate, etc.)
+//! - `utils` - Shared utilities (truncate, colors, etc.)
 
 use std::io::{self, Write};
 
 use crossterm::{
     cursor::MoveTo,
     execute,
+    style::{Color, Print, ResetColor, SetForegroundColor},
     terminal::{self, Clear, ClearType},
 };
 
 use crate::app::{App, ViewMode};
+use crate::constants::{
+    COMMIT_WARNING_PERCENT, MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH, PATH_LINE_MIN_HEIGHT,
+};
 use crate::system::get_system_memory_info;
 
 use super::components::{
-    render_column_headers, render_filter_bar, render_footer, render_header, render_system_stats,
+    render_column_headers, render_commit_warning, render_debug_line, render_filter_bar,
+    render_footer, render_header, render_path_line, render_system_stats,
 };
 use super::affinity::render_affinity_dialog;
 use super::detail_view::render_detail_view;
 use super::help::render_help_overlay;
-use super::process_list::render_process_list;
+use super::layout::column_widths;
+use super::process_list::{render_process_list, scroll_into_view, visible_row_values};
+use super::sort_picker::render_sort_picker;
+use super::histogram::render_histogram;
+use super::sysinfo::render_sys_info_panel;
+use super::utils::truncate_string;
 
 /// Renders the UI to the terminal
 ///
@@ -38,18 +48,52 @@ pub fn render(stdout: &mut io::Stdout, app: &mut App) -> io::Result<()> {
     // Clear and move to top
     execute!(stdout, MoveTo(0, 0), Clear(ClearType::All))?;
 
+    // Layouts assume a minimum size; show a notice until the terminal grows
+    if width < MIN_TERMINAL_WIDTH || height < MIN_TERMINAL_HEIGHT {
+        return render_too_small(stdout, width, height);
+    }
+
     // Dispatch based on current view mode
     match app.view_mode {
         ViewMode::Help => render_help_overlay(stdout, app, width, height),
         ViewMode::Affinity => render_affinity_dialog(stdout, app, width, height),
+        ViewMode::SysInfo => render_sys_info_panel(stdout, app, width, height),
+        ViewMode::SortPicker => render_sort_picker(stdout, app, width, height),
+        ViewMode::Histogram => render_histogram(stdout, app, width, height),
         ViewMode::DetailView => render_detail_view(stdout, app, width, height),
         // Process list, filter input, and confirm kill all render the main view
-        ViewMode::ProcessList | ViewMode::FilterInput | ViewMode::ConfirmKill => {
+        ViewMode::ProcessList
+        | ViewMode::FilterInput
+        | ViewMode::FindInput
+        | ViewMode::ConfirmKill
+        | ViewMode::ConfirmQuit
+        | ViewMode::ConfirmRealtime => {
             render_main_view(stdout, app, width, height)
         }
     }
 }
 
+/// Renders a centered notice when the terminal is below the minimum size
+fn render_too_small(stdout: &mut io::Stdout, width: usize, height: usize) -> io::Result<()> {
+    let message = format!(
+        "Terminal too small (need {}x{})",
+        MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT
+    );
+    let message = truncate_string(&message, width);
+    let x = width.saturating_sub(message.chars().count()) / 2;
+    let y = height / 2;
+
+    execute!(
+        stdout,
+        MoveTo(x as u16, y as u16),
+        SetForegroundColor(Color::Yellow),
+        Print(message),
+        ResetColor,
+    )?;
+
+    stdout.flush()
+}
+
 /// Renders the main process list view
 fn render_main_view(
     stdout: &mut io::Stdout,
@@ -60,8 +104,15 @@ fn render_main_view(
     // Get system memory info
     let mem_info = get_system_memory_info().ok();
 
+    // === COMMIT WARNING ===
+    let commit_percent = mem_info.as_ref().map_or(0.0, |info| info.commit_percent());
+    let commit_warning = commit_percent > COMMIT_WARNING_PERCENT;
+    if commit_warning {
+        render_commit_warning(stdout, commit_percent, width)?;
+    }
+
     // === HEADER ===
-    render_header(stdout, width)?;
+    render_header(stdout, app, width)?;
 
     // === SYSTEM STATS ===
     render_system_stats(stdout, app, &mem_info, width)?;
@@ -69,18 +120,31 @@ fn render_main_view(
     // === FILTER BAR ===
     render_filter_bar(stdout, app, width)?;
 
+    // The path gets its own footer line only if the terminal can spare it
+    let path_line = app.path_line && height >= PATH_LINE_MIN_HEIGHT;
+    let header_lines = 5 + usize::from(commit_warning);
+    let footer_lines = if path_line { 3 } else { 2 } + usize::from(app.debug_line);
+    let visible_rows = height.saturating_sub(header_lines + footer_lines);
+
+    // Fit the columns to the rows on screen, for the headers and rows alike
+    scroll_into_view(app, visible_rows);
+    let rows = visible_row_values(app, visible_rows);
+    let widths = column_widths(app.compact_rows, &rows);
+
     // === COLUMN HEADERS ===
-    render_column_headers(stdout, width)?;
+    render_column_headers(stdout, app, &widths, width)?;
 
     // === PROCESS LIST ===
-    let header_lines = 5;
-    let footer_lines = 2;
-    let visible_rows = height.saturating_sub(header_lines + footer_lines);
-
-    render_process_list(stdout, app, visible_rows, width)?;
+    render_process_list(stdout, app, &rows, &widths, visible_rows, width)?;
 
     // === FOOTER ===
-    render_footer(stdout, app, width)?;
+    if app.debug_line {
+        render_debug_line(stdout, app, width)?;
+    }
+    if path_line {
+        render_path_line(stdout, app, width)?;
+    }
+    render_footer(stdout, app, width, path_line)?;
 
     stdout.flush()
 }
@@ -0,0 +1,64 @@
+//! Sort column picker rendering
+
+use std::io::{self, Write};
+
+use crossterm::style::Color;
+
+use crate::app::{App, SortColumn};
+use crate::constants::{DIALOG_MARGIN, SORT_PICKER_DIALOG_WIDTH};
+
+use super::dialog::{draw_backdrop, Dialog};
+
+/// Renders the sort column picker.
+///
+/// Lists every sort column with the direction it would sort in, marks the
+/// current column and highlights the row under the cursor. The list scrolls
+/// when the terminal is too short to show it whole.
+pub fn render_sort_picker(
+    stdout: &mut io::Stdout,
+    app: &App,
+    width: usize,
+    height: usize,
+) -> io::Result<()> {
+    let columns = &SortColumn::ALL;
+    let selected = app.sort_picker_index.min(columns.len() - 1);
+
+    // Borders, title, separator and key hint take five lines
+    let visible = columns.len().min(height.saturating_sub(5 + 2)).max(1);
+    let offset = selected.saturating_sub(visible - 1);
+
+    let box_width = SORT_PICKER_DIALOG_WIDTH.min(width.saturating_sub(DIALOG_MARGIN));
+    let box_height = visible + 5;
+    let dialog = Dialog::centered(width, height, box_width, box_height);
+
+    draw_backdrop(stdout, width, height)?;
+
+    let mut y = dialog.top();
+    dialog.border(stdout, y, "┌", "┐")?;
+    y += 1;
+    dialog.line(stdout, y, " Sort By", Color::Yellow)?;
+    y += 1;
+    dialog.border(stdout, y, "├", "┤")?;
+    y += 1;
+
+    for (i, column) in columns.iter().enumerate().skip(offset).take(visible) {
+        let arrow = if app.sort_direction(*column) { "↑" } else { "↓" };
+        let marker = if *column == app.sort_column { "●" } else { " " };
+        let line = format!(" {} {:<10} {}", marker, column.name(), arrow);
+        let (fg, bg) = if i == selected {
+            (Color::Black, Color::Cyan)
+        } else if *column == app.sort_column {
+            (Color::Green, Color::DarkBlue)
+        } else {
+            (Color::White, Color::DarkBlue)
+        };
+        dialog.line_on(stdout, y, &line, fg, bg)?;
+        y += 1;
+    }
+
+    dialog.line(stdout, y, " ↑↓: Move  Enter: Sort  Esc: Close", Color::DarkGrey)?;
+    y += 1;
+    dialog.border(stdout, y, "└", "┘")?;
+
+    stdout.flush()
+}
@@ -0,0 +1,74 @@
+//! System information panel rendering
+
+use std::io::{self, Write};
+
+use crossterm::style::Color;
+
+use crate::app::App;
+use crate::constants::{APP_NAME, APP_VERSION, DIALOG_MARGIN, SYSINFO_DIALOG_WIDTH, SYSINFO_LABEL_WIDTH};
+use crate::system::{format_bytes, format_uptime};
+
+use super::dialog::{draw_backdrop, Dialog};
+
+/// Renders the system information panel.
+///
+/// Shows OS version, computer name, logical CPU count, total RAM,
+/// time since boot and the app version, as gathered when the panel
+/// was opened, plus how long this session has been monitoring and how
+/// many refreshes it has sampled.
+pub fn render_sys_info_panel(
+    stdout: &mut io::Stdout,
+    app: &App,
+    width: usize,
+    height: usize,
+) -> io::Result<()> {
+    let unknown = || "Unknown".to_string();
+    let monitoring = format!(
+        "{}, {} samples",
+        format_uptime(app.monitoring_seconds()),
+        app.refresh_count
+    );
+    let rows: Vec<(&str, String)> = match app.sys_info.as_ref() {
+        Some(info) => vec![
+            ("OS", info.os_version.clone().unwrap_or_else(unknown)),
+            ("Computer", info.computer_name.clone().unwrap_or_else(unknown)),
+            ("Logical CPUs", info.logical_cpus.to_string()),
+            ("Memory", format_bytes(info.total_memory, app.units)),
+            ("Uptime", format_uptime(info.uptime_seconds)),
+            ("Monitoring", monitoring),
+            ("Version", format!("{} {}", APP_NAME, APP_VERSION)),
+        ],
+        None => vec![
+            ("Monitoring", monitoring),
+            ("Version", format!("{} {}", APP_NAME, APP_VERSION)),
+        ],
+    };
+
+    let box_width = SYSINFO_DIALOG_WIDTH.min(width.saturating_sub(DIALOG_MARGIN));
+    let box_height = rows.len() + 6; // borders, title, separator, padding
+    let dialog = Dialog::centered(width, height, box_width, box_height);
+
+    draw_backdrop(stdout, width, height)?;
+
+    let mut y = dialog.top();
+    dialog.border(stdout, y, "┌", "┐")?;
+    y += 1;
+    dialog.line(stdout, y, " System Information", Color::Yellow)?;
+    y += 1;
+    dialog.border(stdout, y, "├", "┤")?;
+    y += 1;
+    dialog.line(stdout, y, "", Color::White)?;
+    y += 1;
+
+    for (label, value) in &rows {
+        let line = format!(" {:<lw$}{}", label, value, lw = SYSINFO_LABEL_WIDTH);
+        dialog.line(stdout, y, &line, Color::White)?;
+        y += 1;
+    }
+
+    dialog.line(stdout, y, " Esc/Enter/F1: Close", Color::DarkGrey)?;
+    y += 1;
+    dialog.border(stdout, y, "└", "┘")?;
+
+    stdout.flush()
+}
@@ -1,55 +1,138 @@
 //! Utility functions for UI rendering
 
 use crossterm::style::Color;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::constants::{
-    BYTES_PER_KB, BYTES_PER_MB, BYTES_PER_GB,
     CPU_THRESHOLD_CRITICAL, CPU_THRESHOLD_WARNING, CPU_THRESHOLD_MODERATE,
 };
+use crate::system::{format_rate, ByteUnits};
+
+/// Placeholder for metrics that could not be read (access denied),
+/// so they are not mistaken for genuine zeros
+pub const UNAVAILABLE: &str = "—";
+
+/// Returns the number of terminal columns a string occupies.
+///
+/// Wide characters (CJK, most emoji) count as two columns and
+/// combining characters count as zero.
+#[must_use]
+pub fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
 
 /// Truncates a string to fit within a given width.
 ///
-/// If the string exceeds `max_len`, it is truncated and "..." is appended.
+/// If the string exceeds `max_len` display columns, it is cut on a
+/// character boundary and "..." is appended.
 ///
 /// # Arguments
 /// * `s` - The string to truncate
-/// * `max_len` - Maximum character length for the output
+/// * `max_len` - Maximum display width (in columns) for the output
 ///
 /// # Returns
 /// The original string if it fits, or a truncated version with "..." suffix
 #[must_use]
 pub fn truncate_string(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else if max_len > 3 {
-        format!("{}...", &s[..max_len - 3])
+    if display_width(s) <= max_len {
+        return s.to_string();
+    }
+
+    let (budget, suffix) = if max_len > 3 {
+        (max_len - 3, "...")
     } else {
-        s[..max_len].to_string()
+        (max_len, "")
+    };
+
+    let mut result = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = UnicodeWidthChar::width(c).unwrap_or(0);
+        if used + w > budget {
+            break;
+        }
+        result.push(c);
+        used += w;
     }
+    result.push_str(suffix);
+    result
 }
 
-/// Formats a byte rate (bytes/sec) as a human-readable string.
+/// Formats a signed byte rate with an explicit sign, e.g. "+1.2 MB/s".
 ///
-/// Automatically selects appropriate unit (B/s, KB/s, MB/s, GB/s).
+/// Used for growth rates, where shrinking values are as meaningful as
+/// growing ones.
 ///
 /// # Arguments
-/// * `bytes_per_sec` - Transfer rate in bytes per second
+/// * `bytes_per_sec` - Change rate in bytes per second (may be negative)
+/// * `units` - Binary (KiB) or decimal (KB) units
 ///
 /// # Returns
-/// Formatted string with appropriate unit suffix
+/// Formatted string, or "0 B/s" for changes under one byte per second
 #[must_use]
-pub fn format_rate(bytes_per_sec: f64) -> String {
-    if bytes_per_sec < 1.0 {
-        "0 B/s".to_string()
-    } else if bytes_per_sec < BYTES_PER_KB {
-        format!("{:.0} B/s", bytes_per_sec)
-    } else if bytes_per_sec < BYTES_PER_MB {
-        format!("{:.1} KB/s", bytes_per_sec / BYTES_PER_KB)
-    } else if bytes_per_sec < BYTES_PER_GB {
-        format!("{:.1} MB/s", bytes_per_sec / BYTES_PER_MB)
-    } else {
-        format!("{:.1} GB/s", bytes_per_sec / BYTES_PER_GB)
+pub fn format_growth_rate(bytes_per_sec: f64, units: ByteUnits) -> String {
+    if bytes_per_sec.abs() < 1.0 {
+        return "0 B/s".to_string();
     }
+    let sign = if bytes_per_sec < 0.0 { '-' } else { '+' };
+    format!("{}{}", sign, format_rate(bytes_per_sec.abs(), units))
+}
+
+/// Block characters for sparklines, from empty to full
+const SPARK_LEVELS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Renders percentage samples as a block-character sparkline.
+///
+/// The range is fixed at 0–100% so heights are comparable over time.
+/// Only the most recent `width` samples are drawn, right-aligned.
+///
+/// # Arguments
+/// * `samples` - Percentages, oldest first
+/// * `width` - Number of columns available
+///
+/// # Returns
+/// A string exactly `width` columns wide
+#[must_use]
+pub fn sparkline<'a, I>(samples: I, width: usize) -> String
+where
+    I: IntoIterator<Item = &'a f64>,
+    I::IntoIter: ExactSizeIterator,
+{
+    let samples = samples.into_iter();
+    let skip = samples.len().saturating_sub(width);
+    let bars: String = samples
+        .skip(skip)
+        .map(|&p| {
+            let level = (p.clamp(0.0, 100.0) / 100.0 * 8.0).round() as usize;
+            SPARK_LEVELS[level]
+        })
+        .collect();
+
+    format!("{:>width$}", bars, width = width)
+}
+
+/// Computes the thumb of a vertical scrollbar for a scrolled list.
+///
+/// The track is as tall as the visible window; the thumb's length is
+/// proportional to the visible fraction (at least one row), and it sits
+/// at the top for offset 0 and at the bottom for the last page.
+///
+/// # Arguments
+/// * `total` - Number of items in the list
+/// * `visible` - Number of rows shown at once (the track height)
+/// * `offset` - Index of the first visible item
+///
+/// # Returns
+/// `Some((start, len))` in track rows, or None when everything fits
+#[must_use]
+pub fn scrollbar_thumb(total: usize, visible: usize, offset: usize) -> Option<(usize, usize)> {
+    if visible == 0 || total <= visible {
+        return None;
+    }
+    let len = (visible * visible / total).max(1);
+    let max_offset = total - visible;
+    let start = offset.min(max_offset) * (visible - len) / max_offset;
+    Some((start, len))
 }
 
 /// Returns a color based on CPU usage percentage for visual indication.
@@ -74,3 +157,72 @@ pub fn cpu_color(percent: f64) -> Color {
         Color::Green
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::BYTES_PER_MB;
+
+    #[test]
+    fn test_truncate_ascii() {
+        assert_eq!(truncate_string("notepad.exe", 20), "notepad.exe");
+        assert_eq!(truncate_string("notepad.exe", 8), "notep...");
+        assert_eq!(truncate_string("notepad.exe", 3), "not");
+    }
+
+    #[test]
+    fn test_truncate_cjk() {
+        // Each CJK character is two columns wide
+        let name = "日本語のプロセス.exe";
+        let truncated = truncate_string(name, 9);
+        assert_eq!(truncated, "日本語...");
+        assert!(display_width(&truncated) <= 9);
+
+        // Never splits a wide character in half
+        assert_eq!(truncate_string("日本語", 5), "日...");
+    }
+
+    #[test]
+    fn test_truncate_combining() {
+        // "e" + combining acute accent renders as a single column
+        let name = "cafe\u{301}-service.exe";
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(truncate_string(name, 7), "cafe\u{301}...");
+    }
+
+    #[test]
+    fn test_format_growth_rate() {
+        let units = ByteUnits::Binary;
+        assert_eq!(format_growth_rate(0.4, units), "0 B/s");
+        assert_eq!(format_growth_rate(-0.4, units), "0 B/s");
+        assert_eq!(format_growth_rate(512.0, units), "+512 B/s");
+        assert_eq!(format_growth_rate(-1.5 * BYTES_PER_MB, units), "-1.5 MiB/s");
+        assert_eq!(format_growth_rate(2500.0, ByteUnits::Decimal), "+2.5 KB/s");
+    }
+
+    #[test]
+    fn test_scrollbar_thumb() {
+        assert_eq!(scrollbar_thumb(10, 20, 0), None);
+        assert_eq!(scrollbar_thumb(200, 20, 0), Some((0, 2)));
+        assert_eq!(scrollbar_thumb(200, 20, 180), Some((18, 2)));
+        assert_eq!(scrollbar_thumb(200, 20, 90), Some((9, 2)));
+        // Very long lists still get a one-row thumb
+        assert_eq!(scrollbar_thumb(10_000, 20, 9_980), Some((19, 1)));
+    }
+
+    #[test]
+    fn test_sparkline() {
+        let samples = [0.0, 50.0, 100.0, 150.0];
+        assert_eq!(sparkline(&samples, 4), " ▄██");
+        // Only the newest samples fit; short histories are right-aligned
+        assert_eq!(sparkline(&samples, 2), "██");
+        assert_eq!(sparkline(&samples[..1], 3), "   ");
+        assert_eq!(sparkline(&samples[1..2], 3), "  ▄");
+    }
+
+    #[test]
+    fn test_truncate_emoji() {
+        let truncated = truncate_string("🚀🚀🚀🚀 launcher", 7);
+        assert_eq!(truncated, "🚀🚀...");
+    }
+}